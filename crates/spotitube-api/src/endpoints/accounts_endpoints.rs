@@ -0,0 +1,109 @@
+use axum::{
+    extract::{
+        rejection::{PathRejection, QueryRejection},
+        Path, Query,
+    },
+    http::StatusCode,
+    routing::{delete, get, post},
+    Extension, Json, Router,
+};
+use spotitube_core::{
+    accounts::service::DynAccountsService,
+    errors::{SpotitubeError, SpotitubeResult},
+};
+use spotitube_domain::{
+    accounts::{
+        requests::{LinkAccountRequest, OAuthCallbackQuery},
+        responses::{LinkAccountResponse, LinkedAccountResponse, LinkedAccountsResponse},
+    },
+    providers::Provider,
+};
+use spotitube_infrastructure::service_register::ServiceRegister;
+use tracing::{info, warn};
+
+use crate::extractors::{
+    authentication_extractor::RequiredAuthentication, validation_extractor::ValidationExtractor,
+};
+
+pub struct AccountsRouter;
+
+impl AccountsRouter {
+    pub fn new_router(service_register: &ServiceRegister) -> Router {
+        Router::new()
+            .route("/accounts", get(AccountsRouter::list_accounts_endpoint))
+            .route("/accounts", post(AccountsRouter::link_account_endpoint))
+            .route(
+                "/accounts/callback",
+                get(AccountsRouter::link_account_callback_endpoint),
+            )
+            .route(
+                "/accounts/:provider",
+                delete(AccountsRouter::unlink_account_endpoint),
+            )
+            .layer(Extension(service_register.accounts_service.clone()))
+            .layer(Extension(service_register.users_service.clone()))
+            .layer(Extension(service_register.token_service.clone()))
+    }
+
+    pub async fn list_accounts_endpoint(
+        Extension(accounts_service): Extension<DynAccountsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+    ) -> SpotitubeResult<Json<LinkedAccountsResponse>> {
+        info!("received request to list accounts of user {:?}", user_id);
+        let accounts = accounts_service.list_accounts(&user_id).await?;
+        Ok(Json(LinkedAccountsResponse { accounts }))
+    }
+
+    pub async fn link_account_endpoint(
+        Extension(accounts_service): Extension<DynAccountsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        ValidationExtractor(request): ValidationExtractor<LinkAccountRequest>,
+    ) -> SpotitubeResult<Json<LinkAccountResponse>> {
+        let provider = request.provider.unwrap();
+        info!(
+            "received request to link {} account to user {:?}",
+            provider, user_id
+        );
+        let authorization_url = accounts_service.begin_link(&user_id, provider).await?;
+        Ok(Json(LinkAccountResponse { authorization_url }))
+    }
+
+    /// Redirect target of the provider consent screen; the user is identified by `state`.
+    pub async fn link_account_callback_endpoint(
+        Extension(accounts_service): Extension<DynAccountsService>,
+        query: Result<Query<OAuthCallbackQuery>, QueryRejection>,
+    ) -> SpotitubeResult<Json<LinkedAccountResponse>> {
+        let Query(query) = query?;
+        if let Some(error) = query.error {
+            warn!("provider denied account linking: {}", error);
+            return Err(SpotitubeError::BadRequest(format!(
+                "authorization was denied: {}",
+                error
+            )));
+        }
+
+        let (Some(state), Some(code)) = (query.state, query.code) else {
+            return Err(SpotitubeError::BadRequest(String::from(
+                "code and state are required",
+            )));
+        };
+
+        info!("received account linking callback");
+        let account = accounts_service.complete_link(&state, &code).await?;
+        Ok(Json(LinkedAccountResponse { account }))
+    }
+
+    pub async fn unlink_account_endpoint(
+        Extension(accounts_service): Extension<DynAccountsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        provider: Result<Path<Provider>, PathRejection>,
+    ) -> SpotitubeResult<StatusCode> {
+        let Path(provider) = provider?;
+        info!(
+            "received request to unlink {} account from user {:?}",
+            provider, user_id
+        );
+        accounts_service.unlink(&user_id, provider).await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+}
@@ -0,0 +1,300 @@
+use std::pin::pin;
+
+use axum::{
+    extract::{
+        rejection::{PathRejection, QueryRejection},
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        Path, Query,
+    },
+    http::{HeaderMap, HeaderName, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        Response,
+    },
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use futures_util::{stream, Stream, StreamExt};
+use spotitube_core::{
+    conversions::{
+        review_service::DynConversionReviewService,
+        service::{ConversionEventStream, DynConversionsService},
+    },
+    errors::SpotitubeResult,
+};
+use spotitube_domain::{
+    conversions::{
+        requests::{
+            AcceptCandidateRequest, ConversionEventsQuery, CreateConversionRequest,
+            ManualMatchRequest, TrackReviewsQuery,
+        },
+        responses::{ConversionResponse, ConversionTrackResponse, TrackReviewsResponse},
+        ConversionEventDto,
+    },
+    providers::Provider,
+};
+use spotitube_infrastructure::service_register::ServiceRegister;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::extractors::{
+    authentication_extractor::RequiredAuthentication, validation_extractor::ValidationExtractor,
+};
+
+const LAST_EVENT_ID: HeaderName = HeaderName::from_static("last-event-id");
+
+pub struct ConversionsRouter;
+
+impl ConversionsRouter {
+    pub fn new_router(service_register: &ServiceRegister) -> Router {
+        Router::new()
+            .route(
+                "/conversions",
+                post(ConversionsRouter::create_conversion_endpoint),
+            )
+            .route(
+                "/conversions/:id",
+                get(ConversionsRouter::get_conversion_endpoint),
+            )
+            .route(
+                "/conversions/:id/events",
+                get(ConversionsRouter::conversion_events_endpoint),
+            )
+            .route(
+                "/conversions/:id/events/ws",
+                get(ConversionsRouter::conversion_events_socket_endpoint),
+            )
+            .route(
+                "/conversions/:id/reviews",
+                get(ConversionsRouter::list_reviews_endpoint),
+            )
+            .route(
+                "/conversions/:id/reviews/:position/accept",
+                post(ConversionsRouter::accept_candidate_endpoint),
+            )
+            .route(
+                "/conversions/:id/reviews/:position/reject",
+                post(ConversionsRouter::reject_candidates_endpoint),
+            )
+            .route(
+                "/conversions/:id/reviews/:position/manual",
+                post(ConversionsRouter::manual_match_endpoint),
+            )
+            .layer(Extension(service_register.conversions_service.clone()))
+            .layer(Extension(
+                service_register.conversion_review_service.clone(),
+            ))
+            .layer(Extension(service_register.users_service.clone()))
+            .layer(Extension(service_register.token_service.clone()))
+    }
+
+    pub async fn create_conversion_endpoint(
+        Extension(conversions_service): Extension<DynConversionsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        ValidationExtractor(request): ValidationExtractor<CreateConversionRequest>,
+    ) -> SpotitubeResult<(StatusCode, Json<ConversionResponse>)> {
+        let source_provider = request.source_provider.unwrap_or(Provider::Spotify);
+        let source_playlist_id = request.source_playlist_id.unwrap();
+        info!(
+            "received request to convert {} playlist {:?} for user {:?}",
+            source_provider, source_playlist_id, user_id
+        );
+        let conversion = conversions_service
+            .create_conversion(
+                &user_id,
+                source_provider,
+                &source_playlist_id,
+                request.name.as_deref(),
+            )
+            .await?;
+        Ok((
+            StatusCode::ACCEPTED,
+            Json(ConversionResponse { conversion }),
+        ))
+    }
+
+    pub async fn get_conversion_endpoint(
+        Extension(conversions_service): Extension<DynConversionsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        id: Result<Path<Uuid>, PathRejection>,
+    ) -> SpotitubeResult<Json<ConversionResponse>> {
+        let Path(id) = id?;
+        info!("received request to retrieve conversion {:?}", id);
+        let conversion = conversions_service.get_conversion(&user_id, &id).await?;
+        Ok(Json(ConversionResponse { conversion }))
+    }
+
+    pub async fn conversion_events_endpoint(
+        Extension(conversions_service): Extension<DynConversionsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        id: Result<Path<Uuid>, PathRejection>,
+        headers: HeaderMap,
+    ) -> SpotitubeResult<Sse<impl Stream<Item = Result<Event, axum::Error>>>> {
+        let Path(id) = id?;
+        let last_event_id = headers
+            .get(LAST_EVENT_ID)
+            .and_then(|value| value.to_str().ok());
+        info!(
+            "received request to stream events of conversion {:?} after {:?}",
+            id, last_event_id
+        );
+        let events = conversions_service
+            .watch_conversion(&user_id, &id, last_event_id)
+            .await?;
+
+        let stream = Self::event_stream(events).map(|event| {
+            let sse_event = Event::default().event(event.event.name());
+            match &event.id {
+                Some(event_id) => sse_event.id(event_id),
+                None => sse_event,
+            }
+            .json_data(&event)
+        });
+        Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+    }
+
+    pub async fn conversion_events_socket_endpoint(
+        Extension(conversions_service): Extension<DynConversionsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        id: Result<Path<Uuid>, PathRejection>,
+        query: Result<Query<ConversionEventsQuery>, QueryRejection>,
+        headers: HeaderMap,
+        upgrade: WebSocketUpgrade,
+    ) -> SpotitubeResult<Response> {
+        let Path(id) = id?;
+        let Query(query) = query?;
+        let last_event_id = query.last_event_id.as_deref().or_else(|| {
+            headers
+                .get(LAST_EVENT_ID)
+                .and_then(|value| value.to_str().ok())
+        });
+        info!(
+            "received request to stream events of conversion {:?} over a websocket after {:?}",
+            id, last_event_id
+        );
+        let events = conversions_service
+            .watch_conversion(&user_id, &id, last_event_id)
+            .await?;
+
+        Ok(upgrade.on_upgrade(move |socket| Self::send_events(socket, events)))
+    }
+
+    /// Events of a conversion in the order they happened, ending once it has finished or when
+    /// the subscriber fell too far behind and has to resume from its last event id.
+    fn event_stream(events: ConversionEventStream) -> impl Stream<Item = ConversionEventDto> {
+        let finished = events.initial.iter().any(|event| event.event.is_final());
+        let receiver = events.receiver.filter(|_| !finished);
+
+        stream::iter(events.initial).chain(stream::unfold(receiver, |receiver| async move {
+            let mut receiver = receiver?;
+            match receiver.recv().await {
+                Ok(event) => {
+                    let receiver = (!event.event.is_final()).then_some(receiver);
+                    Some((event, receiver))
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("event subscriber lagged behind by {} events", skipped);
+                    None
+                }
+                Err(RecvError::Closed) => None,
+            }
+        }))
+    }
+
+    async fn send_events(mut socket: WebSocket, events: ConversionEventStream) {
+        let mut events = pin!(Self::event_stream(events));
+        loop {
+            tokio::select! {
+                event = events.next() => {
+                    let Some(event) = event else {
+                        break;
+                    };
+                    let message = match serde_json::to_string(&event) {
+                        Ok(message) => message,
+                        Err(err) => {
+                            error!("failed to serialize conversion event: {:?}", err);
+                            break;
+                        }
+                    };
+                    if socket.send(Message::Text(message)).await.is_err() {
+                        return;
+                    }
+                }
+                message = socket.recv() => match message {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+                    Some(Ok(_)) => {}
+                },
+            }
+        }
+
+        let _ = socket.close().await;
+    }
+
+    pub async fn list_reviews_endpoint(
+        Extension(review_service): Extension<DynConversionReviewService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        id: Result<Path<Uuid>, PathRejection>,
+        query: Result<Query<TrackReviewsQuery>, QueryRejection>,
+    ) -> SpotitubeResult<Json<TrackReviewsResponse>> {
+        let Path(id) = id?;
+        let Query(query) = query?;
+        info!("received request to list reviews of conversion {:?}", id);
+        let reviews = review_service
+            .list_reviews(&user_id, &id, query.candidates)
+            .await?;
+        Ok(Json(TrackReviewsResponse { reviews }))
+    }
+
+    pub async fn accept_candidate_endpoint(
+        Extension(review_service): Extension<DynConversionReviewService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        path: Result<Path<(Uuid, i32)>, PathRejection>,
+        ValidationExtractor(request): ValidationExtractor<AcceptCandidateRequest>,
+    ) -> SpotitubeResult<Json<ConversionTrackResponse>> {
+        let Path((id, position)) = path?;
+        let candidate_id = request.candidate_id.unwrap();
+        info!(
+            "received request to accept candidate {:?} for track {} of conversion {:?}",
+            candidate_id, position, id
+        );
+        let track = review_service
+            .accept_candidate(&user_id, &id, position, &candidate_id)
+            .await?;
+        Ok(Json(ConversionTrackResponse { track }))
+    }
+
+    pub async fn reject_candidates_endpoint(
+        Extension(review_service): Extension<DynConversionReviewService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        path: Result<Path<(Uuid, i32)>, PathRejection>,
+    ) -> SpotitubeResult<Json<ConversionTrackResponse>> {
+        let Path((id, position)) = path?;
+        info!(
+            "received request to reject candidates for track {} of conversion {:?}",
+            position, id
+        );
+        let track = review_service
+            .reject_candidates(&user_id, &id, position)
+            .await?;
+        Ok(Json(ConversionTrackResponse { track }))
+    }
+
+    pub async fn manual_match_endpoint(
+        Extension(review_service): Extension<DynConversionReviewService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        path: Result<Path<(Uuid, i32)>, PathRejection>,
+        ValidationExtractor(request): ValidationExtractor<ManualMatchRequest>,
+    ) -> SpotitubeResult<Json<ConversionTrackResponse>> {
+        let Path((id, position)) = path?;
+        let destination_id = request.destination_id.unwrap();
+        info!(
+            "received request to match track {} of conversion {:?} to {:?}",
+            position, id, destination_id
+        );
+        let track = review_service
+            .set_manual_match(&user_id, &id, position, &destination_id)
+            .await?;
+        Ok(Json(ConversionTrackResponse { track }))
+    }
+}
@@ -0,0 +1,19 @@
+use axum::{routing::get, Extension, Json, Router};
+use jsonwebtoken::jwk::JwkSet;
+use spotitube_core::utils::token_service::DynTokenService;
+
+pub struct JwksRouter;
+
+impl JwksRouter {
+    pub fn new_router(token_service: DynTokenService) -> Router {
+        Router::new()
+            .route("/.well-known/jwks.json", get(JwksRouter::jwks_endpoint))
+            .layer(Extension(token_service))
+    }
+
+    pub async fn jwks_endpoint(
+        Extension(token_service): Extension<DynTokenService>,
+    ) -> Json<JwkSet> {
+        Json(token_service.jwk_set())
+    }
+}
@@ -1 +1,6 @@
+pub mod accounts_endpoints;
+pub mod conversions_endpoints;
+pub mod jwks_endpoints;
+pub mod snapshots_endpoints;
+pub mod syncs_endpoints;
 pub mod users_endpoints;
@@ -0,0 +1,123 @@
+use axum::{
+    extract::{
+        rejection::{PathRejection, QueryRejection},
+        Path, Query,
+    },
+    http::StatusCode,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use spotitube_core::{errors::SpotitubeResult, snapshots::service::DynSnapshotsService};
+use spotitube_domain::snapshots::{
+    requests::{CreateSnapshotRequest, PlaylistSnapshotsQuery},
+    responses::{PlaylistSnapshotResponse, PlaylistSnapshotsResponse, SnapshotDiffResponse},
+};
+use spotitube_infrastructure::service_register::ServiceRegister;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::extractors::{
+    authentication_extractor::RequiredAuthentication, validation_extractor::ValidationExtractor,
+};
+
+pub struct SnapshotsRouter;
+
+impl SnapshotsRouter {
+    pub fn new_router(service_register: &ServiceRegister) -> Router {
+        Router::new()
+            .route(
+                "/snapshots",
+                post(SnapshotsRouter::create_snapshot_endpoint)
+                    .get(SnapshotsRouter::list_snapshots_endpoint),
+            )
+            .route(
+                "/snapshots/:id",
+                get(SnapshotsRouter::get_snapshot_endpoint),
+            )
+            .route(
+                "/snapshots/:id/diff/:other_id",
+                get(SnapshotsRouter::diff_snapshots_endpoint),
+            )
+            .route(
+                "/snapshots/:id/restore",
+                post(SnapshotsRouter::restore_snapshot_endpoint),
+            )
+            .layer(Extension(service_register.snapshots_service.clone()))
+            .layer(Extension(service_register.users_service.clone()))
+            .layer(Extension(service_register.token_service.clone()))
+    }
+
+    pub async fn create_snapshot_endpoint(
+        Extension(snapshots_service): Extension<DynSnapshotsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        ValidationExtractor(request): ValidationExtractor<CreateSnapshotRequest>,
+    ) -> SpotitubeResult<(StatusCode, Json<PlaylistSnapshotResponse>)> {
+        let provider = request.provider.unwrap();
+        let playlist_id = request.playlist_id.unwrap();
+        info!(
+            "received request to snapshot {} playlist {:?} for user {:?}",
+            provider, playlist_id, user_id
+        );
+        let snapshot = snapshots_service
+            .create_snapshot(&user_id, provider, &playlist_id)
+            .await?;
+        Ok((
+            StatusCode::CREATED,
+            Json(PlaylistSnapshotResponse { snapshot }),
+        ))
+    }
+
+    pub async fn list_snapshots_endpoint(
+        Extension(snapshots_service): Extension<DynSnapshotsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        query: Result<Query<PlaylistSnapshotsQuery>, QueryRejection>,
+    ) -> SpotitubeResult<Json<PlaylistSnapshotsResponse>> {
+        let Query(query) = query?;
+        info!(
+            "received request to list versions of {} playlist {:?}",
+            query.provider, query.playlist_id
+        );
+        let snapshots = snapshots_service
+            .list_snapshots(&user_id, query.provider, &query.playlist_id)
+            .await?;
+        Ok(Json(PlaylistSnapshotsResponse { snapshots }))
+    }
+
+    pub async fn get_snapshot_endpoint(
+        Extension(snapshots_service): Extension<DynSnapshotsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        id: Result<Path<Uuid>, PathRejection>,
+    ) -> SpotitubeResult<Json<PlaylistSnapshotResponse>> {
+        let Path(id) = id?;
+        info!("received request to retrieve snapshot {:?}", id);
+        let snapshot = snapshots_service.get_snapshot(&user_id, &id).await?;
+        Ok(Json(PlaylistSnapshotResponse { snapshot }))
+    }
+
+    pub async fn diff_snapshots_endpoint(
+        Extension(snapshots_service): Extension<DynSnapshotsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        ids: Result<Path<(Uuid, Uuid)>, PathRejection>,
+    ) -> SpotitubeResult<Json<SnapshotDiffResponse>> {
+        let Path((id, other_id)) = ids?;
+        info!(
+            "received request to diff snapshot {:?} with {:?}",
+            id, other_id
+        );
+        let diff = snapshots_service
+            .diff_snapshots(&user_id, &id, &other_id)
+            .await?;
+        Ok(Json(SnapshotDiffResponse { diff }))
+    }
+
+    pub async fn restore_snapshot_endpoint(
+        Extension(snapshots_service): Extension<DynSnapshotsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        id: Result<Path<Uuid>, PathRejection>,
+    ) -> SpotitubeResult<Json<PlaylistSnapshotResponse>> {
+        let Path(id) = id?;
+        info!("received request to restore snapshot {:?}", id);
+        let snapshot = snapshots_service.restore_snapshot(&user_id, &id).await?;
+        Ok(Json(PlaylistSnapshotResponse { snapshot }))
+    }
+}
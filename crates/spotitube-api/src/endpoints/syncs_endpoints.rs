@@ -0,0 +1,123 @@
+use axum::{
+    extract::{rejection::PathRejection, Path},
+    http::StatusCode,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use spotitube_core::{errors::SpotitubeResult, syncs::service::DynSyncsService};
+use spotitube_domain::syncs::{
+    requests::CreatePlaylistSyncRequest,
+    responses::{PlaylistSyncResponse, PlaylistSyncsResponse, SyncConflictsResponse},
+};
+use spotitube_infrastructure::service_register::ServiceRegister;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::extractors::{
+    authentication_extractor::RequiredAuthentication, validation_extractor::ValidationExtractor,
+};
+
+pub struct SyncsRouter;
+
+impl SyncsRouter {
+    pub fn new_router(service_register: &ServiceRegister) -> Router {
+        Router::new()
+            .route(
+                "/syncs",
+                post(SyncsRouter::create_sync_endpoint).get(SyncsRouter::list_syncs_endpoint),
+            )
+            .route(
+                "/syncs/:id",
+                get(SyncsRouter::get_sync_endpoint).delete(SyncsRouter::delete_sync_endpoint),
+            )
+            .route("/syncs/:id/run", post(SyncsRouter::request_sync_endpoint))
+            .route(
+                "/syncs/:id/conflicts",
+                get(SyncsRouter::list_conflicts_endpoint),
+            )
+            .layer(Extension(service_register.syncs_service.clone()))
+            .layer(Extension(service_register.users_service.clone()))
+            .layer(Extension(service_register.token_service.clone()))
+    }
+
+    pub async fn create_sync_endpoint(
+        Extension(syncs_service): Extension<DynSyncsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        ValidationExtractor(request): ValidationExtractor<CreatePlaylistSyncRequest>,
+    ) -> SpotitubeResult<(StatusCode, Json<PlaylistSyncResponse>)> {
+        let spotify_playlist_id = request.spotify_playlist_id.unwrap();
+        let youtube_playlist_id = request.youtube_playlist_id.unwrap();
+        info!(
+            "received request to sync spotify playlist {:?} with youtube playlist {:?} for user {:?}",
+            spotify_playlist_id, youtube_playlist_id, user_id
+        );
+        let sync = syncs_service
+            .create_sync(
+                &user_id,
+                &spotify_playlist_id,
+                &youtube_playlist_id,
+                request.interval_seconds,
+            )
+            .await?;
+        Ok((StatusCode::CREATED, Json(PlaylistSyncResponse { sync })))
+    }
+
+    pub async fn list_syncs_endpoint(
+        Extension(syncs_service): Extension<DynSyncsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+    ) -> SpotitubeResult<Json<PlaylistSyncsResponse>> {
+        info!(
+            "received request to list playlist syncs of user {:?}",
+            user_id
+        );
+        let syncs = syncs_service.list_syncs(&user_id).await?;
+        Ok(Json(PlaylistSyncsResponse { syncs }))
+    }
+
+    pub async fn get_sync_endpoint(
+        Extension(syncs_service): Extension<DynSyncsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        id: Result<Path<Uuid>, PathRejection>,
+    ) -> SpotitubeResult<Json<PlaylistSyncResponse>> {
+        let Path(id) = id?;
+        info!("received request to retrieve playlist sync {:?}", id);
+        let sync = syncs_service.get_sync(&user_id, &id).await?;
+        Ok(Json(PlaylistSyncResponse { sync }))
+    }
+
+    pub async fn delete_sync_endpoint(
+        Extension(syncs_service): Extension<DynSyncsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        id: Result<Path<Uuid>, PathRejection>,
+    ) -> SpotitubeResult<StatusCode> {
+        let Path(id) = id?;
+        info!("received request to delete playlist sync {:?}", id);
+        syncs_service.delete_sync(&user_id, &id).await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    pub async fn request_sync_endpoint(
+        Extension(syncs_service): Extension<DynSyncsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        id: Result<Path<Uuid>, PathRejection>,
+    ) -> SpotitubeResult<(StatusCode, Json<PlaylistSyncResponse>)> {
+        let Path(id) = id?;
+        info!("received request to run playlist sync {:?} now", id);
+        let sync = syncs_service.request_sync(&user_id, &id).await?;
+        Ok((StatusCode::ACCEPTED, Json(PlaylistSyncResponse { sync })))
+    }
+
+    pub async fn list_conflicts_endpoint(
+        Extension(syncs_service): Extension<DynSyncsService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        id: Result<Path<Uuid>, PathRejection>,
+    ) -> SpotitubeResult<Json<SyncConflictsResponse>> {
+        let Path(id) = id?;
+        info!(
+            "received request to list conflicts of playlist sync {:?}",
+            id
+        );
+        let conflicts = syncs_service.list_conflicts(&user_id, &id).await?;
+        Ok(Json(SyncConflictsResponse { conflicts }))
+    }
+}
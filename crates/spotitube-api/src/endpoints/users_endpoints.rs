@@ -1,26 +1,41 @@
 use axum::{
-    debug_handler,
-    routing::{get, post},
-    Extension, Form, Json, Router,
+    http::StatusCode,
+    routing::{get, post, put},
+    Extension, Json, Router,
+};
+use spotitube_core::{
+    auth::service::DynAuthService, errors::SpotitubeResult, users::service::DynUsersService,
 };
-use spotitube_core::{errors::SpotitubeResult, users::service::DynUsersService};
 use spotitube_domain::users::{
-    requests::{LoginUserRequest, RegisterUserRequest},
+    requests::{LoginUserRequest, RefreshTokenRequest, RegisterUserRequest, UpdateUserRequest},
     responses::UserAuthResponse,
 };
 use spotitube_infrastructure::service_register::ServiceRegister;
 use tracing::info;
 
-use crate::extractors::validation_extractor::ValidationExtractor;
+use crate::extractors::{
+    authentication_extractor::{AuthenticatedUser, RequiredAuthentication},
+    validation_extractor::ValidationExtractor,
+};
 
 pub struct UsersRouter;
 
 impl UsersRouter {
-    pub fn new_router(service_register: ServiceRegister) -> Router {
+    pub fn new_router(service_register: &ServiceRegister) -> Router {
         Router::new()
-            .route("/auth/register", get(UsersRouter::register_user_endpoint))
+            // GET is still served for clients written before register moved to POST.
+            .route(
+                "/auth/register",
+                post(UsersRouter::register_user_endpoint).get(UsersRouter::register_user_endpoint),
+            )
             .route("/auth/login", post(UsersRouter::login_user_endpoint))
-            .layer(Extension(service_register.users_service))
+            .route("/auth/refresh", post(UsersRouter::refresh_token_endpoint))
+            .route("/auth/logout", post(UsersRouter::logout_user_endpoint))
+            .route("/user", get(UsersRouter::get_current_user_endpoint))
+            .route("/user", put(UsersRouter::update_current_user_endpoint))
+            .layer(Extension(service_register.users_service.clone()))
+            .layer(Extension(service_register.auth_service.clone()))
+            .layer(Extension(service_register.token_service.clone()))
     }
 
     pub async fn register_user_endpoint(
@@ -43,4 +58,41 @@ impl UsersRouter {
         let user = users_service.login_user(request.user).await?;
         Ok(Json(UserAuthResponse { user }))
     }
+
+    pub async fn refresh_token_endpoint(
+        Extension(auth_service): Extension<DynAuthService>,
+        ValidationExtractor(request): ValidationExtractor<RefreshTokenRequest>,
+    ) -> SpotitubeResult<Json<UserAuthResponse>> {
+        info!("received request to refresh access token");
+        let user = auth_service
+            .refresh(&request.refresh_token.unwrap())
+            .await?;
+        Ok(Json(UserAuthResponse { user }))
+    }
+
+    pub async fn logout_user_endpoint(
+        Extension(auth_service): Extension<DynAuthService>,
+        ValidationExtractor(request): ValidationExtractor<RefreshTokenRequest>,
+    ) -> SpotitubeResult<StatusCode> {
+        info!("received request to logout");
+        auth_service.logout(&request.refresh_token.unwrap()).await?;
+        Ok(StatusCode::NO_CONTENT)
+    }
+
+    pub async fn get_current_user_endpoint(
+        AuthenticatedUser(user): AuthenticatedUser,
+    ) -> SpotitubeResult<Json<UserAuthResponse>> {
+        info!("received request to retrieve current user {:?}", user.id);
+        Ok(Json(UserAuthResponse { user }))
+    }
+
+    pub async fn update_current_user_endpoint(
+        Extension(users_service): Extension<DynUsersService>,
+        RequiredAuthentication(user_id): RequiredAuthentication,
+        ValidationExtractor(request): ValidationExtractor<UpdateUserRequest>,
+    ) -> SpotitubeResult<Json<UserAuthResponse>> {
+        info!("received request to update user {:?}", user_id);
+        let user = users_service.update_user(&user_id, request.user).await?;
+        Ok(Json(UserAuthResponse { user }))
+    }
 }
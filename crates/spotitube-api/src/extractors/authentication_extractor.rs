@@ -0,0 +1,125 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{header::AUTHORIZATION, request::Parts, HeaderMap},
+    Extension,
+};
+use spotitube_core::{
+    errors::SpotitubeError, users::service::DynUsersService, utils::token_service::DynTokenService,
+};
+use spotitube_domain::users::UserDto;
+use tracing::error;
+use uuid::Uuid;
+
+const TOKEN_SCHEMES: [&str; 2] = ["Token", "Bearer"];
+
+/// Resolves the id of the caller from the `Authorization` header, rejecting anonymous requests.
+#[derive(Debug, Clone, Copy)]
+pub struct RequiredAuthentication(pub Uuid);
+
+/// Resolves the id of the caller if an `Authorization` header is present.
+#[derive(Debug, Clone, Copy)]
+pub struct OptionalAuthentication(pub Option<Uuid>);
+
+/// Resolves the full user record of the caller, rejecting anonymous requests.
+#[derive(Debug)]
+pub struct AuthenticatedUser(pub UserDto);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for RequiredAuthentication
+where
+    S: Send + Sync,
+{
+    type Rejection = SpotitubeError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let token = extract_token(&parts.headers)?.ok_or(SpotitubeError::Unauthorized)?;
+        let user_id = validate_token(parts, state, token).await?;
+        Ok(Self(user_id))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for OptionalAuthentication
+where
+    S: Send + Sync,
+{
+    type Rejection = SpotitubeError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        match extract_token(&parts.headers)? {
+            Some(token) => Ok(Self(Some(validate_token(parts, state, token).await?))),
+            None => Ok(Self(None)),
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AuthenticatedUser
+where
+    S: Send + Sync,
+{
+    type Rejection = SpotitubeError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let RequiredAuthentication(user_id) =
+            RequiredAuthentication::from_request_parts(parts, state).await?;
+        let Extension(users_service) =
+            Extension::<DynUsersService>::from_request_parts(parts, state)
+                .await
+                .map_err(|_| SpotitubeError::InternalServerError)?;
+
+        match users_service.get_user(&user_id).await {
+            Ok(user) => Ok(Self(user)),
+            Err(SpotitubeError::SqlxError(sqlx::Error::RowNotFound)) => {
+                error!("token refers to user {:?} which does not exist", user_id);
+                Err(SpotitubeError::Unauthorized)
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn extract_token(headers: &HeaderMap) -> Result<Option<String>, SpotitubeError> {
+    let Some(header) = headers.get(AUTHORIZATION) else {
+        return Ok(None);
+    };
+
+    let header = header.to_str().map_err(|_| SpotitubeError::Unauthorized)?;
+    let (scheme, token) = header.split_once(' ').ok_or(SpotitubeError::Unauthorized)?;
+
+    if !TOKEN_SCHEMES
+        .iter()
+        .any(|expected| expected.eq_ignore_ascii_case(scheme))
+    {
+        return Err(SpotitubeError::Unauthorized);
+    }
+
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(SpotitubeError::Unauthorized);
+    }
+
+    Ok(Some(String::from(token)))
+}
+
+async fn validate_token<S>(
+    parts: &mut Parts,
+    state: &S,
+    token: String,
+) -> Result<Uuid, SpotitubeError>
+where
+    S: Send + Sync,
+{
+    let Extension(token_service) = Extension::<DynTokenService>::from_request_parts(parts, state)
+        .await
+        .map_err(|_| SpotitubeError::InternalServerError)?;
+
+    token_service.get_user_id_from_token(token).map_err(|err| {
+        error!("failed to validate token: {:?}", err);
+        match err {
+            SpotitubeError::JwtError(_) => err,
+            _ => SpotitubeError::Unauthorized,
+        }
+    })
+}
@@ -1 +1,2 @@
+pub mod authentication_extractor;
 pub mod validation_extractor;
@@ -1,6 +1,11 @@
 use axum::{
     async_trait,
-    extract::{rejection::FormRejection, Form, FromRequest, Request},
+    extract::{
+        rejection::{FormRejection, JsonRejection},
+        Form, FromRequest, Request,
+    },
+    http::header::CONTENT_TYPE,
+    Json,
 };
 use serde::de::DeserializeOwned;
 use spotitube_core::errors::SpotitubeError;
@@ -15,12 +20,38 @@ where
     T: DeserializeOwned + Validate,
     S: Send + Sync,
     Form<T>: FromRequest<S, Rejection = FormRejection>,
+    Json<T>: FromRequest<S, Rejection = JsonRejection>,
 {
     type Rejection = SpotitubeError;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
-        let Form(value) = Form::<T>::from_request(req, state).await?;
+        let value = if has_json_content_type(&req) {
+            let Json(value) = Json::<T>::from_request(req, state).await?;
+            value
+        } else {
+            let Form(value) = Form::<T>::from_request(req, state).await?;
+            value
+        };
+
         value.validate()?;
         Ok(Self(value))
     }
 }
+
+fn has_json_content_type(req: &Request) -> bool {
+    let Some(content_type) = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    mime == "application/json" || (mime.starts_with("application/") && mime.ends_with("+json"))
+}
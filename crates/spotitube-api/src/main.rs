@@ -0,0 +1,56 @@
+use std::{sync::Arc, time::Duration};
+
+use clap::Parser;
+use dotenv::dotenv;
+use spotitube_api::router::SpotitubeApplicationController;
+use spotitube_core::{config::AppConfig, errors::SpotitubeResult};
+use spotitube_infrastructure::{
+    connection_pool::SpotitubeConnectionPoolManager,
+    reencryption_service::SpotitubeReencryptionService, seed_service::SpotitubeSeedService,
+    service_register::ServiceRegister,
+};
+use tracing::info;
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() -> SpotitubeResult<()> {
+    dotenv().ok();
+
+    let config = Arc::new(AppConfig::parse());
+
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::new(&config.rust_log))
+        .init();
+
+    info!("initializing connection pool...");
+    let pool =
+        SpotitubeConnectionPoolManager::new_pool(&config.database_url, config.run_migrations)
+            .await?;
+
+    if config.seed {
+        SpotitubeSeedService::new(pool.clone(), config.clone())
+            .seed()
+            .await?;
+    }
+
+    if config.reencrypt_tokens {
+        SpotitubeReencryptionService::new(pool.clone(), config.clone())?
+            .reencrypt()
+            .await?;
+        SpotitubeConnectionPoolManager::close_pool(&pool).await;
+        return Ok(());
+    }
+
+    let service_register = ServiceRegister::new(pool, config.clone())?;
+
+    info!("starting server on port {}...", config.port);
+    SpotitubeApplicationController::serve(
+        config.port,
+        &config.cors_origing,
+        Duration::from_secs(config.shutdown_timeout),
+        service_register,
+    )
+    .await?;
+
+    Ok(())
+}
@@ -1,4 +1,8 @@
-use std::{future::ready, time::Instant};
+use std::{
+    future::{ready, Future, IntoFuture},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use axum::{
     extract::{MatchedPath, Request},
@@ -7,16 +11,28 @@ use axum::{
     routing::get,
     Router,
 };
-use http::{HeaderValue, Method};
+use http::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    HeaderName, HeaderValue, Method,
+};
 use lazy_static::lazy_static;
 use metrics_exporter_prometheus::{Matcher, PrometheusBuilder};
-use spotitube_core::errors::{SpotitubeError, SpotitubeResult};
-use spotitube_infrastructure::service_register::ServiceRegister;
-use tokio::net::TcpListener;
+use spotitube_core::errors::{SpotitubeError, SpotitubeResult, REQUEST_ID};
+use spotitube_infrastructure::{
+    connection_pool::SpotitubeConnectionPoolManager, service_register::ServiceRegister,
+};
+use tokio::{net::TcpListener, signal, sync::Notify, task::JoinHandle};
+use tokio_util::sync::CancellationToken;
 use tower::ServiceBuilder;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
+use tracing::{info, info_span, warn};
+use uuid::Uuid;
 
-use crate::endpoints::users_endpoints::UsersRouter;
+use crate::endpoints::{
+    accounts_endpoints::AccountsRouter, conversions_endpoints::ConversionsRouter,
+    jwks_endpoints::JwksRouter, snapshots_endpoints::SnapshotsRouter, syncs_endpoints::SyncsRouter,
+    users_endpoints::UsersRouter,
+};
 
 lazy_static! {
     static ref HTTP_TIMEOUT: u64 = 30;
@@ -24,14 +40,42 @@ lazy_static! {
         &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,];
 }
 
+const X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
+
 pub struct SpotitubeApplicationController;
 
 impl SpotitubeApplicationController {
     pub async fn serve(
         port: u32,
         cors_origin: &str,
+        drain_timeout: Duration,
         service_register: ServiceRegister,
     ) -> SpotitubeResult<()> {
+        Self::serve_with_shutdown(
+            port,
+            cors_origin,
+            drain_timeout,
+            service_register,
+            Self::shutdown_signal(),
+        )
+        .await
+    }
+
+    /// Serves the application and runs the background workers until `shutdown` resolves, then
+    /// waits up to `drain_timeout` for in-flight requests and again for the workers to complete
+    /// before closing the connection pool.
+    pub async fn serve_with_shutdown<F>(
+        port: u32,
+        cors_origin: &str,
+        drain_timeout: Duration,
+        service_register: ServiceRegister,
+        shutdown: F,
+    ) -> SpotitubeResult<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let pool = service_register.pool.clone();
+
         let recorder_handle = PrometheusBuilder::new()
             .set_buckets_for_metric(
                 Matcher::Full(String::from("http_request_duration_seconds")),
@@ -41,25 +85,157 @@ impl SpotitubeApplicationController {
             .map_err(|_| SpotitubeError::AppStartup)?;
 
         let router = Router::new()
-            .nest("/api", UsersRouter::new_router(service_register))
+            .merge(JwksRouter::new_router(
+                service_register.token_service.clone(),
+            ))
+            .nest(
+                "/api",
+                UsersRouter::new_router(&service_register)
+                    .merge(AccountsRouter::new_router(&service_register))
+                    .merge(ConversionsRouter::new_router(&service_register))
+                    .merge(SyncsRouter::new_router(&service_register))
+                    .merge(SnapshotsRouter::new_router(&service_register)),
+            )
             .route("/metrics", get(move || ready(recorder_handle.render())))
-            .layer(ServiceBuilder::new().layer(TraceLayer::new_for_http()))
+            .layer(
+                ServiceBuilder::new().layer(TraceLayer::new_for_http().make_span_with(
+                    |request: &Request| {
+                        let request_id = request
+                            .headers()
+                            .get(X_REQUEST_ID)
+                            .and_then(|value| value.to_str().ok())
+                            .unwrap_or_default();
+                        info_span!(
+                            "request",
+                            method = %request.method(),
+                            uri = %request.uri(),
+                            request_id,
+                        )
+                    },
+                )),
+            )
             .layer(
                 CorsLayer::new()
                     .allow_origin(cors_origin.parse::<HeaderValue>().unwrap())
-                    .allow_methods([Method::GET]),
+                    .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
+                    .allow_headers([AUTHORIZATION, CONTENT_TYPE, X_REQUEST_ID])
+                    .expose_headers([X_REQUEST_ID]),
             )
-            .route_layer(middleware::from_fn(Self::track_matrics));
+            .route_layer(middleware::from_fn(Self::track_matrics))
+            .layer(middleware::from_fn(Self::propagate_request_id));
 
         let listener = TcpListener::bind(&format!("0.0.0.0:{}", port))
             .await
             .map_err(|_| SpotitubeError::AppStartup)?;
-        axum::serve(listener, router.into_make_service())
-            .await
-            .map_err(|_| SpotitubeError::AppStartup)?;
+
+        let background_shutdown = CancellationToken::new();
+        let mut background_tasks = service_register
+            .job_worker
+            .start(background_shutdown.clone());
+        background_tasks.push(
+            service_register
+                .sync_scheduler
+                .start(background_shutdown.clone()),
+        );
+
+        let shutdown_started = Arc::new(Notify::new());
+        let server_shutdown_started = shutdown_started.clone();
+        let server_background_shutdown = background_shutdown.clone();
+        let server = axum::serve(listener, router.into_make_service())
+            .with_graceful_shutdown(async move {
+                shutdown.await;
+                info!("shutdown requested, draining connections...");
+                server_background_shutdown.cancel();
+                server_shutdown_started.notify_one();
+            })
+            .into_future();
+        let drain_deadline = async {
+            shutdown_started.notified().await;
+            tokio::time::sleep(drain_timeout).await;
+        };
+
+        tokio::select! {
+            result = server => result.map_err(|_| SpotitubeError::AppStartup)?,
+            _ = drain_deadline => warn!(
+                "connections were not drained within {:?}, forcing shutdown",
+                drain_timeout
+            ),
+        }
+
+        background_shutdown.cancel();
+        Self::join_background_tasks(background_tasks, drain_timeout).await;
+        SpotitubeConnectionPoolManager::close_pool(&pool).await;
         Ok(())
     }
 
+    /// Waits up to `timeout` for the background workers to stop, aborting them past it; jobs
+    /// they were holding are picked up again once their lock expires.
+    async fn join_background_tasks(mut tasks: Vec<JoinHandle<()>>, timeout: Duration) {
+        let joined = tokio::time::timeout(timeout, async {
+            for task in tasks.iter_mut() {
+                if let Err(err) = task.await {
+                    warn!("background worker failed: {:?}", err);
+                }
+            }
+        })
+        .await;
+
+        if joined.is_err() {
+            warn!(
+                "background workers did not stop within {:?}, aborting them",
+                timeout
+            );
+            for task in &tasks {
+                task.abort();
+            }
+        }
+    }
+
+    pub async fn shutdown_signal() {
+        let ctrl_c = async {
+            signal::ctrl_c()
+                .await
+                .expect("failed to install SIGINT handler");
+        };
+
+        #[cfg(unix)]
+        let terminate = async {
+            signal::unix::signal(signal::unix::SignalKind::terminate())
+                .expect("failed to install SIGTERM handler")
+                .recv()
+                .await;
+        };
+
+        #[cfg(not(unix))]
+        let terminate = std::future::pending::<()>();
+
+        tokio::select! {
+            _ = ctrl_c => {},
+            _ = terminate => {},
+        }
+    }
+
+    async fn propagate_request_id(mut request: Request, next: Next) -> impl IntoResponse {
+        let request_id = match request
+            .headers()
+            .get(X_REQUEST_ID)
+            .and_then(|value| value.to_str().ok())
+        {
+            Some(request_id) if !request_id.is_empty() => String::from(request_id),
+            _ => Uuid::new_v4().to_string(),
+        };
+
+        let header_value =
+            HeaderValue::from_str(&request_id).unwrap_or_else(|_| HeaderValue::from_static(""));
+        request
+            .headers_mut()
+            .insert(X_REQUEST_ID, header_value.clone());
+
+        let mut response = REQUEST_ID.scope(request_id, next.run(request)).await;
+        response.headers_mut().insert(X_REQUEST_ID, header_value);
+        response
+    }
+
     async fn track_matrics(request: Request, next: Next) -> impl IntoResponse {
         let path = match request.extensions().get::<MatchedPath>() {
             Some(matched_path) => matched_path.as_str().to_owned(),
@@ -0,0 +1,125 @@
+use std::{str::FromStr, sync::Arc};
+
+use axum::async_trait;
+use spotitube_domain::{accounts::LinkedAccountDto, providers::Provider};
+use sqlx::prelude::FromRow;
+use sqlx::types::time::OffsetDateTime;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::errors::{SpotitubeError, SpotitubeResult};
+
+pub type DynLinkedAccountsRepository = Arc<dyn LinkedAccountsRepository + Send + Sync>;
+
+#[async_trait]
+pub trait LinkedAccountsRepository {
+    async fn upsert_linked_account(
+        &self,
+        account: NewLinkedAccount<'_>,
+    ) -> SpotitubeResult<LinkedAccountEntity>;
+
+    async fn get_linked_accounts(
+        &self,
+        user_id: &Uuid,
+    ) -> SpotitubeResult<Vec<LinkedAccountEntity>>;
+
+    async fn get_linked_account(
+        &self,
+        user_id: &Uuid,
+        provider: &str,
+    ) -> SpotitubeResult<Option<LinkedAccountEntity>>;
+
+    async fn get_linked_account_by_id(
+        &self,
+        id: &Uuid,
+    ) -> SpotitubeResult<Option<LinkedAccountEntity>>;
+
+    /// Takes the cross-process refresh lease for the account until `locked_until`; returns
+    /// `None` while another worker holds an unexpired lease.
+    async fn acquire_refresh_lease(
+        &self,
+        id: &Uuid,
+        locked_until: OffsetDateTime,
+    ) -> SpotitubeResult<Option<LinkedAccountEntity>>;
+
+    async fn release_refresh_lease(&self, id: &Uuid) -> SpotitubeResult<()>;
+
+    /// Stores refreshed tokens, keeping the previous refresh token when none was issued, and
+    /// releases the refresh lease.
+    async fn update_refreshed_tokens(
+        &self,
+        id: &Uuid,
+        access_token: &str,
+        refresh_token: Option<&str>,
+        expires_at: OffsetDateTime,
+    ) -> SpotitubeResult<LinkedAccountEntity>;
+
+    async fn mark_needs_relink(&self, id: &Uuid) -> SpotitubeResult<()>;
+
+    /// Pages through every linked account in id order, for maintenance tasks.
+    async fn get_linked_accounts_after(
+        &self,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> SpotitubeResult<Vec<LinkedAccountEntity>>;
+
+    /// Swaps the stored tokens unless the access token changed since it was read; returns
+    /// whether the row was updated.
+    async fn replace_linked_account_tokens(
+        &self,
+        id: &Uuid,
+        previous_access_token: &str,
+        access_token: &str,
+        refresh_token: Option<&str>,
+    ) -> SpotitubeResult<bool>;
+
+    /// Returns `false` if the user had no account linked for the provider.
+    async fn delete_linked_account(&self, user_id: &Uuid, provider: &str) -> SpotitubeResult<bool>;
+}
+
+pub struct NewLinkedAccount<'a> {
+    pub user_id: &'a Uuid,
+    pub provider: &'a str,
+    pub external_user_id: &'a str,
+    pub display_name: Option<&'a str>,
+    pub access_token: &'a str,
+    pub refresh_token: Option<&'a str>,
+    pub scopes: &'a str,
+    pub expires_at: OffsetDateTime,
+}
+
+#[derive(FromRow)]
+pub struct LinkedAccountEntity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub provider: String,
+    pub external_user_id: String,
+    pub display_name: Option<String>,
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub scopes: String,
+    pub expires_at: OffsetDateTime,
+    pub needs_relink: bool,
+    pub refresh_locked_until: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl LinkedAccountEntity {
+    pub fn provider(&self) -> SpotitubeResult<Provider> {
+        Provider::from_str(&self.provider).map_err(|err| {
+            error!("linked account {:?} has {}", self.id, err);
+            SpotitubeError::InternalServerError
+        })
+    }
+
+    pub fn into_dto(self) -> SpotitubeResult<LinkedAccountDto> {
+        Ok(LinkedAccountDto {
+            provider: self.provider()?,
+            scopes: self.scopes.split_whitespace().map(String::from).collect(),
+            external_user_id: self.external_user_id,
+            display_name: self.display_name,
+            needs_relink: self.needs_relink,
+        })
+    }
+}
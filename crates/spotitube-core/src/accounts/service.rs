@@ -0,0 +1,17 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use spotitube_domain::{accounts::LinkedAccountDto, providers::Provider};
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+pub type DynAccountsService = Arc<dyn AccountsService + Send + Sync>;
+
+#[async_trait]
+pub trait AccountsService {
+    async fn list_accounts(&self, user_id: &Uuid) -> SpotitubeResult<Vec<LinkedAccountDto>>;
+    async fn begin_link(&self, user_id: &Uuid, provider: Provider) -> SpotitubeResult<String>;
+    async fn complete_link(&self, state: &str, code: &str) -> SpotitubeResult<LinkedAccountDto>;
+    async fn unlink(&self, user_id: &Uuid, provider: Provider) -> SpotitubeResult<()>;
+}
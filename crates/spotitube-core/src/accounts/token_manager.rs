@@ -0,0 +1,16 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use spotitube_domain::providers::Provider;
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+pub type DynProviderTokenManager = Arc<dyn ProviderTokenManager + Send + Sync>;
+
+#[async_trait]
+pub trait ProviderTokenManager {
+    /// Returns a usable access token for the user's linked account, refreshing and persisting
+    /// it first when it is about to expire.
+    async fn access_token(&self, user_id: &Uuid, provider: Provider) -> SpotitubeResult<String>;
+}
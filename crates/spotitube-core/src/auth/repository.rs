@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use sqlx::prelude::FromRow;
+use sqlx::types::time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+pub type DynRefreshTokensRepository = Arc<dyn RefreshTokensRepository + Send + Sync>;
+
+#[async_trait]
+pub trait RefreshTokensRepository {
+    async fn create_refresh_token(
+        &self,
+        user_id: &Uuid,
+        token_hash: &str,
+        expires_at: OffsetDateTime,
+    ) -> SpotitubeResult<RefreshTokenEntity>;
+
+    async fn get_refresh_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> SpotitubeResult<Option<RefreshTokenEntity>>;
+
+    /// Returns `false` if the token had already been revoked.
+    async fn revoke_refresh_token(&self, id: &Uuid) -> SpotitubeResult<bool>;
+
+    async fn revoke_user_refresh_tokens(&self, user_id: &Uuid) -> SpotitubeResult<()>;
+}
+
+#[derive(FromRow)]
+pub struct RefreshTokenEntity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub token_hash: String,
+    pub expires_at: OffsetDateTime,
+    pub revoked_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
@@ -0,0 +1,16 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use spotitube_domain::users::UserDto;
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+pub type DynAuthService = Arc<dyn AuthService + Send + Sync>;
+
+#[async_trait]
+pub trait AuthService {
+    async fn issue_refresh_token(&self, user_id: &Uuid) -> SpotitubeResult<String>;
+    async fn refresh(&self, refresh_token: &str) -> SpotitubeResult<UserDto>;
+    async fn logout(&self, refresh_token: &str) -> SpotitubeResult<()>;
+}
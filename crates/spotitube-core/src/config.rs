@@ -5,7 +5,77 @@ pub struct AppConfig {
     #[clap(long, env)]
     pub rust_log: String,
     #[clap(long, env)]
-    pub argon_salt: String,
+    pub argon_salt: Option<String>,
+    #[clap(long, env, default_value_t = 19456)]
+    pub argon_memory_cost: u32,
+    #[clap(long, env, default_value_t = 2)]
+    pub argon_iterations: u32,
+    #[clap(long, env, default_value_t = 1)]
+    pub argon_parallelism: u32,
+    #[clap(long, env, default_value_t = 4)]
+    pub argon_max_concurrency: usize,
+    #[clap(long, env, default_value_t = 600)]
+    pub oauth_state_lifetime: i64,
+    #[clap(long, env, default_value = "")]
+    pub spotify_client_id: String,
+    #[clap(long, env)]
+    pub spotify_client_secret: Option<String>,
+    #[clap(long, env, default_value = "")]
+    pub spotify_redirect_uri: String,
+    #[clap(
+        long,
+        env,
+        default_value = "playlist-read-private playlist-modify-private playlist-modify-public user-library-read"
+    )]
+    pub spotify_scopes: String,
+    #[clap(long, env, default_value = "https://api.spotify.com/v1")]
+    pub spotify_api_url: String,
+    #[clap(long, env, default_value = "https://accounts.spotify.com")]
+    pub spotify_accounts_url: String,
+    #[clap(long, env, default_value = "")]
+    pub youtube_client_id: String,
+    #[clap(long, env)]
+    pub youtube_client_secret: Option<String>,
+    #[clap(long, env, default_value = "")]
+    pub youtube_redirect_uri: String,
+    #[clap(long, env, default_value = "https://www.googleapis.com/auth/youtube")]
+    pub youtube_scopes: String,
+    #[clap(long, env, default_value = "https://www.googleapis.com/youtube/v3")]
+    pub youtube_api_url: String,
+    #[clap(long, env, default_value = "https://accounts.google.com/o/oauth2/v2/auth")]
+    pub youtube_authorize_url: String,
+    #[clap(long, env, default_value = "https://oauth2.googleapis.com/token")]
+    pub youtube_token_url: String,
+    #[clap(long, env, default_value_t = 10000)]
+    pub youtube_daily_quota: u64,
+    #[clap(long, env, default_value_t = 0.7)]
+    pub match_review_threshold: f64,
+    #[clap(long, env, default_value_t = 5)]
+    pub match_review_candidates: usize,
+    #[clap(long, env, default_value_t = 2592000)]
+    pub match_cache_lifetime: i64,
+    #[clap(long, env, default_value_t = 1)]
+    pub match_confirmations: i64,
+    #[clap(long, env, default_value_t = 2)]
+    pub job_workers: usize,
+    #[clap(long, env, default_value_t = 1)]
+    pub job_poll_interval: u64,
+    #[clap(long, env, default_value_t = 300)]
+    pub job_visibility_timeout: i64,
+    #[clap(long, env, default_value_t = 5)]
+    pub job_max_attempts: i32,
+    #[clap(long, env, default_value_t = 30)]
+    pub job_retry_delay: i64,
+    #[clap(long, env, default_value_t = 1000)]
+    pub conversion_event_history: usize,
+    #[clap(long, env, default_value_t = 300)]
+    pub conversion_event_retention: u64,
+    #[clap(long, env, default_value_t = 900)]
+    pub sync_interval: i64,
+    #[clap(long, env, default_value_t = 15)]
+    pub sync_poll_interval: u64,
+    #[clap(long, env, default_value_t = 20)]
+    pub sync_batch_size: i64,
     #[clap(long, env)]
     pub token_secret: String,
     #[clap(long, env)]
@@ -14,6 +84,30 @@ pub struct AppConfig {
     pub run_migrations: bool,
     #[clap(long, env)]
     pub seed: bool,
+    #[clap(long, env, value_delimiter = ',', default_value = "spotitube,demo")]
+    pub seed_users: Vec<String>,
+    #[clap(long, env, default_value = "production")]
+    pub app_env: String,
     #[clap(long, env)]
     pub cors_origing: String,
+    #[clap(long, env, default_value_t = 30)]
+    pub shutdown_timeout: u64,
+    #[clap(long, env, default_value_t = 900)]
+    pub access_token_lifetime: i64,
+    #[clap(long, env, default_value_t = 2592000)]
+    pub refresh_token_lifetime: i64,
+    #[clap(long, env, default_value = "HS256")]
+    pub token_algorithm: String,
+    #[clap(long, env)]
+    pub token_private_key: Option<String>,
+    #[clap(long, env)]
+    pub token_key_id: Option<String>,
+    #[clap(long, env, value_delimiter = ',')]
+    pub token_public_keys: Vec<String>,
+    #[clap(long, env, value_delimiter = ',')]
+    pub encryption_keys: Vec<String>,
+    #[clap(long, env)]
+    pub encryption_key_version: Option<String>,
+    #[clap(long, env)]
+    pub reencrypt_tokens: bool,
 }
@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use spotitube_domain::conversions::{ConversionEvent, ConversionEventDto};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+pub type DynConversionEvents = Arc<dyn ConversionEvents + Send + Sync>;
+
+pub struct ConversionEventSubscription {
+    /// Id of the latest event published for the conversion, if any.
+    pub last_event_id: Option<String>,
+    /// Events published after the requested id, or `None` when they are no longer available.
+    pub missed: Option<Vec<ConversionEventDto>>,
+    pub receiver: broadcast::Receiver<ConversionEventDto>,
+}
+
+/// Channel the job workers publish conversion progress into. Only subscribers in the same
+/// process receive the events.
+pub trait ConversionEvents {
+    fn publish(&self, conversion_id: &Uuid, event: ConversionEvent);
+    fn subscribe(
+        &self,
+        conversion_id: &Uuid,
+        last_event_id: Option<&str>,
+    ) -> ConversionEventSubscription;
+}
@@ -0,0 +1,6 @@
+pub mod events;
+pub mod repository;
+pub mod review_service;
+pub mod runner;
+pub mod service;
+pub mod title_parser;
@@ -0,0 +1,270 @@
+use std::{str::FromStr, sync::Arc};
+
+use axum::async_trait;
+use spotitube_domain::{
+    conversions::{
+        ConversionDto, ConversionStatus, ConversionTrackDto, MatchCandidateDto, TrackStatus,
+    },
+    providers::Provider,
+};
+use sqlx::prelude::FromRow;
+use sqlx::types::time::OffsetDateTime;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::errors::{SpotitubeError, SpotitubeResult};
+
+pub type DynConversionsRepository = Arc<dyn ConversionsRepository + Send + Sync>;
+
+#[async_trait]
+pub trait ConversionsRepository {
+    /// Creates the conversion together with the job that runs it, in one transaction.
+    async fn create_conversion(
+        &self,
+        user_id: &Uuid,
+        source_provider: &str,
+        source_playlist_id: &str,
+        destination_provider: &str,
+        name: Option<&str>,
+        max_attempts: i32,
+    ) -> SpotitubeResult<ConversionEntity>;
+
+    async fn get_conversion(&self, id: &Uuid) -> SpotitubeResult<Option<ConversionEntity>>;
+
+    async fn update_conversion_status(
+        &self,
+        id: &Uuid,
+        status: &str,
+        error: Option<&str>,
+    ) -> SpotitubeResult<()>;
+
+    /// Stores the name and every track of the source playlist in one transaction, so a
+    /// conversion never ends up with only part of its tracks.
+    async fn update_conversion_source(
+        &self,
+        id: &Uuid,
+        name: &str,
+        tracks: &[NewConversionTrack],
+    ) -> SpotitubeResult<Vec<ConversionTrackEntity>>;
+
+    async fn update_conversion_destination(
+        &self,
+        id: &Uuid,
+        destination_playlist_id: &str,
+    ) -> SpotitubeResult<()>;
+
+    async fn get_conversion_tracks(
+        &self,
+        conversion_id: &Uuid,
+    ) -> SpotitubeResult<Vec<ConversionTrackEntity>>;
+
+    async fn get_conversion_track(
+        &self,
+        conversion_id: &Uuid,
+        position: i32,
+    ) -> SpotitubeResult<Option<ConversionTrackEntity>>;
+
+    async fn update_conversion_track(
+        &self,
+        id: &Uuid,
+        outcome: TrackOutcome<'_>,
+    ) -> SpotitubeResult<()>;
+
+    /// Moves a track awaiting review, or one left resolving since before `stale_before`, to
+    /// resolving, returning whether it was claimed.
+    async fn claim_conversion_track(
+        &self,
+        id: &Uuid,
+        stale_before: OffsetDateTime,
+    ) -> SpotitubeResult<bool>;
+
+    /// Moves a track from one status to another, returning whether it was still in `from`.
+    async fn transition_conversion_track(
+        &self,
+        id: &Uuid,
+        from: TrackStatus,
+        to: TrackStatus,
+    ) -> SpotitubeResult<bool>;
+
+    /// Number of matched tracks ahead of `position`, i.e. where a newly matched track at
+    /// `position` belongs in the destination playlist.
+    async fn count_matched_tracks_before(
+        &self,
+        conversion_id: &Uuid,
+        position: i32,
+    ) -> SpotitubeResult<i64>;
+
+    async fn create_track_candidate(
+        &self,
+        conversion_track_id: &Uuid,
+        candidate: &NewTrackCandidate,
+    ) -> SpotitubeResult<()>;
+
+    /// Candidates of every track in the conversion, ordered by track position then rank.
+    async fn get_track_candidates(
+        &self,
+        conversion_id: &Uuid,
+    ) -> SpotitubeResult<Vec<TrackCandidateEntity>>;
+
+    async fn get_track_candidate(
+        &self,
+        conversion_track_id: &Uuid,
+        id: &Uuid,
+    ) -> SpotitubeResult<Option<TrackCandidateEntity>>;
+}
+
+/// A track read from the source playlist, before it has been matched.
+#[derive(Debug, Clone)]
+pub struct NewConversionTrack {
+    pub position: i32,
+    pub source_id: Option<String>,
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub isrc: Option<String>,
+}
+
+/// A ranked search result kept for tracks that need manual review.
+#[derive(Debug, Clone)]
+pub struct NewTrackCandidate {
+    pub rank: i32,
+    pub destination_id: String,
+    pub title: String,
+    pub confidence: f64,
+    pub match_reasons: Vec<String>,
+}
+
+pub struct TrackOutcome<'a> {
+    pub status: TrackStatus,
+    pub destination_id: Option<&'a str>,
+    pub destination_title: Option<&'a str>,
+    pub confidence: Option<f64>,
+    pub match_reasons: &'a [String],
+    pub error: Option<&'a str>,
+}
+
+#[derive(FromRow)]
+pub struct ConversionEntity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub source_provider: String,
+    pub source_playlist_id: String,
+    pub destination_provider: String,
+    pub destination_playlist_id: Option<String>,
+    pub name: Option<String>,
+    pub status: String,
+    pub error: Option<String>,
+    pub total_tracks: i32,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+    pub completed_at: Option<OffsetDateTime>,
+}
+
+#[derive(FromRow)]
+pub struct ConversionTrackEntity {
+    pub id: Uuid,
+    pub conversion_id: Uuid,
+    pub position: i32,
+    pub source_id: Option<String>,
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub isrc: Option<String>,
+    pub status: String,
+    pub destination_id: Option<String>,
+    pub destination_title: Option<String>,
+    pub error: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+    pub confidence: Option<f64>,
+    pub match_reasons: Vec<String>,
+}
+
+#[derive(FromRow)]
+pub struct TrackCandidateEntity {
+    pub id: Uuid,
+    pub conversion_track_id: Uuid,
+    pub rank: i32,
+    pub destination_id: String,
+    pub title: String,
+    pub confidence: f64,
+    pub match_reasons: Vec<String>,
+    pub created_at: OffsetDateTime,
+}
+
+fn parse_column<T: FromStr<Err = String>>(id: &Uuid, value: &str) -> SpotitubeResult<T> {
+    T::from_str(value).map_err(|err| {
+        error!("conversion {:?} has {}", id, err);
+        SpotitubeError::InternalServerError
+    })
+}
+
+impl ConversionEntity {
+    pub fn source_provider(&self) -> SpotitubeResult<Provider> {
+        parse_column(&self.id, &self.source_provider)
+    }
+
+    pub fn destination_provider(&self) -> SpotitubeResult<Provider> {
+        parse_column(&self.id, &self.destination_provider)
+    }
+
+    pub fn status(&self) -> SpotitubeResult<ConversionStatus> {
+        parse_column(&self.id, &self.status)
+    }
+
+    pub fn into_dto(self, tracks: Vec<ConversionTrackEntity>) -> SpotitubeResult<ConversionDto> {
+        let tracks = tracks
+            .into_iter()
+            .map(|track| track.into_dto())
+            .collect::<SpotitubeResult<Vec<_>>>()?;
+
+        Ok(ConversionDto {
+            source_provider: self.source_provider()?,
+            destination_provider: self.destination_provider()?,
+            status: self.status()?,
+            id: self.id,
+            source_playlist_id: self.source_playlist_id,
+            destination_playlist_id: self.destination_playlist_id,
+            name: self.name,
+            error: self.error,
+            total_tracks: self.total_tracks,
+            tracks,
+            created_at: self.created_at,
+            completed_at: self.completed_at,
+        })
+    }
+}
+
+impl ConversionTrackEntity {
+    pub fn into_dto(self) -> SpotitubeResult<ConversionTrackDto> {
+        Ok(ConversionTrackDto {
+            status: parse_column::<TrackStatus>(&self.conversion_id, &self.status)?,
+            position: self.position,
+            source_id: self.source_id,
+            title: self.title,
+            artists: self.artists,
+            album: self.album,
+            duration_ms: self.duration_ms,
+            isrc: self.isrc,
+            destination_id: self.destination_id,
+            destination_title: self.destination_title,
+            confidence: self.confidence,
+            match_reasons: self.match_reasons,
+            error: self.error,
+        })
+    }
+}
+
+impl TrackCandidateEntity {
+    pub fn into_dto(self) -> MatchCandidateDto {
+        MatchCandidateDto {
+            id: self.id,
+            destination_id: self.destination_id,
+            title: self.title,
+            confidence: self.confidence,
+            match_reasons: self.match_reasons,
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use spotitube_domain::conversions::{ConversionTrackDto, TrackReviewDto};
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+pub type DynConversionReviewService = Arc<dyn ConversionReviewService + Send + Sync>;
+
+/// Resolves tracks the matcher flagged for review, patching the destination playlist.
+#[async_trait]
+pub trait ConversionReviewService {
+    async fn list_reviews(
+        &self,
+        user_id: &Uuid,
+        conversion_id: &Uuid,
+        candidates: Option<usize>,
+    ) -> SpotitubeResult<Vec<TrackReviewDto>>;
+
+    async fn accept_candidate(
+        &self,
+        user_id: &Uuid,
+        conversion_id: &Uuid,
+        position: i32,
+        candidate_id: &Uuid,
+    ) -> SpotitubeResult<ConversionTrackDto>;
+
+    async fn reject_candidates(
+        &self,
+        user_id: &Uuid,
+        conversion_id: &Uuid,
+        position: i32,
+    ) -> SpotitubeResult<ConversionTrackDto>;
+
+    /// Matches the track to a destination id the user looked up themselves.
+    async fn set_manual_match(
+        &self,
+        user_id: &Uuid,
+        conversion_id: &Uuid,
+        position: i32,
+        destination_id: &str,
+    ) -> SpotitubeResult<ConversionTrackDto>;
+}
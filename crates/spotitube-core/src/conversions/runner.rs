@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::{SpotitubeError, SpotitubeResult};
+
+pub type DynConversionRunner = Arc<dyn ConversionRunner + Send + Sync>;
+
+/// Kind of the background job that runs a conversion.
+pub const CONVERSION_JOB: &str = "conversion";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConversionJob {
+    pub conversion_id: Uuid,
+}
+
+#[async_trait]
+pub trait ConversionRunner {
+    /// Reads the source playlist, matches every track and fills the destination playlist,
+    /// recording the outcome of each track on the conversion. Runs again after a failure pick
+    /// up the tracks that were not converted yet.
+    async fn run(&self, conversion_id: &Uuid) -> SpotitubeResult<()>;
+
+    /// Marks the conversion as failed once it will not be retried anymore.
+    async fn fail(&self, conversion_id: &Uuid, err: &SpotitubeError) -> SpotitubeResult<()>;
+}
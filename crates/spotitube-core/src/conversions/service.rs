@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use spotitube_domain::{
+    conversions::{ConversionDto, ConversionEventDto},
+    providers::Provider,
+};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+pub type DynConversionsService = Arc<dyn ConversionsService + Send + Sync>;
+
+pub struct ConversionEventStream {
+    /// Events to send before the live ones: the missed events, or a snapshot of the conversion.
+    pub initial: Vec<ConversionEventDto>,
+    /// Live events, absent once the conversion has finished.
+    pub receiver: Option<broadcast::Receiver<ConversionEventDto>>,
+}
+
+#[async_trait]
+pub trait ConversionsService {
+    /// Records a conversion and starts running it in the background.
+    async fn create_conversion(
+        &self,
+        user_id: &Uuid,
+        source_provider: Provider,
+        source_playlist_id: &str,
+        name: Option<&str>,
+    ) -> SpotitubeResult<ConversionDto>;
+    async fn get_conversion(&self, user_id: &Uuid, id: &Uuid) -> SpotitubeResult<ConversionDto>;
+
+    /// Progress events of a conversion, resuming after `last_event_id` when possible.
+    async fn watch_conversion(
+        &self,
+        user_id: &Uuid,
+        id: &Uuid,
+        last_event_id: Option<&str>,
+    ) -> SpotitubeResult<ConversionEventStream>;
+}
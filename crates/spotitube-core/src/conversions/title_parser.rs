@@ -0,0 +1,359 @@
+/// An artist/title guess extracted from a YouTube video title, most likely first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TitleCandidate {
+    pub artists: Vec<String>,
+    pub title: String,
+}
+
+const SEPARATORS: &[&str] = &[" - ", " – ", " — ", " ~ ", " | "];
+const FEATURING: &[&str] = &["ft", "ft.", "feat", "feat.", "featuring"];
+const ARTIST_DELIMITERS: &[&str] = &[", ", " & ", " x ", " X "];
+const NOISE: &[&str] = &[
+    "official",
+    "music",
+    "video",
+    "audio",
+    "lyric",
+    "lyrics",
+    "visualizer",
+    "visualiser",
+    "hd",
+    "hq",
+    "4k",
+    "remastered",
+    "mv",
+    "explicit",
+    "clean",
+    "m/v",
+];
+const BRACKETS: &[(char, char)] = &[('(', ')'), ('[', ']'), ('{', '}'), ('【', '】')];
+const TOPIC_SUFFIX: &str = " - Topic";
+const VEVO_SUFFIX: &str = "VEVO";
+
+/// Parses titles such as `Artist - Title (Official Video)`, `Artist ft. Guest - Title [Lyrics]`
+/// or `Title` uploaded by an auto-generated `Artist - Topic` channel into search candidates.
+pub fn parse_video_title(title: &str, channel_title: Option<&str>) -> Vec<TitleCandidate> {
+    let (cleaned, mut featured) = strip_brackets(title);
+    let channel_artist = channel_title.and_then(channel_artist);
+
+    let mut candidates = Vec::new();
+    match split_artist_title(&cleaned) {
+        Some((artist, song)) => {
+            let (artist, artist_featured) = split_featuring(&artist);
+            let (song, song_featured) = split_featuring(&song);
+            featured.extend(artist_featured);
+            featured.extend(song_featured);
+
+            let mut artists = split_artists(&artist);
+            artists.extend(featured.iter().cloned());
+            push_candidate(&mut candidates, artists, &song);
+            // band names such as `Earth, Wind & Fire` contain the delimiters
+            let mut artists = vec![artist.clone()];
+            artists.extend(featured.iter().cloned());
+            push_candidate(&mut candidates, artists, &song);
+            // some uploads put the title first
+            push_candidate(&mut candidates, split_artists(&song), &artist);
+        }
+        None => {
+            let (song, song_featured) = split_featuring(&cleaned);
+            featured.extend(song_featured);
+            if let Some(channel_artist) = &channel_artist {
+                let mut artists = vec![channel_artist.clone()];
+                artists.extend(featured.iter().cloned());
+                push_candidate(&mut candidates, artists, &song);
+            }
+            push_candidate(&mut candidates, featured.clone(), &song);
+        }
+    }
+
+    candidates
+}
+
+fn push_candidate(candidates: &mut Vec<TitleCandidate>, artists: Vec<String>, title: &str) {
+    let title = tidy(title);
+    if title.is_empty() {
+        return;
+    }
+
+    let candidate = TitleCandidate {
+        artists: artists
+            .into_iter()
+            .map(|artist| tidy(&artist))
+            .filter(|artist| !artist.is_empty())
+            .collect(),
+        title,
+    };
+    if !candidates.contains(&candidate) {
+        candidates.push(candidate);
+    }
+}
+
+/// Removes bracketed segments that only describe the upload, and collects artists from
+/// bracketed `feat.` segments.
+fn strip_brackets(title: &str) -> (String, Vec<String>) {
+    let mut cleaned = String::with_capacity(title.len());
+    let mut featured = Vec::new();
+
+    let mut rest = title;
+    while let Some((start, open)) = rest
+        .char_indices()
+        .find(|(_, c)| BRACKETS.iter().any(|(open, _)| open == c))
+    {
+        let close = BRACKETS
+            .iter()
+            .find(|(bracket, _)| *bracket == open)
+            .map(|(_, close)| *close)
+            .unwrap_or(')');
+        let inner_start = start + open.len_utf8();
+        let Some(length) = rest[inner_start..].find(close) else {
+            break;
+        };
+
+        let inner = &rest[inner_start..inner_start + length];
+        cleaned.push_str(&rest[..start]);
+        let (before, guests) = split_featuring(inner);
+        if !guests.is_empty() && before.is_empty() {
+            featured.extend(guests);
+        } else if !is_noise(inner) {
+            cleaned.push_str(&rest[start..inner_start + length + close.len_utf8()]);
+        }
+
+        rest = &rest[inner_start + length + close.len_utf8()..];
+    }
+    cleaned.push_str(rest);
+
+    (cleaned, featured)
+}
+
+/// Whether the segment only describes the upload, like `Official Video` or `Remastered 2011`;
+/// segments that also say something about the recording, like `Live Audio`, are kept.
+fn is_noise(segment: &str) -> bool {
+    let words: Vec<String> = segment
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '/')
+        .map(|word| {
+            word.trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase()
+        })
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    words.iter().any(|word| NOISE.contains(&word.as_str()))
+        && words
+            .iter()
+            .all(|word| NOISE.contains(&word.as_str()) || word.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn split_artist_title(title: &str) -> Option<(String, String)> {
+    let separator = SEPARATORS
+        .iter()
+        .filter_map(|separator| title.find(separator).map(|index| (index, *separator)))
+        .min_by_key(|(index, _)| *index);
+    if let Some((index, separator)) = separator {
+        return Some((
+            String::from(&title[..index]),
+            String::from(&title[index + separator.len()..]),
+        ));
+    }
+
+    // Artist "Title"
+    let start = title.find('"')?;
+    let end = title[start + 1..].find('"')? + start + 1;
+    let artist = title[..start].trim();
+    if artist.is_empty() {
+        return None;
+    }
+    Some((String::from(artist), String::from(&title[start + 1..end])))
+}
+
+/// Splits `Artist ft. Guest, Other` into `Artist` and the featured artists.
+fn split_featuring(value: &str) -> (String, Vec<String>) {
+    let words: Vec<&str> = value.split_whitespace().collect();
+    match words
+        .iter()
+        .position(|word| FEATURING.contains(&word.to_lowercase().as_str()))
+    {
+        Some(index) => (
+            words[..index].join(" "),
+            split_artists(&words[index + 1..].join(" ")),
+        ),
+        None => (String::from(value), Vec::new()),
+    }
+}
+
+fn split_artists(value: &str) -> Vec<String> {
+    let mut artists = vec![String::from(value)];
+    for delimiter in ARTIST_DELIMITERS {
+        artists = artists
+            .iter()
+            .flat_map(|artist| artist.split(delimiter).map(String::from))
+            .collect();
+    }
+
+    artists
+        .into_iter()
+        .map(|artist| tidy(&artist))
+        .filter(|artist| !artist.is_empty())
+        .collect()
+}
+
+/// The artist behind auto-generated `Artist - Topic` and `ArtistVEVO` channels.
+fn channel_artist(channel_title: &str) -> Option<String> {
+    channel_title
+        .strip_suffix(TOPIC_SUFFIX)
+        .or_else(|| channel_title.strip_suffix(VEVO_SUFFIX))
+        .map(tidy)
+        .filter(|artist| !artist.is_empty())
+}
+
+fn tidy(value: &str) -> String {
+    value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_matches(|c: char| c == '-' || c == '|' || c == '"' || c == ':' || c.is_whitespace())
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_video_title, TitleCandidate};
+
+    fn candidate(artists: &[&str], title: &str) -> TitleCandidate {
+        TitleCandidate {
+            artists: artists.iter().map(|artist| String::from(*artist)).collect(),
+            title: String::from(title),
+        }
+    }
+
+    fn best(title: &str, channel_title: Option<&str>) -> TitleCandidate {
+        parse_video_title(title, channel_title)
+            .into_iter()
+            .next()
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_artist_and_title_without_upload_descriptions() {
+        assert_eq!(
+            best("Queen - Bohemian Rhapsody (Official Video)", None),
+            candidate(&["Queen"], "Bohemian Rhapsody")
+        );
+        assert_eq!(
+            best("Adele - Hello [Lyrics]", None),
+            candidate(&["Adele"], "Hello")
+        );
+        assert_eq!(
+            best("Daft Punk | Get Lucky (Official Music Video) [HD]", None),
+            candidate(&["Daft Punk"], "Get Lucky")
+        );
+        assert_eq!(
+            best("The Beatles - Let It Be (Remastered 2009)", None),
+            candidate(&["The Beatles"], "Let It Be")
+        );
+    }
+
+    #[test]
+    fn collects_featured_artists() {
+        assert_eq!(
+            best("Mark Ronson ft. Bruno Mars - Uptown Funk [Lyrics]", None),
+            candidate(&["Mark Ronson", "Bruno Mars"], "Uptown Funk")
+        );
+        assert_eq!(
+            best(
+                "Calvin Harris - Feels (feat. Pharrell Williams & Katy Perry)",
+                None
+            ),
+            candidate(
+                &["Calvin Harris", "Pharrell Williams", "Katy Perry"],
+                "Feels"
+            )
+        );
+        assert_eq!(
+            best("Eminem - Love The Way You Lie feat. Rihanna", None),
+            candidate(&["Eminem", "Rihanna"], "Love The Way You Lie")
+        );
+    }
+
+    #[test]
+    fn splits_collaborations() {
+        assert_eq!(
+            best("Calvin Harris & Dua Lipa - One Kiss", None),
+            candidate(&["Calvin Harris", "Dua Lipa"], "One Kiss")
+        );
+        assert_eq!(
+            best("Skrillex x Diplo - Where Are U Now", None),
+            candidate(&["Skrillex", "Diplo"], "Where Are U Now")
+        );
+    }
+
+    #[test]
+    fn keeps_band_names_containing_delimiters_as_a_candidate() {
+        assert_eq!(
+            best("Simon and Garfunkel - The Boxer", None),
+            candidate(&["Simon and Garfunkel"], "The Boxer")
+        );
+        for (title, artist) in [
+            ("Earth, Wind & Fire - September", "Earth, Wind & Fire"),
+            ("Years & Years - King", "Years & Years"),
+        ] {
+            let candidates = parse_video_title(title, None);
+            assert!(
+                candidates
+                    .iter()
+                    .any(|candidate| candidate.artists == [artist]),
+                "{:?} has no candidate by {:?}: {:?}",
+                title,
+                artist,
+                candidates
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_recording_variants() {
+        assert_eq!(
+            best("Avicii - Levels (Official Remix)", None),
+            candidate(&["Avicii"], "Levels (Official Remix)")
+        );
+        assert_eq!(
+            best("Nirvana - Lithium (Live Audio)", None),
+            candidate(&["Nirvana"], "Lithium (Live Audio)")
+        );
+    }
+
+    #[test]
+    fn offers_the_title_first_reading() {
+        let candidates = parse_video_title("Bohemian Rhapsody - Queen", None);
+        assert!(candidates.contains(&candidate(&["Bohemian Rhapsody"], "Queen")));
+        assert!(candidates.contains(&candidate(&["Queen"], "Bohemian Rhapsody")));
+    }
+
+    #[test]
+    fn parses_quoted_titles() {
+        assert_eq!(
+            best("Radiohead \"Creep\" (Official Video)", None),
+            candidate(&["Radiohead"], "Creep")
+        );
+    }
+
+    #[test]
+    fn takes_the_artist_from_auto_generated_channels() {
+        assert_eq!(
+            best("Bohemian Rhapsody", Some("Queen - Topic")),
+            candidate(&["Queen"], "Bohemian Rhapsody")
+        );
+        assert_eq!(
+            best("Hello (Official Video)", Some("AdeleVEVO")),
+            candidate(&["Adele"], "Hello")
+        );
+        assert_eq!(
+            best("Some Song", Some("Random Uploads")),
+            candidate(&[], "Some Song")
+        );
+    }
+
+    #[test]
+    fn ignores_empty_titles() {
+        assert!(parse_video_title("(Official Video)", None).is_empty());
+    }
+}
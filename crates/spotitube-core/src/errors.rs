@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use axum::{http::StatusCode, response::IntoResponse, Json};
+use jsonwebtoken::errors::ErrorKind;
 use spotitube_domain::ApiError;
+use tracing::error;
 use validator::ValidationErrorsKind;
 
 pub type SpotitubeResult<T> = Result<T, SpotitubeError>;
@@ -16,6 +18,10 @@ pub enum SpotitubeError {
     NotFound(String),
     BadRequest(String),
     Conflict(String),
+    ProviderError(String),
+    ProviderUnauthorized(String),
+    ProviderQuotaExceeded(String),
+    ProviderRateLimited(String),
     InternalServerError,
     SqlxError(sqlx::error::Error),
     SqlxMigrateError(sqlx::migrate::MigrateError),
@@ -24,56 +30,223 @@ pub enum SpotitubeError {
     UuidError(uuid::Error),
     ValidationError(validator::ValidationErrors),
     FormRejection(axum::extract::rejection::FormRejection),
+    JsonRejection(axum::extract::rejection::JsonRejection),
+    QueryRejection(axum::extract::rejection::QueryRejection),
+    PathRejection(axum::extract::rejection::PathRejection),
 }
 
-impl IntoResponse for SpotitubeError {
-    fn into_response(self) -> axum::response::Response {
-        let (status, api_error) = match self {
-            SpotitubeError::Unauthorized => {
-                (StatusCode::UNAUTHORIZED, ApiError::from_str("unauthorized"))
-            }
+tokio::task_local! {
+    /// Id of the request currently being handled, echoed back in error bodies.
+    pub static REQUEST_ID: String;
+}
+
+const UNIQUE_VIOLATION: &str = "23505";
+const FOREIGN_KEY_VIOLATION: &str = "23503";
+
+impl SpotitubeError {
+    /// Message that can be stored and shown to the user, without leaking internals.
+    pub fn user_message(&self) -> String {
+        match self {
+            SpotitubeError::NotFound(reason)
+            | SpotitubeError::BadRequest(reason)
+            | SpotitubeError::Conflict(reason)
+            | SpotitubeError::ProviderError(reason)
+            | SpotitubeError::ProviderUnauthorized(reason)
+            | SpotitubeError::ProviderQuotaExceeded(reason)
+            | SpotitubeError::ProviderRateLimited(reason) => reason.clone(),
+            _ => String::from("internal error"),
+        }
+    }
+
+    fn status_and_error(self) -> (StatusCode, ApiError) {
+        match self {
+            SpotitubeError::Unauthorized => (
+                StatusCode::UNAUTHORIZED,
+                ApiError::from_str("unauthorized", "unauthorized"),
+            ),
             SpotitubeError::InvalidUsername => (
                 StatusCode::BAD_REQUEST,
-                ApiError::from_str("invalid username"),
+                ApiError::from_str("invalid_username", "invalid username"),
             ),
             SpotitubeError::InvalidPassword => (
                 StatusCode::BAD_REQUEST,
-                ApiError::from_str("invalid password"),
+                ApiError::from_str("invalid_password", "invalid password"),
+            ),
+            SpotitubeError::Forbidden => (
+                StatusCode::FORBIDDEN,
+                ApiError::from_str("forbidden", "forbidden"),
+            ),
+            SpotitubeError::BadRequest(err) => (
+                StatusCode::BAD_REQUEST,
+                ApiError::from_str("bad_request", &err),
+            ),
+            SpotitubeError::Conflict(err) => {
+                (StatusCode::CONFLICT, ApiError::from_str("conflict", &err))
+            }
+            SpotitubeError::NotFound(err) => {
+                (StatusCode::NOT_FOUND, ApiError::from_str("not_found", &err))
+            }
+            SpotitubeError::ProviderUnauthorized(_) => (
+                StatusCode::FORBIDDEN,
+                ApiError::from_str(
+                    "provider_unauthorized",
+                    "the linked streaming account rejected our credentials",
+                ),
+            ),
+            SpotitubeError::ProviderQuotaExceeded(err) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                ApiError::from_str("provider_quota_exceeded", &err),
+            ),
+            SpotitubeError::ProviderRateLimited(err) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                ApiError::from_str("provider_rate_limited", &err),
+            ),
+            SpotitubeError::ProviderError(_) => (
+                StatusCode::BAD_GATEWAY,
+                ApiError::from_str("provider_error", "streaming provider request failed"),
+            ),
+            SpotitubeError::SqlxError(sqlx::Error::RowNotFound) => (
+                StatusCode::NOT_FOUND,
+                ApiError::from_str("not_found", "resource not found"),
+            ),
+            SpotitubeError::SqlxError(sqlx::Error::Database(err))
+                if err.code().as_deref() == Some(UNIQUE_VIOLATION) =>
+            {
+                (
+                    StatusCode::CONFLICT,
+                    ApiError::from_str("conflict", "resource already exists"),
+                )
+            }
+            SpotitubeError::SqlxError(sqlx::Error::Database(err))
+                if err.code().as_deref() == Some(FOREIGN_KEY_VIOLATION) =>
+            {
+                (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    ApiError::from_str("invalid_reference", "referenced resource does not exist"),
+                )
+            }
+            SpotitubeError::JwtError(err) => match err.kind() {
+                ErrorKind::ExpiredSignature => (
+                    StatusCode::UNAUTHORIZED,
+                    ApiError::from_str("token_expired", "token has expired"),
+                ),
+                ErrorKind::InvalidToken
+                | ErrorKind::InvalidSignature
+                | ErrorKind::MissingRequiredClaim(_)
+                | ErrorKind::InvalidIssuer
+                | ErrorKind::InvalidAudience
+                | ErrorKind::InvalidSubject
+                | ErrorKind::ImmatureSignature
+                | ErrorKind::InvalidAlgorithm
+                | ErrorKind::MissingAlgorithm
+                | ErrorKind::Base64(_)
+                | ErrorKind::Json(_)
+                | ErrorKind::Utf8(_) => (
+                    StatusCode::UNAUTHORIZED,
+                    ApiError::from_str("invalid_token", "invalid token"),
+                ),
+                // Keys that can not be loaded or used are our fault, not the client's.
+                _ => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    ApiError::from_str("internal_server_error", "internal server error"),
+                ),
+            },
+            SpotitubeError::UuidError(err) => (
+                StatusCode::BAD_REQUEST,
+                ApiError::from_str("invalid_id", &err.to_string()),
             ),
-            SpotitubeError::Conflict(err) => (StatusCode::CONFLICT, ApiError::from_str(&err)),
-            SpotitubeError::NotFound(err) => (StatusCode::NOT_FOUND, ApiError::from_str(&err)),
             SpotitubeError::ValidationError(errors) => {
                 let mut validation_errors = HashMap::new();
-                for (_, error_kind) in errors.into_errors() {
-                    if let ValidationErrorsKind::Struct(meta) = error_kind {
-                        for (struct_property, struct_error_kind) in meta.into_errors() {
-                            if let ValidationErrorsKind::Field(field_meta) = struct_error_kind {
-                                for error in field_meta.into_iter() {
-                                    let message = error
-                                        .message
-                                        .map(|c| c.into_owned())
-                                        .unwrap_or(format!("{} is required", struct_property));
-
-                                    validation_errors
-                                        .entry(String::from(struct_property))
-                                        .or_insert_with(Vec::new)
-                                        .push(message);
+                let mut field_errors = Vec::new();
+                for (property, error_kind) in errors.into_errors() {
+                    match error_kind {
+                        ValidationErrorsKind::Struct(meta) => {
+                            for (struct_property, struct_error_kind) in meta.into_errors() {
+                                if let ValidationErrorsKind::Field(field_meta) = struct_error_kind {
+                                    field_errors.push((struct_property, field_meta));
                                 }
                             }
                         }
+                        ValidationErrorsKind::Field(field_meta) => {
+                            field_errors.push((property, field_meta))
+                        }
+                        ValidationErrorsKind::List(_) => {}
+                    }
+                }
+
+                for (property, field_meta) in field_errors {
+                    for error in field_meta.into_iter() {
+                        let message = error
+                            .message
+                            .map(|c| c.into_owned())
+                            .unwrap_or(format!("{} is required", property));
+
+                        validation_errors
+                            .entry(String::from(property))
+                            .or_insert_with(Vec::new)
+                            .push(message);
                     }
                 }
 
                 (
                     StatusCode::UNPROCESSABLE_ENTITY,
-                    ApiError::from_map(validation_errors),
+                    ApiError::from_map("validation_failed", validation_errors),
                 )
             }
-            _ => (
+            SpotitubeError::FormRejection(rejection) => {
+                let mut form_errors = HashMap::new();
+                form_errors.insert(String::from("body"), vec![rejection.body_text()]);
+                (
+                    rejection.status(),
+                    ApiError::from_map("invalid_form", form_errors),
+                )
+            }
+            SpotitubeError::JsonRejection(rejection) => {
+                let mut json_errors = HashMap::new();
+                json_errors.insert(String::from("body"), vec![rejection.body_text()]);
+                (
+                    rejection.status(),
+                    ApiError::from_map("invalid_json", json_errors),
+                )
+            }
+            SpotitubeError::QueryRejection(rejection) => {
+                let mut query_errors = HashMap::new();
+                query_errors.insert(String::from("query"), vec![rejection.body_text()]);
+                (
+                    rejection.status(),
+                    ApiError::from_map("invalid_query", query_errors),
+                )
+            }
+            SpotitubeError::PathRejection(rejection) => {
+                let mut path_errors = HashMap::new();
+                path_errors.insert(String::from("path"), vec![rejection.body_text()]);
+                (
+                    rejection.status(),
+                    ApiError::from_map("invalid_path", path_errors),
+                )
+            }
+            SpotitubeError::AppStartup
+            | SpotitubeError::InternalServerError
+            | SpotitubeError::SqlxError(_)
+            | SpotitubeError::SqlxMigrateError(_)
+            | SpotitubeError::ArgonError(_) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                ApiError::from_str("internal server error"),
+                ApiError::from_str("internal_server_error", "internal server error"),
             ),
-        };
+        }
+    }
+}
+
+impl IntoResponse for SpotitubeError {
+    fn into_response(self) -> axum::response::Response {
+        let request_id = REQUEST_ID.try_with(|request_id| request_id.clone()).ok();
+        let details = format!("{:?}", self);
+
+        let (status, mut api_error) = self.status_and_error();
+        if status.is_server_error() {
+            error!("request {:?} failed: {}", request_id, details);
+        }
+        api_error.request_id = request_id;
 
         let body = Json(api_error);
         (status, body).into_response()
@@ -121,3 +294,59 @@ impl From<axum::extract::rejection::FormRejection> for SpotitubeError {
         Self::FormRejection(value)
     }
 }
+
+impl From<axum::extract::rejection::JsonRejection> for SpotitubeError {
+    fn from(value: axum::extract::rejection::JsonRejection) -> Self {
+        Self::JsonRejection(value)
+    }
+}
+
+impl From<axum::extract::rejection::QueryRejection> for SpotitubeError {
+    fn from(value: axum::extract::rejection::QueryRejection) -> Self {
+        Self::QueryRejection(value)
+    }
+}
+
+impl From<axum::extract::rejection::PathRejection> for SpotitubeError {
+    fn from(value: axum::extract::rejection::PathRejection) -> Self {
+        Self::PathRejection(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use jsonwebtoken::errors::ErrorKind;
+
+    use super::SpotitubeError;
+
+    fn status(kind: ErrorKind) -> StatusCode {
+        SpotitubeError::JwtError(kind.into()).status_and_error().0
+    }
+
+    #[test]
+    fn rejects_invalid_tokens_as_unauthorized() {
+        for kind in [
+            ErrorKind::ExpiredSignature,
+            ErrorKind::InvalidToken,
+            ErrorKind::InvalidSignature,
+            ErrorKind::InvalidAlgorithm,
+            ErrorKind::MissingRequiredClaim(String::from("exp")),
+        ] {
+            assert_eq!(status(kind), StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    #[test]
+    fn reports_unusable_keys_as_internal_errors() {
+        for kind in [
+            ErrorKind::InvalidKeyFormat,
+            ErrorKind::InvalidEcdsaKey,
+            ErrorKind::InvalidRsaKey(String::from("modulus too small")),
+            ErrorKind::RsaFailedSigning,
+            ErrorKind::InvalidAlgorithmName,
+        ] {
+            assert_eq!(status(kind), StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use crate::errors::{SpotitubeError, SpotitubeResult};
+
+use super::repository::JobEntity;
+
+pub type DynJobHandler = Arc<dyn JobHandler + Send + Sync>;
+
+#[async_trait]
+pub trait JobHandler {
+    /// Runs the job; failures are retried with backoff, so handling must be safe to repeat.
+    async fn handle(&self, job: &JobEntity) -> SpotitubeResult<()>;
+
+    /// Called once the job failed for the last time and was dead-lettered.
+    async fn dead_lettered(&self, job: &JobEntity, error: &SpotitubeError) -> SpotitubeResult<()>;
+}
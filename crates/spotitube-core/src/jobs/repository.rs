@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use sqlx::prelude::FromRow;
+use sqlx::types::time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+pub type DynJobRepository = Arc<dyn JobRepository + Send + Sync>;
+
+#[async_trait]
+pub trait JobRepository {
+    async fn enqueue_job(
+        &self,
+        kind: &str,
+        payload: &serde_json::Value,
+        max_attempts: i32,
+    ) -> SpotitubeResult<JobEntity>;
+
+    /// Locks the next due job of one of `kinds` until `locked_until`, including running jobs
+    /// whose worker let the lock expire, and counts the attempt.
+    async fn claim_job(
+        &self,
+        kinds: &[String],
+        worker_id: &str,
+        locked_until: OffsetDateTime,
+    ) -> SpotitubeResult<Option<JobEntity>>;
+
+    /// Returns whether `worker_id` still held the lock.
+    async fn extend_job_lock(
+        &self,
+        id: &Uuid,
+        worker_id: &str,
+        locked_until: OffsetDateTime,
+    ) -> SpotitubeResult<bool>;
+
+    async fn complete_job(&self, id: &Uuid, worker_id: &str) -> SpotitubeResult<bool>;
+
+    /// Releases the job so it can be claimed again from `run_at`.
+    async fn retry_job(
+        &self,
+        id: &Uuid,
+        worker_id: &str,
+        run_at: OffsetDateTime,
+        error: &str,
+    ) -> SpotitubeResult<bool>;
+
+    /// Releases the job until `run_at` without counting the attempt it was claimed for.
+    async fn defer_job(
+        &self,
+        id: &Uuid,
+        worker_id: &str,
+        run_at: OffsetDateTime,
+        error: &str,
+    ) -> SpotitubeResult<bool>;
+
+    /// Parks the job for good; dead jobs are kept for inspection and never claimed.
+    async fn dead_letter_job(
+        &self,
+        id: &Uuid,
+        worker_id: &str,
+        error: &str,
+    ) -> SpotitubeResult<bool>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Completed,
+    Dead,
+}
+
+impl JobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Pending => "pending",
+            JobStatus::Running => "running",
+            JobStatus::Completed => "completed",
+            JobStatus::Dead => "dead",
+        }
+    }
+}
+
+#[derive(FromRow)]
+pub struct JobEntity {
+    pub id: Uuid,
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub status: String,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub run_at: OffsetDateTime,
+    pub locked_by: Option<String>,
+    pub locked_until: Option<OffsetDateTime>,
+    pub last_error: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+    pub completed_at: Option<OffsetDateTime>,
+}
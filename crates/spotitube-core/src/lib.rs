@@ -1,4 +1,15 @@
+pub mod accounts;
+pub mod auth;
 pub mod config;
+pub mod conversions;
 pub mod errors;
+pub mod jobs;
+pub mod matching;
+pub mod oauth;
+pub mod playlists;
+pub mod snapshots;
+pub mod spotify;
+pub mod syncs;
+pub mod users;
 pub mod utils;
-pub mod users;
\ No newline at end of file
+pub mod youtube;
\ No newline at end of file
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use crate::{
+    conversions::title_parser::parse_video_title, spotify::models::SpotifyTrack,
+    youtube::models::YouTubeVideo,
+};
+
+pub type DynTrackMatcher = Arc<dyn TrackMatcher + Send + Sync>;
+
+pub trait TrackMatcher {
+    /// Scores how likely `candidate` is the same recording as `source`.
+    fn score(&self, source: &TrackFeatures, candidate: &TrackFeatures) -> MatchScore;
+}
+
+/// The provider-independent facts a matcher compares.
+#[derive(Debug, Clone, Default)]
+pub struct TrackFeatures {
+    pub title: String,
+    pub artists: Vec<String>,
+    pub duration_ms: Option<i64>,
+    pub isrc: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchScore {
+    /// Between 0 and 1.
+    pub confidence: f64,
+    pub reasons: Vec<String>,
+}
+
+impl TrackFeatures {
+    pub fn from_spotify_track(track: &SpotifyTrack) -> Self {
+        Self {
+            title: track.name.clone(),
+            artists: track
+                .artists
+                .iter()
+                .map(|artist| artist.name.clone())
+                .collect(),
+            duration_ms: Some(track.duration_ms as i64),
+            isrc: track.external_ids.isrc.clone(),
+        }
+    }
+
+    /// Splits a video title into artist and title using the most likely parse.
+    pub fn from_video_title(
+        title: &str,
+        channel_title: Option<&str>,
+        duration_ms: Option<i64>,
+    ) -> Self {
+        let candidate = parse_video_title(title, channel_title).into_iter().next();
+        Self {
+            title: candidate
+                .as_ref()
+                .map(|candidate| candidate.title.clone())
+                .unwrap_or_else(|| String::from(title)),
+            artists: candidate
+                .map(|candidate| candidate.artists)
+                .unwrap_or_default(),
+            duration_ms,
+            isrc: None,
+        }
+    }
+
+    pub fn from_youtube_video(video: &YouTubeVideo) -> Self {
+        Self::from_video_title(
+            &video.snippet.title,
+            video.snippet.channel_title.as_deref(),
+            video
+                .content_details
+                .as_ref()
+                .and_then(|details| details.duration_ms()),
+        )
+    }
+}
+
+/// Scores every candidate and returns them best first.
+pub fn rank_candidates<T>(
+    matcher: &dyn TrackMatcher,
+    source: &TrackFeatures,
+    candidates: Vec<(T, TrackFeatures)>,
+) -> Vec<(T, MatchScore)> {
+    let mut ranked: Vec<(T, MatchScore)> = candidates
+        .into_iter()
+        .map(|(candidate, features)| {
+            let score = matcher.score(source, &features);
+            (candidate, score)
+        })
+        .collect();
+    ranked.sort_by(|(_, left), (_, right)| right.confidence.total_cmp(&left.confidence));
+    ranked
+}
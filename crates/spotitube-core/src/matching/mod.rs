@@ -0,0 +1,4 @@
+pub mod matcher;
+pub mod repository;
+pub mod resolver;
+pub mod scoring_matcher;
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use sqlx::prelude::FromRow;
+use sqlx::types::time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+pub type DynTrackMatchesRepository = Arc<dyn TrackMatchesRepository + Send + Sync>;
+
+/// Matches shared by every user so that a song is only searched for once per destination.
+/// Every user confirms matches separately, so that a single user can not override a match for
+/// everyone unless enough users are required to agree.
+#[async_trait]
+pub trait TrackMatchesRepository {
+    /// Finds a live match for the source track, falling back to any match of the same ISRC.
+    /// Prefers the match the user confirmed, then one confirmed by at least `confirmations`
+    /// users, then an automatic one.
+    async fn find_track_match(
+        &self,
+        user_id: &Uuid,
+        confirmations: i64,
+        source_provider: &str,
+        source_id: &str,
+        isrc: Option<&str>,
+        destination_provider: &str,
+    ) -> SpotitubeResult<Option<TrackMatchEntity>>;
+
+    /// Stores the automatic match of a track, or the one confirmed by a user.
+    async fn upsert_track_match(&self, track_match: NewTrackMatch<'_>) -> SpotitubeResult<()>;
+}
+
+pub struct NewTrackMatch<'a> {
+    pub source_provider: &'a str,
+    pub source_id: &'a str,
+    pub isrc: Option<&'a str>,
+    pub destination_provider: &'a str,
+    pub destination_id: &'a str,
+    pub destination_title: &'a str,
+    pub confidence: f64,
+    /// `None` for matches chosen automatically.
+    pub confirmed_by: Option<&'a Uuid>,
+    /// Only automatic matches expire, and confirmations once their user is deleted.
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+#[derive(FromRow)]
+pub struct TrackMatchEntity {
+    pub id: Uuid,
+    pub source_provider: String,
+    pub source_id: String,
+    pub isrc: Option<String>,
+    pub destination_provider: String,
+    pub destination_id: String,
+    pub destination_title: String,
+    pub confidence: f64,
+    pub confirmed_by: Option<Uuid>,
+    pub expires_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use spotitube_domain::providers::Provider;
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+use super::matcher::MatchScore;
+
+pub type DynTrackResolver = Arc<dyn TrackResolver + Send + Sync>;
+
+/// What is known about a track on the provider it comes from.
+#[derive(Debug, Clone, Copy)]
+pub struct SourceTrack<'a> {
+    pub id: Option<&'a str>,
+    pub title: &'a str,
+    pub artists: &'a [String],
+    pub duration_ms: Option<i64>,
+    pub isrc: Option<&'a str>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolvedTrack {
+    pub id: String,
+    pub title: String,
+    pub score: MatchScore,
+}
+
+#[derive(Debug)]
+pub enum TrackResolution {
+    Matched(ResolvedTrack),
+    /// Candidates best first, none of them confident enough to be used without a review.
+    Unsure(Vec<ResolvedTrack>),
+    NotFound,
+}
+
+#[async_trait]
+pub trait TrackResolver {
+    /// Finds the counterpart of a `source` track on the other provider, from the shared match
+    /// cache when possible and by searching otherwise.
+    async fn resolve(
+        &self,
+        user_id: &Uuid,
+        source: Provider,
+        track: &SourceTrack<'_>,
+    ) -> SpotitubeResult<TrackResolution>;
+}
@@ -0,0 +1,320 @@
+use std::collections::HashSet;
+
+use super::matcher::{MatchScore, TrackFeatures, TrackMatcher};
+
+/// Words dropped before comparing titles since they only describe the upload.
+const NOISE_WORDS: &[&str] = &[
+    "feat",
+    "ft",
+    "featuring",
+    "official",
+    "video",
+    "audio",
+    "lyrics",
+    "lyric",
+    "hd",
+    "hq",
+    "remaster",
+    "remastered",
+    "version",
+];
+
+/// Variants that are almost never what the listener wants when the source is the original,
+/// with the confidence each costs.
+const VARIANTS: &[(&str, f64)] = &[
+    ("karaoke", 0.5),
+    ("cover", 0.4),
+    ("instrumental", 0.4),
+    ("nightcore", 0.4),
+    ("live", 0.3),
+    ("remix", 0.3),
+    ("acoustic", 0.2),
+];
+
+/// Weighted blend of title similarity, artist similarity and duration proximity, overridden
+/// by an ISRC match and reduced by unwanted variants.
+pub struct ScoringTrackMatcher {
+    pub title_weight: f64,
+    pub artist_weight: f64,
+    pub duration_weight: f64,
+    /// Durations closer than this count as identical.
+    pub duration_tolerance_ms: i64,
+    /// Durations further apart than this contribute nothing.
+    pub duration_cutoff_ms: i64,
+}
+
+impl Default for ScoringTrackMatcher {
+    fn default() -> Self {
+        Self {
+            title_weight: 0.5,
+            artist_weight: 0.3,
+            duration_weight: 0.2,
+            duration_tolerance_ms: 3000,
+            duration_cutoff_ms: 30000,
+        }
+    }
+}
+
+impl ScoringTrackMatcher {
+    fn duration_score(&self, source: Option<i64>, candidate: Option<i64>) -> (f64, Option<String>) {
+        let (Some(source), Some(candidate)) = (source, candidate) else {
+            return (0.5, None);
+        };
+
+        let delta = (source - candidate).abs();
+        let score = if delta <= self.duration_tolerance_ms {
+            1.0
+        } else if delta >= self.duration_cutoff_ms {
+            0.0
+        } else {
+            1.0 - (delta - self.duration_tolerance_ms) as f64
+                / (self.duration_cutoff_ms - self.duration_tolerance_ms) as f64
+        };
+
+        (
+            score,
+            Some(format!("duration differs by {}s", delta / 1000)),
+        )
+    }
+
+    fn artist_score(source: &TrackFeatures, candidate: &TrackFeatures) -> f64 {
+        if source.artists.is_empty() {
+            return 0.5;
+        }
+
+        let candidate_title = normalize(&candidate.title);
+        source
+            .artists
+            .iter()
+            .map(|artist| {
+                let artist = normalize(artist);
+                if !artist.is_empty() && candidate_title.contains(&artist) {
+                    return 1.0;
+                }
+                candidate
+                    .artists
+                    .iter()
+                    .map(|candidate_artist| similarity(&artist, &normalize(candidate_artist)))
+                    .fold(0.0, f64::max)
+            })
+            .fold(0.0, f64::max)
+    }
+}
+
+impl TrackMatcher for ScoringTrackMatcher {
+    fn score(&self, source: &TrackFeatures, candidate: &TrackFeatures) -> MatchScore {
+        let mut reasons = Vec::new();
+
+        let title_score = similarity(&normalize(&source.title), &normalize(&candidate.title));
+        reasons.push(format!("title similarity {:.2}", title_score));
+
+        let artist_score = Self::artist_score(source, candidate);
+        reasons.push(format!("artist similarity {:.2}", artist_score));
+
+        let (duration_score, duration_reason) =
+            self.duration_score(source.duration_ms, candidate.duration_ms);
+        reasons.extend(duration_reason);
+
+        let mut confidence = self.title_weight * title_score
+            + self.artist_weight * artist_score
+            + self.duration_weight * duration_score;
+
+        match (&source.isrc, &candidate.isrc) {
+            (Some(source_isrc), Some(candidate_isrc))
+                if source_isrc.eq_ignore_ascii_case(candidate_isrc) =>
+            {
+                // the same recording, whatever its title says about it
+                reasons.push(String::from("isrc matches"));
+                return MatchScore {
+                    confidence: 1.0,
+                    reasons,
+                };
+            }
+            (Some(_), Some(_)) => {
+                confidence -= 0.1;
+                reasons.push(String::from("isrc differs"));
+            }
+            _ => {}
+        }
+
+        let source_words = words(&format!("{} {}", source.title, source.artists.join(" ")));
+        let candidate_words = words(&format!(
+            "{} {}",
+            candidate.title,
+            candidate.artists.join(" ")
+        ));
+        for (variant, penalty) in VARIANTS {
+            if candidate_words.contains(*variant) && !source_words.contains(*variant) {
+                confidence -= penalty;
+                reasons.push(format!("candidate is a {} version", variant));
+            }
+        }
+
+        MatchScore {
+            confidence: confidence.clamp(0.0, 1.0),
+            reasons,
+        }
+    }
+}
+
+fn words(value: &str) -> HashSet<String> {
+    value
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Lowercases, replaces punctuation with spaces and drops noise words.
+fn normalize(value: &str) -> String {
+    value
+        .to_lowercase()
+        .replace('&', " and ")
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty() && !NOISE_WORDS.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Sørensen–Dice coefficient over character bigrams.
+fn similarity(left: &str, right: &str) -> f64 {
+    if left == right {
+        return if left.is_empty() { 0.0 } else { 1.0 };
+    }
+
+    let bigrams = |value: &str| -> Vec<(char, char)> {
+        let chars: Vec<char> = value.chars().collect();
+        chars.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    };
+    let left = bigrams(left);
+    let mut right = bigrams(right);
+    if left.is_empty() || right.is_empty() {
+        return 0.0;
+    }
+
+    let total = left.len() + right.len();
+    let mut shared = 0;
+    for bigram in left {
+        if let Some(index) = right.iter().position(|candidate| *candidate == bigram) {
+            right.swap_remove(index);
+            shared += 1;
+        }
+    }
+
+    2.0 * shared as f64 / total as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{similarity, ScoringTrackMatcher};
+    use crate::matching::matcher::{TrackFeatures, TrackMatcher};
+
+    fn track(title: &str, artists: &[&str], duration_ms: Option<i64>) -> TrackFeatures {
+        TrackFeatures {
+            title: String::from(title),
+            artists: artists.iter().map(|artist| String::from(*artist)).collect(),
+            duration_ms,
+            isrc: None,
+        }
+    }
+
+    fn with_isrc(mut track: TrackFeatures, isrc: &str) -> TrackFeatures {
+        track.isrc = Some(String::from(isrc));
+        track
+    }
+
+    fn confidence(source: &TrackFeatures, candidate: &TrackFeatures) -> f64 {
+        ScoringTrackMatcher::default()
+            .score(source, candidate)
+            .confidence
+    }
+
+    #[test]
+    fn identical_tracks_are_certain() {
+        let source = track("Bohemian Rhapsody", &["Queen"], Some(354000));
+
+        assert_eq!(confidence(&source, &source), 1.0);
+    }
+
+    #[test]
+    fn ignores_upload_descriptions_and_artist_in_the_video_title() {
+        let source = track("Bohemian Rhapsody", &["Queen"], Some(354000));
+        let video = track(
+            "Queen - Bohemian Rhapsody (Official Video)",
+            &["Queen Official"],
+            Some(355000),
+        );
+
+        assert!(confidence(&source, &video) > 0.8);
+    }
+
+    #[test]
+    fn unrelated_tracks_score_low() {
+        let source = track("Bohemian Rhapsody", &["Queen"], Some(354000));
+        let candidate = track("Levitating", &["Dua Lipa"], Some(203000));
+
+        assert!(confidence(&source, &candidate) < 0.3);
+    }
+
+    #[test]
+    fn duration_counts_less_the_further_apart() {
+        let source = track("Hello", &["Adele"], Some(295000));
+        let close = track("Hello", &["Adele"], Some(297000));
+        let off = track("Hello", &["Adele"], Some(310000));
+        let far = track("Hello", &["Adele"], Some(400000));
+        let unknown = track("Hello", &["Adele"], None);
+
+        assert_eq!(confidence(&source, &close), 1.0);
+        assert!(confidence(&source, &off) < 1.0);
+        assert!(confidence(&source, &far) < confidence(&source, &off));
+        assert!(confidence(&source, &unknown) > confidence(&source, &far));
+    }
+
+    #[test]
+    fn penalizes_unwanted_variants() {
+        let source = track("Levitating", &["Dua Lipa"], Some(203000));
+        let karaoke = track("Levitating (Karaoke Version)", &["Dua Lipa"], Some(203000));
+        let live = track("Levitating (Live)", &["Dua Lipa"], Some(203000));
+
+        assert!(confidence(&source, &karaoke) < 0.7);
+        assert!(confidence(&source, &live) < confidence(&source, &source));
+    }
+
+    #[test]
+    fn variants_the_source_asked_for_are_not_penalized() {
+        let source = track("Levitating (Live)", &["Dua Lipa"], Some(203000));
+
+        assert_eq!(confidence(&source, &source), 1.0);
+    }
+
+    #[test]
+    fn isrc_match_is_certain_even_for_variant_titles() {
+        let source = with_isrc(track("Let It Be", &["The Beatles"], None), "GBAYE0601713");
+        let candidate = with_isrc(
+            track("Let It Be (Live)", &["The Beatles"], Some(243000)),
+            "gbaye0601713",
+        );
+
+        let score = ScoringTrackMatcher::default().score(&source, &candidate);
+
+        assert_eq!(score.confidence, 1.0);
+        assert!(score.reasons.iter().any(|reason| reason == "isrc matches"));
+    }
+
+    #[test]
+    fn different_isrcs_cost_confidence() {
+        let source = with_isrc(track("Hello", &["Adele"], Some(295000)), "GBBKS1500214");
+        let candidate = with_isrc(track("Hello", &["Adele"], Some(295000)), "USUM71100001");
+
+        assert!((confidence(&source, &candidate) - 0.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn similarity_compares_character_bigrams() {
+        assert_eq!(similarity("night", "night"), 1.0);
+        assert_eq!(similarity("", ""), 0.0);
+        assert_eq!(similarity("ab", "cd"), 0.0);
+        assert!((similarity("night", "nacht") - 0.25).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::SpotitubeResult;
+
+pub type DynOAuthClient = Arc<dyn OAuthClient + Send + Sync>;
+
+#[async_trait]
+pub trait OAuthClient {
+    fn authorize_url(&self, state: &str, code_challenge: &str) -> SpotitubeResult<String>;
+    async fn exchange_code(&self, code: &str, code_verifier: &str) -> SpotitubeResult<OAuthTokens>;
+    async fn refresh_access_token(&self, refresh_token: &str) -> SpotitubeResult<OAuthTokens>;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthTokens {
+    pub access_token: String,
+    pub refresh_token: Option<String>,
+    pub expires_in: i64,
+    pub scope: Option<String>,
+}
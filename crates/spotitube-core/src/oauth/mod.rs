@@ -0,0 +1,3 @@
+pub mod client;
+pub mod repository;
+pub mod service;
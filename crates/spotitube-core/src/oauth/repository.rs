@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use sqlx::prelude::FromRow;
+use sqlx::types::time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+pub type DynOAuthAuthorizationsRepository = Arc<dyn OAuthAuthorizationsRepository + Send + Sync>;
+
+#[async_trait]
+pub trait OAuthAuthorizationsRepository {
+    async fn create_authorization(
+        &self,
+        state: &str,
+        user_id: &Uuid,
+        provider: &str,
+        code_verifier: &str,
+        expires_at: OffsetDateTime,
+    ) -> SpotitubeResult<OAuthAuthorizationEntity>;
+
+    /// Removes and returns the pending authorization so that a state can only be used once.
+    async fn take_authorization(
+        &self,
+        state: &str,
+    ) -> SpotitubeResult<Option<OAuthAuthorizationEntity>>;
+}
+
+#[derive(FromRow)]
+pub struct OAuthAuthorizationEntity {
+    pub state: String,
+    pub user_id: Uuid,
+    pub provider: String,
+    pub code_verifier: String,
+    pub expires_at: OffsetDateTime,
+    pub created_at: OffsetDateTime,
+}
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use spotitube_domain::providers::Provider;
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+use super::client::OAuthTokens;
+
+pub type DynOAuthService = Arc<dyn OAuthService + Send + Sync>;
+
+#[async_trait]
+pub trait OAuthService {
+    /// Starts an authorization-code + PKCE flow and returns the provider URL to redirect to.
+    async fn begin_authorization(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+    ) -> SpotitubeResult<String>;
+    async fn complete_authorization(
+        &self,
+        state: &str,
+        code: &str,
+    ) -> SpotitubeResult<CompletedAuthorization>;
+}
+
+#[derive(Debug)]
+pub struct CompletedAuthorization {
+    pub user_id: Uuid,
+    pub provider: Provider,
+    pub tokens: OAuthTokens,
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use spotitube_domain::providers::Provider;
+use uuid::Uuid;
+
+use crate::{errors::SpotitubeResult, matching::resolver::SourceTrack};
+
+pub type DynPlaylistEditor = Arc<dyn PlaylistEditor + Send + Sync>;
+
+/// A playlist as it currently is on its provider.
+#[derive(Debug, Clone)]
+pub struct PlaylistContents {
+    pub name: String,
+    pub description: Option<String>,
+    /// Spotify snapshot id or YouTube etag of the playlist.
+    pub source_version: Option<String>,
+    pub items: Vec<PlaylistItem>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlaylistItem {
+    /// Spotify track id or YouTube video id; `None` for local files and unavailable videos,
+    /// which can not be added or moved.
+    pub id: Option<String>,
+    /// Id of the YouTube playlist item, needed to move or delete it.
+    pub item_id: Option<String>,
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub isrc: Option<String>,
+}
+
+impl PlaylistItem {
+    pub fn source_track(&self) -> SourceTrack<'_> {
+        SourceTrack {
+            id: self.id.as_deref(),
+            title: &self.title,
+            artists: &self.artists,
+            duration_ms: self.duration_ms,
+            isrc: self.isrc.as_deref(),
+        }
+    }
+}
+
+#[async_trait]
+pub trait PlaylistEditor {
+    async fn read_playlist(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+        playlist_id: &str,
+    ) -> SpotitubeResult<PlaylistContents>;
+
+    /// Brings the playlist from `contents`, as last read, to the tracks of `target` in order
+    /// with as few edits as possible. Local files and unavailable videos are left where they are.
+    async fn apply(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+        playlist_id: &str,
+        contents: &PlaylistContents,
+        target: &[&str],
+    ) -> SpotitubeResult<()>;
+}
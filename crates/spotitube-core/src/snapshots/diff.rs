@@ -0,0 +1,158 @@
+use std::collections::{HashMap, VecDeque};
+
+use spotitube_domain::snapshots::{MovedTrackDto, SnapshotDiffDto, SnapshotTrackDto};
+use uuid::Uuid;
+
+use crate::syncs::merge::common_subsequence;
+
+/// Tracks added, removed and moved between two versions of a playlist. Tracks that kept their
+/// order relative to each other count as unchanged even when their positions shifted.
+pub fn diff_snapshots(
+    from_id: Uuid,
+    from: &[SnapshotTrackDto],
+    to_id: Uuid,
+    to: &[SnapshotTrackDto],
+) -> SnapshotDiffDto {
+    let from_keys: Vec<(Option<&str>, &str)> = from.iter().map(track_key).collect();
+    let to_keys: Vec<(Option<&str>, &str)> = to.iter().map(track_key).collect();
+    let matches = common_subsequence(&from_keys, &to_keys);
+
+    let mut kept = vec![false; to.len()];
+    let mut unmatched: HashMap<(Option<&str>, &str), VecDeque<&SnapshotTrackDto>> = HashMap::new();
+    for (track, matched) in from.iter().zip(&matches) {
+        match matched {
+            Some(index) => kept[*index] = true,
+            None => unmatched
+                .entry(track_key(track))
+                .or_default()
+                .push_back(track),
+        }
+    }
+
+    let mut added = Vec::new();
+    let mut moved = Vec::new();
+    for (track, _) in to.iter().zip(&kept).filter(|(_, kept)| !**kept) {
+        match unmatched
+            .get_mut(&track_key(track))
+            .and_then(|tracks| tracks.pop_front())
+        {
+            Some(previous) => moved.push(MovedTrackDto {
+                from_position: previous.position,
+                track: track.clone(),
+            }),
+            None => added.push(track.clone()),
+        }
+    }
+
+    let mut removed: Vec<SnapshotTrackDto> = unmatched.into_values().flatten().cloned().collect();
+    removed.sort_by_key(|track| track.position);
+
+    SnapshotDiffDto {
+        from: from_id,
+        to: to_id,
+        added,
+        removed,
+        moved,
+    }
+}
+
+/// Local files and unavailable videos have no id and are told apart by their title.
+fn track_key(track: &SnapshotTrackDto) -> (Option<&str>, &str) {
+    match &track.provider_id {
+        Some(provider_id) => (Some(provider_id), ""),
+        None => (None, &track.title),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use spotitube_domain::snapshots::{SnapshotDiffDto, SnapshotTrackDto};
+    use uuid::Uuid;
+
+    use super::diff_snapshots;
+
+    /// Tracks at consecutive positions, `local:` ids standing for local files.
+    fn tracks(ids: &[&str]) -> Vec<SnapshotTrackDto> {
+        ids.iter()
+            .enumerate()
+            .map(|(position, id)| SnapshotTrackDto {
+                position: position as i32,
+                provider_id: Some(String::from(*id)).filter(|id| !id.starts_with("local:")),
+                title: String::from(*id),
+                artists: Vec::new(),
+                album: None,
+                duration_ms: None,
+                isrc: None,
+            })
+            .collect()
+    }
+
+    fn diff(from: &[&str], to: &[&str]) -> SnapshotDiffDto {
+        diff_snapshots(Uuid::nil(), &tracks(from), Uuid::nil(), &tracks(to))
+    }
+
+    fn titles(tracks: &[SnapshotTrackDto]) -> Vec<(i32, &str)> {
+        tracks
+            .iter()
+            .map(|track| (track.position, track.title.as_str()))
+            .collect()
+    }
+
+    fn moves(diff: &SnapshotDiffDto) -> Vec<(i32, i32, &str)> {
+        diff.moved
+            .iter()
+            .map(|moved| {
+                let track = &moved.track;
+                (moved.from_position, track.position, track.title.as_str())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reports_added_tracks_at_their_new_position() {
+        let diff = diff(&["a", "b"], &["a", "c", "b", "d"]);
+
+        assert_eq!(titles(&diff.added), [(1, "c"), (3, "d")]);
+        assert!(diff.removed.is_empty());
+        assert!(diff.moved.is_empty());
+    }
+
+    #[test]
+    fn reports_removed_tracks_at_their_old_position() {
+        let diff = diff(&["a", "b", "c", "d"], &["a", "c"]);
+
+        assert!(diff.added.is_empty());
+        assert_eq!(titles(&diff.removed), [(1, "b"), (3, "d")]);
+        assert!(diff.moved.is_empty());
+    }
+
+    #[test]
+    fn reports_only_the_track_that_moved() {
+        let diff = diff(&["a", "b", "c", "d"], &["d", "a", "b", "c"]);
+
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(moves(&diff), [(3, 0, "d")]);
+    }
+
+    #[test]
+    fn reports_a_duplicate_copy_as_added() {
+        let diff = diff(&["a", "b"], &["a", "b", "a"]);
+
+        assert_eq!(titles(&diff.added), [(2, "a")]);
+        assert!(diff.removed.is_empty());
+        assert!(diff.moved.is_empty());
+    }
+
+    #[test]
+    fn tells_local_files_apart_by_title() {
+        let diff = diff(
+            &["a", "local:Demo", "b"],
+            &["local:Demo (take 2)", "a", "b", "local:Demo"],
+        );
+
+        assert_eq!(titles(&diff.added), [(0, "local:Demo (take 2)")]);
+        assert!(diff.removed.is_empty());
+        assert_eq!(moves(&diff), [(1, 3, "local:Demo")]);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod diff;
+pub mod recorder;
+pub mod repository;
+pub mod service;
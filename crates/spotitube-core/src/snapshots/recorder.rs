@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use spotitube_domain::{providers::Provider, snapshots::SnapshotOrigin};
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+use super::repository::PlaylistSnapshotEntity;
+
+pub type DynSnapshotRecorder = Arc<dyn SnapshotRecorder + Send + Sync>;
+
+#[async_trait]
+pub trait SnapshotRecorder {
+    /// Reads the playlist and stores it as a new version, unless it is unchanged since the
+    /// latest version, which is returned instead.
+    async fn record(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+        playlist_id: &str,
+        origin: SnapshotOrigin,
+    ) -> SpotitubeResult<PlaylistSnapshotEntity>;
+}
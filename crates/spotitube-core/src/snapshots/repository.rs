@@ -0,0 +1,147 @@
+use std::{str::FromStr, sync::Arc};
+
+use axum::async_trait;
+use spotitube_domain::{
+    providers::Provider,
+    snapshots::{PlaylistSnapshotDto, SnapshotTrackDto},
+};
+use sqlx::prelude::FromRow;
+use sqlx::types::time::OffsetDateTime;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::errors::{SpotitubeError, SpotitubeResult};
+
+pub type DynSnapshotsRepository = Arc<dyn SnapshotsRepository + Send + Sync>;
+
+#[async_trait]
+pub trait SnapshotsRepository {
+    /// Stores the snapshot as the next version of its playlist.
+    async fn create_snapshot(
+        &self,
+        snapshot: &NewPlaylistSnapshot,
+        tracks: &[NewSnapshotTrack],
+    ) -> SpotitubeResult<PlaylistSnapshotEntity>;
+
+    async fn get_snapshot(&self, id: &Uuid) -> SpotitubeResult<Option<PlaylistSnapshotEntity>>;
+
+    async fn get_latest_snapshot(
+        &self,
+        user_id: &Uuid,
+        provider: &str,
+        playlist_id: &str,
+    ) -> SpotitubeResult<Option<PlaylistSnapshotEntity>>;
+
+    /// Versions of the playlist, newest first.
+    async fn get_playlist_snapshots(
+        &self,
+        user_id: &Uuid,
+        provider: &str,
+        playlist_id: &str,
+    ) -> SpotitubeResult<Vec<PlaylistSnapshotEntity>>;
+
+    async fn get_snapshot_tracks(
+        &self,
+        snapshot_id: &Uuid,
+    ) -> SpotitubeResult<Vec<SnapshotTrackEntity>>;
+}
+
+#[derive(Debug, Clone)]
+pub struct NewPlaylistSnapshot {
+    pub user_id: Uuid,
+    pub provider: String,
+    pub playlist_id: String,
+    pub origin: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub source_version: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewSnapshotTrack {
+    pub provider_id: Option<String>,
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub isrc: Option<String>,
+}
+
+#[derive(FromRow)]
+pub struct PlaylistSnapshotEntity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub provider: String,
+    pub playlist_id: String,
+    pub version: i32,
+    pub origin: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub source_version: Option<String>,
+    pub track_count: i32,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(FromRow)]
+pub struct SnapshotTrackEntity {
+    pub id: Uuid,
+    pub snapshot_id: Uuid,
+    pub position: i32,
+    pub provider_id: Option<String>,
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub isrc: Option<String>,
+}
+
+fn parse_column<T: FromStr<Err = String>>(id: &Uuid, value: &str) -> SpotitubeResult<T> {
+    T::from_str(value).map_err(|err| {
+        error!("snapshot {:?} has {}", id, err);
+        SpotitubeError::InternalServerError
+    })
+}
+
+impl PlaylistSnapshotEntity {
+    pub fn provider(&self) -> SpotitubeResult<Provider> {
+        parse_column(&self.id, &self.provider)
+    }
+
+    pub fn into_dto(
+        self,
+        tracks: Option<Vec<SnapshotTrackEntity>>,
+    ) -> SpotitubeResult<PlaylistSnapshotDto> {
+        Ok(PlaylistSnapshotDto {
+            provider: self.provider()?,
+            origin: parse_column(&self.id, &self.origin)?,
+            id: self.id,
+            playlist_id: self.playlist_id,
+            version: self.version,
+            name: self.name,
+            description: self.description,
+            source_version: self.source_version,
+            track_count: self.track_count,
+            tracks: tracks.map(|tracks| {
+                tracks
+                    .into_iter()
+                    .map(SnapshotTrackEntity::into_dto)
+                    .collect()
+            }),
+            created_at: self.created_at,
+        })
+    }
+}
+
+impl SnapshotTrackEntity {
+    pub fn into_dto(self) -> SnapshotTrackDto {
+        SnapshotTrackDto {
+            position: self.position,
+            provider_id: self.provider_id,
+            title: self.title,
+            artists: self.artists,
+            album: self.album,
+            duration_ms: self.duration_ms,
+            isrc: self.isrc,
+        }
+    }
+}
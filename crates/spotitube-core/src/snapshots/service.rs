@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use spotitube_domain::{
+    providers::Provider,
+    snapshots::{PlaylistSnapshotDto, SnapshotDiffDto},
+};
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+pub type DynSnapshotsService = Arc<dyn SnapshotsService + Send + Sync>;
+
+#[async_trait]
+pub trait SnapshotsService {
+    async fn create_snapshot(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+        playlist_id: &str,
+    ) -> SpotitubeResult<PlaylistSnapshotDto>;
+    async fn list_snapshots(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+        playlist_id: &str,
+    ) -> SpotitubeResult<Vec<PlaylistSnapshotDto>>;
+    async fn get_snapshot(&self, user_id: &Uuid, id: &Uuid)
+        -> SpotitubeResult<PlaylistSnapshotDto>;
+    async fn diff_snapshots(
+        &self,
+        user_id: &Uuid,
+        from: &Uuid,
+        to: &Uuid,
+    ) -> SpotitubeResult<SnapshotDiffDto>;
+
+    /// Puts the playlist the snapshot was taken of back the way it was, and returns the
+    /// version this creates.
+    async fn restore_snapshot(
+        &self,
+        user_id: &Uuid,
+        id: &Uuid,
+    ) -> SpotitubeResult<PlaylistSnapshotDto>;
+}
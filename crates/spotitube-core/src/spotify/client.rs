@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use crate::errors::SpotitubeResult;
+
+use super::models::{
+    SpotifyPage, SpotifyPlaylist, SpotifyPlaylistTrack, SpotifySavedTrack, SpotifyTrack,
+    SpotifyUser,
+};
+
+pub type DynSpotifyClient = Arc<dyn SpotifyClient + Send + Sync>;
+
+#[async_trait]
+pub trait SpotifyClient {
+    async fn get_current_user(&self, access_token: &str) -> SpotitubeResult<SpotifyUser>;
+
+    async fn get_current_user_playlists(
+        &self,
+        access_token: &str,
+        limit: u32,
+        offset: u32,
+    ) -> SpotitubeResult<SpotifyPage<SpotifyPlaylist>>;
+
+    async fn get_playlist(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+    ) -> SpotitubeResult<SpotifyPlaylist>;
+
+    async fn get_playlist_tracks(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        limit: u32,
+        offset: u32,
+    ) -> SpotitubeResult<SpotifyPage<SpotifyPlaylistTrack>>;
+
+    async fn get_saved_tracks(
+        &self,
+        access_token: &str,
+        limit: u32,
+        offset: u32,
+    ) -> SpotitubeResult<SpotifyPage<SpotifySavedTrack>>;
+
+    async fn get_track(&self, access_token: &str, track_id: &str) -> SpotitubeResult<SpotifyTrack>;
+
+    async fn search_tracks(
+        &self,
+        access_token: &str,
+        query: &str,
+        limit: u32,
+    ) -> SpotitubeResult<Vec<SpotifyTrack>>;
+
+    async fn create_playlist(
+        &self,
+        access_token: &str,
+        user_id: &str,
+        name: &str,
+        description: &str,
+        public: bool,
+    ) -> SpotitubeResult<SpotifyPlaylist>;
+
+    /// Inserts tracks at `position`, or appends them when `None`, and returns the resulting
+    /// snapshot id.
+    async fn add_tracks_to_playlist(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        uris: &[String],
+        position: Option<u32>,
+    ) -> SpotitubeResult<String>;
+
+    /// Removes the tracks at the given positions of the playlist as it was at `snapshot_id`, or
+    /// as it is when there is none, and returns the resulting snapshot id.
+    async fn remove_tracks_from_playlist(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        tracks: &[(String, u32)],
+        snapshot_id: Option<&str>,
+    ) -> SpotitubeResult<String>;
+
+    /// Moves the track at `range_start` in front of the track at `insert_before` and returns
+    /// the resulting snapshot id.
+    async fn reorder_playlist_track(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        range_start: u32,
+        insert_before: u32,
+    ) -> SpotitubeResult<String>;
+}
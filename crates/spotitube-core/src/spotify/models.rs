@@ -0,0 +1,318 @@
+use serde::{Deserialize, Serialize};
+use spotitube_domain::{
+    catalog::{Album, Artist, ExternalId, Playlist, Track},
+    providers::Provider,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifyPage<T> {
+    pub items: Vec<T>,
+    pub limit: u32,
+    pub offset: u32,
+    pub total: u32,
+    pub next: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifyUser {
+    pub id: String,
+    pub display_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifyPlaylist {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub public: Option<bool>,
+    pub snapshot_id: String,
+    pub uri: String,
+    pub owner: SpotifyUser,
+    pub tracks: SpotifyPlaylistTracksRef,
+}
+
+impl SpotifyPlaylist {
+    pub fn into_playlist(self, tracks: Vec<Track>) -> Playlist {
+        Playlist {
+            name: self.name,
+            description: self
+                .description
+                .filter(|description| !description.is_empty()),
+            external_ids: vec![ExternalId::new(Provider::Spotify, self.id)],
+            tracks,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifyPlaylistTracksRef {
+    pub total: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifyPlaylistTrack {
+    pub added_at: Option<String>,
+    #[serde(default)]
+    pub is_local: bool,
+    /// `None` for tracks that are no longer available.
+    pub track: Option<SpotifyTrack>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifySavedTrack {
+    pub added_at: String,
+    pub track: SpotifyTrack,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifyTrack {
+    /// `None` for local files.
+    pub id: Option<String>,
+    pub name: String,
+    pub uri: String,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub explicit: bool,
+    pub artists: Vec<SpotifyArtist>,
+    pub album: Option<SpotifyAlbum>,
+    #[serde(default)]
+    pub external_ids: SpotifyExternalIds,
+}
+
+impl SpotifyTrack {
+    /// `Artist, Artist - Name`, the way the track is shown back to users.
+    pub fn display_title(&self) -> String {
+        let artists: Vec<&str> = self
+            .artists
+            .iter()
+            .map(|artist| artist.name.as_str())
+            .collect();
+        format!("{} - {}", artists.join(", "), self.name)
+    }
+
+    pub fn into_track(self) -> Track {
+        Track {
+            title: self.name,
+            artists: self
+                .artists
+                .into_iter()
+                .map(SpotifyArtist::into_artist)
+                .collect(),
+            album: self.album.map(SpotifyAlbum::into_album),
+            duration_ms: Some(self.duration_ms as i64),
+            explicit: self.explicit,
+            isrc: self.external_ids.isrc,
+            external_ids: spotify_ids(self.id),
+            parsed_title: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifyArtist {
+    pub id: Option<String>,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifyAlbum {
+    pub id: Option<String>,
+    pub name: String,
+    pub release_date: Option<String>,
+    #[serde(default)]
+    pub artists: Vec<SpotifyArtist>,
+    #[serde(default)]
+    pub external_ids: SpotifyExternalIds,
+}
+
+impl SpotifyArtist {
+    pub fn into_artist(self) -> Artist {
+        Artist {
+            name: self.name,
+            external_ids: spotify_ids(self.id),
+        }
+    }
+}
+
+impl SpotifyAlbum {
+    pub fn into_album(self) -> Album {
+        Album {
+            title: self.name,
+            artists: self
+                .artists
+                .into_iter()
+                .map(SpotifyArtist::into_artist)
+                .collect(),
+            release_date: self.release_date,
+            upc: self.external_ids.upc,
+            external_ids: spotify_ids(self.id),
+        }
+    }
+}
+
+fn spotify_ids(id: Option<String>) -> Vec<ExternalId> {
+    id.map(|id| ExternalId::new(Provider::Spotify, id))
+        .into_iter()
+        .collect()
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpotifyExternalIds {
+    pub isrc: Option<String>,
+    pub upc: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifySearchResponse {
+    pub tracks: SpotifyPage<SpotifyTrack>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpotifySnapshotResponse {
+    pub snapshot_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use spotitube_domain::{
+        catalog::{Album, Artist, ExternalId, Playlist, Track},
+        providers::Provider,
+    };
+
+    use super::{SpotifyPlaylist, SpotifyPlaylistTrack};
+
+    fn playlist_track(value: serde_json::Value) -> SpotifyPlaylistTrack {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn maps_a_track_with_its_album_and_ids() {
+        let item = playlist_track(json!({
+            "added_at": "2024-03-01T10:00:00Z",
+            "track": {
+                "id": "3z8h0TU7ReDPLIbEnYhWZb",
+                "name": "Bohemian Rhapsody",
+                "uri": "spotify:track:3z8h0TU7ReDPLIbEnYhWZb",
+                "duration_ms": 354320,
+                "explicit": false,
+                "artists": [{ "id": "1dfeR4HaWDbWqFHLkxsg1d", "name": "Queen" }],
+                "album": {
+                    "id": "6X9k3hSsvQck2OfKYdBbXr",
+                    "name": "A Night At The Opera",
+                    "release_date": "1975-11-21",
+                    "external_ids": { "upc": "00602547202697" }
+                },
+                "external_ids": { "isrc": "GBUM71029604" }
+            }
+        }));
+
+        let queen = Artist {
+            name: String::from("Queen"),
+            external_ids: vec![ExternalId::new(Provider::Spotify, "1dfeR4HaWDbWqFHLkxsg1d")],
+        };
+        assert_eq!(
+            item.track.unwrap().into_track(),
+            Track {
+                title: String::from("Bohemian Rhapsody"),
+                artists: vec![queen],
+                album: Some(Album {
+                    title: String::from("A Night At The Opera"),
+                    artists: Vec::new(),
+                    release_date: Some(String::from("1975-11-21")),
+                    upc: Some(String::from("00602547202697")),
+                    external_ids: vec![ExternalId::new(
+                        Provider::Spotify,
+                        "6X9k3hSsvQck2OfKYdBbXr"
+                    )],
+                }),
+                duration_ms: Some(354320),
+                explicit: false,
+                isrc: Some(String::from("GBUM71029604")),
+                external_ids: vec![ExternalId::new(Provider::Spotify, "3z8h0TU7ReDPLIbEnYhWZb")],
+                parsed_title: None,
+            }
+        );
+    }
+
+    #[test]
+    fn maps_a_local_file_without_ids() {
+        let item = playlist_track(json!({
+            "added_at": null,
+            "is_local": true,
+            "track": {
+                "id": null,
+                "name": "Demo",
+                "uri": "spotify:local:::Demo:180",
+                "duration_ms": 180000,
+                "artists": [{ "id": null, "name": "Garage Band" }],
+                "album": null
+            }
+        }));
+
+        let track = item.track.unwrap().into_track();
+
+        assert!(track.external_ids.is_empty());
+        assert_eq!(track.external_id(Provider::Spotify), None);
+        assert_eq!(track.artists[0].external_ids, Vec::new());
+        assert_eq!(track.album, None);
+    }
+
+    #[test]
+    fn maps_a_playlist_without_an_empty_description() {
+        let playlist: SpotifyPlaylist = serde_json::from_value(json!({
+            "id": "road",
+            "name": "Road trip",
+            "description": "",
+            "public": true,
+            "snapshot_id": "MTY",
+            "uri": "spotify:playlist:road",
+            "owner": { "id": "owner", "display_name": null },
+            "tracks": { "total": 0 }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            playlist.into_playlist(Vec::new()),
+            Playlist {
+                name: String::from("Road trip"),
+                description: None,
+                external_ids: vec![ExternalId::new(Provider::Spotify, "road")],
+                tracks: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn round_trips_a_mapped_playlist_through_json() {
+        let track = playlist_track(json!({
+            "added_at": null,
+            "track": {
+                "id": "4u7EnebtmKWzUH433cf5Qv",
+                "name": "Under Pressure",
+                "uri": "spotify:track:4u7EnebtmKWzUH433cf5Qv",
+                "duration_ms": 248440,
+                "explicit": true,
+                "artists": [
+                    { "id": "1dfeR4HaWDbWqFHLkxsg1d", "name": "Queen" },
+                    { "id": "0oSGxfWSnnOXhD2fKuz2Gy", "name": "David Bowie" }
+                ],
+                "album": { "id": null, "name": "Hot Space", "release_date": "1982" },
+                "external_ids": { "isrc": "GBUM71029606" }
+            }
+        }))
+        .track
+        .unwrap()
+        .into_track();
+        let playlist = Playlist {
+            name: String::from("Duets"),
+            description: Some(String::from("Two voices")),
+            external_ids: vec![ExternalId::new(Provider::Spotify, "duets")],
+            tracks: vec![track],
+        };
+
+        let json = serde_json::to_string(&playlist).unwrap();
+
+        assert_eq!(serde_json::from_str::<Playlist>(&json).unwrap(), playlist);
+    }
+}
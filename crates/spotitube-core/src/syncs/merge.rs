@@ -0,0 +1,291 @@
+/// Result of merging the changes two sides made to the same base list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Merge<T> {
+    pub items: Vec<T>,
+    pub conflicts: Vec<MergeConflict<T>>,
+}
+
+/// A range of the base list both sides changed in different ways.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict<T> {
+    /// Where the range starts in the merged list.
+    pub position: usize,
+    pub base: Vec<T>,
+    pub left: Vec<T>,
+    pub right: Vec<T>,
+}
+
+/// Three-way merge (diff3) of two edited copies of `base`. Changes to different parts of the
+/// base are all applied. Where both sides changed the same range differently, everything either
+/// side has there is kept, left first, and the range is reported as a conflict; both sides only
+/// inserting at the same place keeps both insertions without a conflict.
+pub fn merge<T: Clone + PartialEq>(base: &[T], left: &[T], right: &[T]) -> Merge<T> {
+    let left_matches = common_subsequence(base, left);
+    let right_matches = common_subsequence(base, right);
+
+    let mut items = Vec::with_capacity(left.len().max(right.len()));
+    let mut conflicts = Vec::new();
+    let (mut base_index, mut left_index, mut right_index) = (0, 0, 0);
+    while base_index < base.len() || left_index < left.len() || right_index < right.len() {
+        if base_index < base.len()
+            && left_matches[base_index] == Some(left_index)
+            && right_matches[base_index] == Some(right_index)
+        {
+            items.push(base[base_index].clone());
+            base_index += 1;
+            left_index += 1;
+            right_index += 1;
+            continue;
+        }
+
+        // The changed range runs up to the next item both sides kept.
+        let (base_end, left_end, right_end) = (base_index..base.len())
+            .find_map(|index| Some((index, left_matches[index]?, right_matches[index]?)))
+            .unwrap_or((base.len(), left.len(), right.len()));
+        let base_range = &base[base_index..base_end];
+        let left_range = &left[left_index..left_end];
+        let right_range = &right[right_index..right_end];
+
+        if left_range == base_range {
+            items.extend_from_slice(right_range);
+        } else if right_range == base_range || left_range == right_range {
+            items.extend_from_slice(left_range);
+        } else {
+            let position = items.len();
+            items.extend_from_slice(left_range);
+            items.extend(
+                right_range
+                    .iter()
+                    .filter(|item| !left_range.contains(item))
+                    .cloned(),
+            );
+            if !base_range.is_empty() {
+                conflicts.push(MergeConflict {
+                    position,
+                    base: base_range.to_vec(),
+                    left: left_range.to_vec(),
+                    right: right_range.to_vec(),
+                });
+            }
+        }
+
+        base_index = base_end;
+        left_index = left_end;
+        right_index = right_end;
+    }
+
+    Merge { items, conflicts }
+}
+
+/// For every item of `base`, the index of the same item in `other` along a longest common
+/// subsequence of the two lists.
+pub fn common_subsequence<T: PartialEq>(base: &[T], other: &[T]) -> Vec<Option<usize>> {
+    let mut result = vec![None; base.len()];
+
+    // Playlists mostly change in a few places, so only the middle needs the quadratic part.
+    let prefix = base
+        .iter()
+        .zip(other)
+        .take_while(|(base_item, other_item)| base_item == other_item)
+        .count();
+    let suffix = base[prefix..]
+        .iter()
+        .rev()
+        .zip(other[prefix..].iter().rev())
+        .take_while(|(base_item, other_item)| base_item == other_item)
+        .count();
+    for (index, matched) in result.iter_mut().enumerate().take(prefix) {
+        *matched = Some(index);
+    }
+    for offset in 1..=suffix {
+        result[base.len() - offset] = Some(other.len() - offset);
+    }
+
+    // Hirschberg's algorithm keeps the memory linear, as reordering a playlist of thousands
+    // of tracks would otherwise need a table of hundreds of megabytes.
+    match_middle(
+        &base[prefix..base.len() - suffix],
+        &other[prefix..other.len() - suffix],
+        prefix,
+        prefix,
+        &mut result,
+    );
+
+    result
+}
+
+/// Matches `base` against `other`, which start at the given offsets of the full lists, by
+/// splitting `base` in half where the longest common subsequence crosses it.
+fn match_middle<T: PartialEq>(
+    base: &[T],
+    other: &[T],
+    base_offset: usize,
+    other_offset: usize,
+    result: &mut [Option<usize>],
+) {
+    if base.is_empty() || other.is_empty() {
+        return;
+    }
+    if base.len() == 1 {
+        if let Some(index) = other.iter().position(|item| *item == base[0]) {
+            result[base_offset] = Some(other_offset + index);
+        }
+        return;
+    }
+
+    let half = base.len() / 2;
+    let forward = subsequence_lengths(base[..half].iter(), other.iter());
+    let backward = subsequence_lengths(base[half..].iter().rev(), other.iter().rev());
+    let split = (0..=other.len())
+        .max_by_key(|index| {
+            (
+                forward[*index] + backward[other.len() - index],
+                usize::MAX - index,
+            )
+        })
+        .unwrap_or_default();
+
+    match_middle(
+        &base[..half],
+        &other[..split],
+        base_offset,
+        other_offset,
+        result,
+    );
+    match_middle(
+        &base[half..],
+        &other[split..],
+        base_offset + half,
+        other_offset + split,
+        result,
+    );
+}
+
+/// Length of the longest common subsequence of `base` and every prefix of `other`.
+fn subsequence_lengths<'a, T: PartialEq + 'a>(
+    base: impl Iterator<Item = &'a T>,
+    other: impl Iterator<Item = &'a T> + Clone,
+) -> Vec<u32> {
+    let mut lengths = vec![0u32; other.clone().count() + 1];
+    for base_item in base {
+        let mut diagonal = 0;
+        for (column, other_item) in other.clone().enumerate() {
+            let above = lengths[column + 1];
+            lengths[column + 1] = if base_item == other_item {
+                diagonal + 1
+            } else {
+                above.max(lengths[column])
+            };
+            diagonal = above;
+        }
+    }
+    lengths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{common_subsequence, merge, MergeConflict};
+
+    #[test]
+    fn keeps_an_unchanged_list() {
+        let merged = merge(&['a', 'b', 'c'], &['a', 'b', 'c'], &['a', 'b', 'c']);
+
+        assert_eq!(merged.items, ['a', 'b', 'c']);
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn applies_changes_to_different_parts_from_both_sides() {
+        let base = ['a', 'b', 'c', 'd', 'e'];
+        let left = ['a', 'x', 'b', 'c', 'd', 'e'];
+        let right = ['a', 'b', 'c', 'e'];
+
+        let merged = merge(&base, &left, &right);
+
+        assert_eq!(merged.items, ['a', 'x', 'b', 'c', 'e']);
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn applies_a_move_next_to_an_addition() {
+        let base = ['a', 'b', 'c', 'd'];
+        let left = ['b', 'c', 'a', 'd'];
+        let right = ['a', 'b', 'c', 'd', 'e'];
+
+        let merged = merge(&base, &left, &right);
+
+        assert_eq!(merged.items, ['b', 'c', 'a', 'd', 'e']);
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn keeps_both_insertions_at_the_same_place() {
+        let merged = merge(&['a', 'b'], &['a', 'x', 'b'], &['a', 'y', 'b']);
+
+        assert_eq!(merged.items, ['a', 'x', 'y', 'b']);
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn applies_the_same_change_once() {
+        let merged = merge(&['a', 'b', 'c'], &['a', 'x', 'c'], &['a', 'x', 'c']);
+
+        assert_eq!(merged.items, ['a', 'x', 'c']);
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn reports_a_range_both_sides_changed_differently() {
+        let merged = merge(&['a', 'b', 'c'], &['a', 'c'], &['a', 'x', 'c']);
+
+        assert_eq!(merged.items, ['a', 'x', 'c']);
+        assert_eq!(
+            merged.conflicts,
+            [MergeConflict {
+                position: 1,
+                base: vec!['b'],
+                left: vec![],
+                right: vec!['x'],
+            }]
+        );
+    }
+
+    #[test]
+    fn removes_one_copy_of_a_duplicate() {
+        let merged = merge(&['a', 'b', 'a'], &['a', 'b', 'a', 'x'], &['b', 'a']);
+
+        assert_eq!(merged.items, ['b', 'a', 'x']);
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn matches_items_along_a_common_subsequence() {
+        assert_eq!(
+            common_subsequence(&['a', 'b', 'c', 'd', 'e'], &['a', 'x', 'c', 'y', 'e']),
+            [Some(0), None, Some(2), None, Some(4)]
+        );
+        assert_eq!(
+            common_subsequence(&['a', 'b', 'c'], &['c', 'a', 'b']),
+            [Some(1), Some(2), None]
+        );
+        assert_eq!(common_subsequence(&['a', 'a'], &['a']), [Some(0), None]);
+        assert_eq!(common_subsequence(&['a'], &[]), [None]);
+    }
+
+    #[test]
+    fn matches_long_reordered_lists() {
+        let base: Vec<u32> = (0..3000).collect();
+        let mut other: Vec<u32> = (0..3000).rev().step_by(2).collect();
+        other.extend((0..3000).step_by(2));
+
+        let matches = common_subsequence(&base, &other);
+
+        assert_eq!(matches.iter().flatten().count(), 1500);
+        let matched: Vec<usize> = matches.iter().flatten().copied().collect();
+        assert!(matched.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(base
+            .iter()
+            .zip(&matches)
+            .all(|(item, matched)| matched.is_none_or(|index| other[index] == *item)));
+    }
+}
@@ -0,0 +1,4 @@
+pub mod merge;
+pub mod repository;
+pub mod runner;
+pub mod service;
@@ -0,0 +1,154 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use spotitube_domain::syncs::{PlaylistSyncDto, SyncConflictDto, SyncConflictKind};
+use sqlx::prelude::FromRow;
+use sqlx::types::time::OffsetDateTime;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::errors::{SpotitubeError, SpotitubeResult};
+
+pub type DynSyncsRepository = Arc<dyn SyncsRepository + Send + Sync>;
+
+#[async_trait]
+pub trait SyncsRepository {
+    async fn create_sync(
+        &self,
+        user_id: &Uuid,
+        spotify_playlist_id: &str,
+        youtube_playlist_id: &str,
+        interval_seconds: i64,
+    ) -> SpotitubeResult<PlaylistSyncEntity>;
+
+    async fn get_sync(&self, id: &Uuid) -> SpotitubeResult<Option<PlaylistSyncEntity>>;
+
+    async fn get_user_syncs(&self, user_id: &Uuid) -> SpotitubeResult<Vec<PlaylistSyncEntity>>;
+
+    async fn delete_sync(&self, id: &Uuid) -> SpotitubeResult<()>;
+
+    async fn schedule_sync(&self, id: &Uuid, next_sync_at: OffsetDateTime) -> SpotitubeResult<()>;
+
+    /// Pushes the next sync of up to `limit` due pairs one interval ahead and queues a sync job
+    /// for each of them in the same transaction, so each due sync is handed out once. Pairs with
+    /// a sync in progress since after `stale_before` are left alone.
+    async fn claim_due_syncs(
+        &self,
+        limit: i64,
+        stale_before: OffsetDateTime,
+        max_attempts: i32,
+    ) -> SpotitubeResult<Vec<PlaylistSyncEntity>>;
+
+    /// Marks the pair as syncing, returning false when another sync started after
+    /// `stale_before` is still in progress.
+    async fn start_sync(&self, id: &Uuid, stale_before: OffsetDateTime) -> SpotitubeResult<bool>;
+
+    /// Ends the sync in progress, recording when it succeeded or why it failed.
+    async fn finish_sync(&self, id: &Uuid, error: Option<&str>) -> SpotitubeResult<()>;
+
+    /// Tracks of the last synced version of the pair, in playlist order.
+    async fn get_sync_entries(&self, sync_id: &Uuid) -> SpotitubeResult<Vec<SyncEntryEntity>>;
+
+    async fn replace_sync_entries(
+        &self,
+        sync_id: &Uuid,
+        entries: &[NewSyncEntry],
+    ) -> SpotitubeResult<()>;
+
+    async fn create_sync_conflict(
+        &self,
+        sync_id: &Uuid,
+        conflict: &NewSyncConflict,
+    ) -> SpotitubeResult<()>;
+
+    /// Conflicts of the pair, most recent first.
+    async fn get_sync_conflicts(&self, sync_id: &Uuid) -> SpotitubeResult<Vec<SyncConflictEntity>>;
+}
+
+/// A track of the synced playlists, known on one side only when it could not be matched.
+#[derive(Debug, Clone)]
+pub struct NewSyncEntry {
+    pub spotify_track_id: Option<String>,
+    pub youtube_video_id: Option<String>,
+    pub title: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewSyncConflict {
+    pub kind: SyncConflictKind,
+    pub position: i32,
+    pub base_titles: Vec<String>,
+    pub spotify_titles: Vec<String>,
+    pub youtube_titles: Vec<String>,
+}
+
+#[derive(FromRow)]
+pub struct PlaylistSyncEntity {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub spotify_playlist_id: String,
+    pub youtube_playlist_id: String,
+    pub interval_seconds: i64,
+    pub error: Option<String>,
+    pub next_sync_at: OffsetDateTime,
+    pub syncing_since: Option<OffsetDateTime>,
+    pub last_synced_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(FromRow)]
+pub struct SyncEntryEntity {
+    pub id: Uuid,
+    pub sync_id: Uuid,
+    pub position: i32,
+    pub spotify_track_id: Option<String>,
+    pub youtube_video_id: Option<String>,
+    pub title: String,
+}
+
+#[derive(FromRow)]
+pub struct SyncConflictEntity {
+    pub id: Uuid,
+    pub sync_id: Uuid,
+    pub kind: String,
+    pub position: i32,
+    pub base_titles: Vec<String>,
+    pub spotify_titles: Vec<String>,
+    pub youtube_titles: Vec<String>,
+    pub created_at: OffsetDateTime,
+}
+
+impl PlaylistSyncEntity {
+    pub fn into_dto(self) -> PlaylistSyncDto {
+        PlaylistSyncDto {
+            id: self.id,
+            spotify_playlist_id: self.spotify_playlist_id,
+            youtube_playlist_id: self.youtube_playlist_id,
+            interval_seconds: self.interval_seconds,
+            error: self.error,
+            last_synced_at: self.last_synced_at,
+            next_sync_at: self.next_sync_at,
+            created_at: self.created_at,
+        }
+    }
+}
+
+impl SyncConflictEntity {
+    pub fn into_dto(self) -> SpotitubeResult<SyncConflictDto> {
+        let kind = self.kind.parse().map_err(|err| {
+            error!("sync conflict {:?} has {}", self.id, err);
+            SpotitubeError::InternalServerError
+        })?;
+
+        Ok(SyncConflictDto {
+            id: self.id,
+            kind,
+            position: self.position,
+            base: self.base_titles,
+            spotify: self.spotify_titles,
+            youtube: self.youtube_titles,
+            created_at: self.created_at,
+        })
+    }
+}
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::errors::{SpotitubeError, SpotitubeResult};
+
+pub type DynSyncRunner = Arc<dyn SyncRunner + Send + Sync>;
+
+/// Kind of the background job that syncs a playlist pair.
+pub const SYNC_JOB: &str = "playlist_sync";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncJob {
+    pub sync_id: Uuid,
+}
+
+#[async_trait]
+pub trait SyncRunner {
+    /// Diffs both playlists against the last synced version, applies the changes of each side
+    /// to the other and records what could not be merged.
+    async fn sync(&self, sync_id: &Uuid) -> SpotitubeResult<()>;
+
+    /// Records why the sync failed once it will not be retried anymore.
+    async fn fail(&self, sync_id: &Uuid, err: &SpotitubeError) -> SpotitubeResult<()>;
+}
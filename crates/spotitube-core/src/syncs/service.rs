@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use spotitube_domain::syncs::{PlaylistSyncDto, SyncConflictDto};
+use uuid::Uuid;
+
+use crate::errors::SpotitubeResult;
+
+pub type DynSyncsService = Arc<dyn SyncsService + Send + Sync>;
+
+#[async_trait]
+pub trait SyncsService {
+    /// Links the two playlists; the first sync runs shortly after.
+    async fn create_sync(
+        &self,
+        user_id: &Uuid,
+        spotify_playlist_id: &str,
+        youtube_playlist_id: &str,
+        interval_seconds: Option<i64>,
+    ) -> SpotitubeResult<PlaylistSyncDto>;
+    async fn list_syncs(&self, user_id: &Uuid) -> SpotitubeResult<Vec<PlaylistSyncDto>>;
+    async fn get_sync(&self, user_id: &Uuid, id: &Uuid) -> SpotitubeResult<PlaylistSyncDto>;
+    async fn delete_sync(&self, user_id: &Uuid, id: &Uuid) -> SpotitubeResult<()>;
+
+    /// Moves the next sync of the pair to now.
+    async fn request_sync(&self, user_id: &Uuid, id: &Uuid) -> SpotitubeResult<PlaylistSyncDto>;
+    async fn list_conflicts(
+        &self,
+        user_id: &Uuid,
+        id: &Uuid,
+    ) -> SpotitubeResult<Vec<SyncConflictDto>>;
+}
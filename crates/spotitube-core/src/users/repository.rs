@@ -21,6 +21,16 @@ pub trait UsersRepository {
     async fn get_user_by_username(&self, username: &str) -> SpotitubeResult<Option<UserEntity>>;
 
     async fn get_user_by_id(&self, user_id: &Uuid) -> SpotitubeResult<UserEntity>;
+
+    /// Updates the user, revoking all of their refresh tokens in the same transaction when
+    /// `revoke_refresh_tokens` is set.
+    async fn update_user(
+        &self,
+        user_id: &Uuid,
+        username: &str,
+        hashed_password: &str,
+        revoke_refresh_tokens: bool,
+    ) -> SpotitubeResult<UserEntity>;
 }
 
 #[derive(FromRow)]
@@ -37,7 +47,8 @@ impl UserEntity {
         UserDto {
             id: self.id,
             username: self.username,
-            token: token,
+            token,
+            refresh_token: None,
         }
     }
 }
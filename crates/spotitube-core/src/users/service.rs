@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use axum::async_trait;
 use spotitube_domain::users::{
-    requests::{LoginUserDto, RegisterUserDto},
+    requests::{LoginUserDto, RegisterUserDto, UpdateUserDto},
     UserDto,
 };
 use uuid::Uuid;
@@ -16,4 +16,6 @@ pub trait UsersService {
     async fn register_user(&self, request: RegisterUserDto) -> SpotitubeResult<UserDto>;
     async fn login_user(&self, request: LoginUserDto) -> SpotitubeResult<UserDto>;
     async fn get_user(&self, user_id: &Uuid) -> SpotitubeResult<UserDto>;
+    async fn update_user(&self, user_id: &Uuid, request: UpdateUserDto)
+        -> SpotitubeResult<UserDto>;
 }
@@ -0,0 +1,15 @@
+use std::sync::Arc;
+
+use crate::errors::SpotitubeResult;
+
+pub type DynEncryptionService = Arc<dyn EncryptionService + Send + Sync>;
+
+pub trait EncryptionService {
+    fn encrypt(&self, plaintext: &str) -> SpotitubeResult<String>;
+    fn decrypt(&self, ciphertext: &str) -> SpotitubeResult<String>;
+    /// Whether `value` is plaintext or was sealed under a key other than the active one.
+    fn needs_reencryption(&self, value: &str) -> bool;
+    /// Opens `value` with whichever key sealed it, treating unsealed values as legacy
+    /// plaintext, and seals it again under the active key.
+    fn reencrypt(&self, value: &str) -> SpotitubeResult<String>;
+}
@@ -1,2 +1,3 @@
 pub mod token_service;
-pub mod security_service;
\ No newline at end of file
+pub mod security_service;
+pub mod encryption_service;
\ No newline at end of file
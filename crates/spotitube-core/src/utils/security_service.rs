@@ -1,14 +1,18 @@
 use std::sync::Arc;
 
+use axum::async_trait;
+
 use crate::errors::SpotitubeResult;
 
 pub type DynSecurityService = Arc<dyn SecurityService + Send + Sync>;
 
+#[async_trait]
 pub trait SecurityService {
-    fn hash_password(&self, raw_password: &str) -> SpotitubeResult<String>;
-    fn verify_password(
+    async fn hash_password(&self, raw_password: &str) -> SpotitubeResult<String>;
+    async fn verify_password(
         &self,
         stored_password: &str,
         attempted_password: &str,
     ) -> SpotitubeResult<bool>;
+    fn needs_rehash(&self, stored_password: &str) -> bool;
 }
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use jsonwebtoken::jwk::JwkSet;
 use uuid::Uuid;
 
 use crate::errors::SpotitubeResult;
@@ -9,4 +10,5 @@ pub type DynTokenService = Arc<dyn TokenService + Send + Sync>;
 pub trait TokenService {
     fn new_token(&self, user_id: &Uuid, username: &str) -> SpotitubeResult<String>;
     fn get_user_id_from_token(&self, token: String) -> SpotitubeResult<Uuid>;
+    fn jwk_set(&self) -> JwkSet;
 }
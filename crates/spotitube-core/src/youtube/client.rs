@@ -0,0 +1,82 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use crate::errors::SpotitubeResult;
+
+use super::models::{
+    YouTubeChannel, YouTubePage, YouTubePlaylist, YouTubePlaylistItem, YouTubeQuotaUsage,
+    YouTubeSearchResult, YouTubeVideo,
+};
+
+pub type DynYouTubeClient = Arc<dyn YouTubeClient + Send + Sync>;
+
+#[async_trait]
+pub trait YouTubeClient {
+    async fn get_my_channel(&self, access_token: &str) -> SpotitubeResult<YouTubeChannel>;
+
+    async fn list_playlists(
+        &self,
+        access_token: &str,
+        page_token: Option<&str>,
+        max_results: u32,
+    ) -> SpotitubeResult<YouTubePage<YouTubePlaylist>>;
+
+    async fn get_playlist(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+    ) -> SpotitubeResult<YouTubePlaylist>;
+
+    async fn list_playlist_items(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        page_token: Option<&str>,
+        max_results: u32,
+    ) -> SpotitubeResult<YouTubePage<YouTubePlaylistItem>>;
+
+    async fn search_videos(
+        &self,
+        access_token: &str,
+        query: &str,
+        max_results: u32,
+    ) -> SpotitubeResult<Vec<YouTubeSearchResult>>;
+
+    async fn get_videos(
+        &self,
+        access_token: &str,
+        video_ids: &[String],
+    ) -> SpotitubeResult<Vec<YouTubeVideo>>;
+
+    async fn create_playlist(
+        &self,
+        access_token: &str,
+        title: &str,
+        description: &str,
+        privacy_status: &str,
+    ) -> SpotitubeResult<YouTubePlaylist>;
+
+    async fn insert_playlist_item(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        video_id: &str,
+        position: Option<u32>,
+    ) -> SpotitubeResult<YouTubePlaylistItem>;
+
+    /// Moves an existing playlist item to `position`.
+    async fn update_playlist_item(
+        &self,
+        access_token: &str,
+        item_id: &str,
+        playlist_id: &str,
+        video_id: &str,
+        position: u32,
+    ) -> SpotitubeResult<YouTubePlaylistItem>;
+
+    async fn delete_playlist_item(&self, access_token: &str, item_id: &str) -> SpotitubeResult<()>;
+
+    /// Quota units spent in the current YouTube quota day.
+    async fn quota_usage(&self) -> SpotitubeResult<YouTubeQuotaUsage>;
+}
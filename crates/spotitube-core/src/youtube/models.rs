@@ -0,0 +1,351 @@
+use serde::{Deserialize, Serialize};
+use spotitube_domain::{
+    catalog::{Artist, ExternalId, ParsedTitle, Playlist, Track},
+    providers::Provider,
+};
+
+use crate::conversions::title_parser::parse_video_title;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubePage<T> {
+    pub items: Vec<T>,
+    pub next_page_token: Option<String>,
+    pub page_info: Option<YouTubePageInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubePageInfo {
+    pub total_results: u32,
+    pub results_per_page: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubeChannel {
+    pub id: String,
+    pub snippet: YouTubeChannelSnippet,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubeChannelSnippet {
+    pub title: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubePlaylist {
+    pub id: String,
+    pub etag: Option<String>,
+    pub snippet: YouTubePlaylistSnippet,
+    pub status: Option<YouTubePlaylistStatus>,
+    pub content_details: Option<YouTubePlaylistContentDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubePlaylistSnippet {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub channel_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubePlaylistStatus {
+    pub privacy_status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubePlaylistContentDetails {
+    pub item_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubePlaylistItem {
+    pub id: String,
+    pub snippet: YouTubePlaylistItemSnippet,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubePlaylistItemSnippet {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub playlist_id: String,
+    pub position: Option<u32>,
+    pub resource_id: YouTubeResourceId,
+    pub video_owner_channel_title: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubeResourceId {
+    pub kind: String,
+    pub video_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubeSearchResult {
+    pub id: YouTubeResourceId,
+    pub snippet: YouTubeVideoSnippet,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubeVideo {
+    pub id: String,
+    pub snippet: YouTubeVideoSnippet,
+    pub content_details: Option<YouTubeVideoContentDetails>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubeVideoSnippet {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub channel_title: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct YouTubeVideoContentDetails {
+    /// ISO 8601 duration such as `PT3M33S`.
+    pub duration: String,
+}
+
+impl YouTubePlaylist {
+    pub fn into_playlist(self, tracks: Vec<Track>) -> Playlist {
+        Playlist {
+            name: self.snippet.title,
+            description: Some(self.snippet.description)
+                .filter(|description| !description.is_empty()),
+            external_ids: vec![ExternalId::new(Provider::YouTube, self.id)],
+            tracks,
+        }
+    }
+}
+
+impl YouTubePlaylistItem {
+    /// The video as a track, without a duration since playlist items do not carry one.
+    pub fn into_track(self) -> Track {
+        let video_id = self.snippet.available_video_id().map(String::from);
+        video_track(
+            &self.snippet.title,
+            self.snippet.video_owner_channel_title.as_deref(),
+            video_id,
+            None,
+        )
+    }
+}
+
+impl YouTubeVideo {
+    pub fn into_track(self) -> Track {
+        let duration_ms = self
+            .content_details
+            .as_ref()
+            .and_then(|details| details.duration_ms());
+        video_track(
+            &self.snippet.title,
+            self.snippet.channel_title.as_deref(),
+            Some(self.id),
+            duration_ms,
+        )
+    }
+}
+
+/// Keeps the title and channel as uploaded, along with the most likely artist and title parsed
+/// out of them.
+fn video_track(
+    title: &str,
+    channel_title: Option<&str>,
+    video_id: Option<String>,
+    duration_ms: Option<i64>,
+) -> Track {
+    let parsed_title = parse_video_title(title, channel_title)
+        .into_iter()
+        .next()
+        .map(|candidate| ParsedTitle {
+            title: candidate.title,
+            artists: candidate.artists,
+        });
+
+    Track {
+        title: String::from(title),
+        artists: channel_title
+            .map(|name| Artist {
+                name: String::from(name),
+                external_ids: Vec::new(),
+            })
+            .into_iter()
+            .collect(),
+        album: None,
+        duration_ms,
+        explicit: false,
+        isrc: None,
+        external_ids: video_id
+            .map(|id| ExternalId::new(Provider::YouTube, id))
+            .into_iter()
+            .collect(),
+        parsed_title,
+    }
+}
+
+/// Placeholder titles YouTube returns for playlist entries whose video is gone.
+const UNAVAILABLE_VIDEO_TITLES: &[&str] = &["Deleted video", "Private video"];
+
+impl YouTubePlaylistItemSnippet {
+    /// Id of the video, unless it was deleted or made private since it was added.
+    pub fn available_video_id(&self) -> Option<&str> {
+        let available = !UNAVAILABLE_VIDEO_TITLES.contains(&self.title.as_str())
+            || self.video_owner_channel_title.is_some();
+        self.resource_id.video_id.as_deref().filter(|_| available)
+    }
+}
+
+impl YouTubeVideoContentDetails {
+    /// Parses the ISO 8601 duration into milliseconds, `None` for live streams and malformed
+    /// values.
+    pub fn duration_ms(&self) -> Option<i64> {
+        let mut remaining = self.duration.strip_prefix('P')?;
+        let mut seconds = 0;
+        let mut in_time = false;
+        while !remaining.is_empty() {
+            if let Some(rest) = remaining.strip_prefix('T') {
+                in_time = true;
+                remaining = rest;
+                continue;
+            }
+
+            let digits = remaining.find(|c: char| !c.is_ascii_digit())?;
+            let value: i64 = remaining[..digits].parse().ok()?;
+            let unit = match (in_time, remaining[digits..].chars().next()?) {
+                (false, 'W') => 604800,
+                (false, 'D') => 86400,
+                (true, 'H') => 3600,
+                (true, 'M') => 60,
+                (true, 'S') => 1,
+                _ => return None,
+            };
+            seconds += value * unit;
+            remaining = &remaining[digits + 1..];
+        }
+
+        Some(seconds * 1000)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct YouTubeQuotaUsage {
+    pub used: u64,
+    pub limit: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use spotitube_domain::{
+        catalog::{Artist, ExternalId, ParsedTitle, Playlist, Track},
+        providers::Provider,
+    };
+
+    use super::{YouTubePlaylist, YouTubePlaylistItem, YouTubeVideo};
+
+    fn playlist_item(title: &str, channel_title: Option<&str>) -> YouTubePlaylistItem {
+        serde_json::from_value(json!({
+            "id": "item",
+            "snippet": {
+                "title": title,
+                "playlistId": "road",
+                "position": 0,
+                "resourceId": { "kind": "youtube#video", "videoId": "fJ9rUzIMcZQ" },
+                "videoOwnerChannelTitle": channel_title
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn keeps_the_uploaded_title_next_to_the_parsed_one() {
+        let track = playlist_item(
+            "Queen – Bohemian Rhapsody (Official Video Remastered)",
+            Some("Queen Official"),
+        )
+        .into_track();
+
+        assert_eq!(
+            track,
+            Track {
+                title: String::from("Queen – Bohemian Rhapsody (Official Video Remastered)"),
+                artists: vec![Artist {
+                    name: String::from("Queen Official"),
+                    external_ids: Vec::new(),
+                }],
+                album: None,
+                duration_ms: None,
+                explicit: false,
+                isrc: None,
+                external_ids: vec![ExternalId::new(Provider::YouTube, "fJ9rUzIMcZQ")],
+                parsed_title: Some(ParsedTitle {
+                    title: String::from("Bohemian Rhapsody"),
+                    artists: vec![String::from("Queen")],
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn maps_an_unavailable_video_without_an_id() {
+        let track = playlist_item("Deleted video", None).into_track();
+
+        assert_eq!(track.title, "Deleted video");
+        assert!(track.artists.is_empty());
+        assert_eq!(track.external_id(Provider::YouTube), None);
+    }
+
+    #[test]
+    fn maps_a_video_with_its_duration() {
+        let video: YouTubeVideo = serde_json::from_value(json!({
+            "id": "fJ9rUzIMcZQ",
+            "snippet": { "title": "Bohemian Rhapsody", "channelTitle": "Queen Official" },
+            "contentDetails": { "duration": "PT5M59S" }
+        }))
+        .unwrap();
+
+        let track = video.into_track();
+
+        assert_eq!(track.duration_ms, Some(359000));
+        assert_eq!(track.title, "Bohemian Rhapsody");
+        assert_eq!(track.external_id(Provider::YouTube), Some("fJ9rUzIMcZQ"));
+    }
+
+    #[test]
+    fn round_trips_a_mapped_playlist_through_json() {
+        let playlist: YouTubePlaylist = serde_json::from_value(json!({
+            "id": "road",
+            "etag": "etag",
+            "snippet": { "title": "Road trip", "description": "Songs for the car" }
+        }))
+        .unwrap();
+        let tracks = vec![
+            playlist_item("Queen - Bohemian Rhapsody", Some("Queen Official")).into_track(),
+            playlist_item("Private video", None).into_track(),
+        ];
+
+        let playlist = playlist.into_playlist(tracks);
+        let json = serde_json::to_string(&playlist).unwrap();
+
+        assert_eq!(playlist.name, "Road trip");
+        assert_eq!(playlist.description.as_deref(), Some("Songs for the car"));
+        assert_eq!(playlist.external_id(Provider::YouTube), Some("road"));
+        assert_eq!(serde_json::from_str::<Playlist>(&json).unwrap(), playlist);
+    }
+}
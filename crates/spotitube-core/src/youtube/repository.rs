@@ -0,0 +1,23 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+
+use sqlx::types::time::OffsetDateTime;
+
+use crate::errors::SpotitubeResult;
+
+pub type DynYouTubeQuotaRepository = Arc<dyn YouTubeQuotaRepository + Send + Sync>;
+
+/// Quota units spent per YouTube quota day, which starts at midnight America/Los_Angeles.
+/// Shared by every instance of the server.
+#[async_trait]
+pub trait YouTubeQuotaRepository {
+    /// Adds `cost` to the units spent today unless that would go over `limit`, in which case
+    /// nothing is recorded and `false` is returned.
+    async fn reserve_quota(&self, cost: i64, limit: i64) -> SpotitubeResult<bool>;
+
+    async fn get_quota_used(&self) -> SpotitubeResult<i64>;
+
+    /// When the current quota day ends and the daily limit is available again.
+    async fn get_quota_reset(&self) -> SpotitubeResult<OffsetDateTime>;
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use crate::providers::Provider;
+
+pub mod requests;
+pub mod responses;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkedAccountDto {
+    pub provider: Provider,
+    pub external_user_id: String,
+    pub display_name: Option<String>,
+    pub scopes: Vec<String>,
+    pub needs_relink: bool,
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::providers::Provider;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct LinkAccountRequest {
+    #[validate(required)]
+    pub provider: Option<Provider>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OAuthCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+}
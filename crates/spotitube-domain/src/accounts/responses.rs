@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use super::LinkedAccountDto;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkedAccountsResponse {
+    pub accounts: Vec<LinkedAccountDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkedAccountResponse {
+    pub account: LinkedAccountDto,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkAccountResponse {
+    pub authorization_url: String,
+}
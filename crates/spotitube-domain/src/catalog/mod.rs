@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+use crate::providers::Provider;
+
+/// Id of a catalog item on one of the providers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ExternalId {
+    pub provider: Provider,
+    pub id: String,
+}
+
+impl ExternalId {
+    pub fn new(provider: Provider, id: impl Into<String>) -> Self {
+        Self {
+            provider,
+            id: id.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Artist {
+    pub name: String,
+    #[serde(default)]
+    pub external_ids: Vec<ExternalId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Album {
+    pub title: String,
+    #[serde(default)]
+    pub artists: Vec<Artist>,
+    /// `YYYY`, `YYYY-MM` or `YYYY-MM-DD`, as precise as the provider knows it.
+    pub release_date: Option<String>,
+    pub upc: Option<String>,
+    #[serde(default)]
+    pub external_ids: Vec<ExternalId>,
+}
+
+/// A song independently of the provider it was found on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Track {
+    pub title: String,
+    #[serde(default)]
+    pub artists: Vec<Artist>,
+    pub album: Option<Album>,
+    pub duration_ms: Option<i64>,
+    #[serde(default)]
+    pub explicit: bool,
+    pub isrc: Option<String>,
+    /// Empty for local files and videos that are no longer available.
+    #[serde(default)]
+    pub external_ids: Vec<ExternalId>,
+    /// Most likely artist and title read out of a free-form title such as a video title, which
+    /// is kept untouched in `title`.
+    pub parsed_title: Option<ParsedTitle>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParsedTitle {
+    pub title: String,
+    #[serde(default)]
+    pub artists: Vec<String>,
+}
+
+impl Track {
+    pub fn external_id(&self, provider: Provider) -> Option<&str> {
+        find_external_id(&self.external_ids, provider)
+    }
+
+    pub fn artist_names(&self) -> Vec<String> {
+        self.artists
+            .iter()
+            .map(|artist| artist.name.clone())
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Playlist {
+    pub name: String,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub external_ids: Vec<ExternalId>,
+    #[serde(default)]
+    pub tracks: Vec<Track>,
+}
+
+impl Playlist {
+    pub fn external_id(&self, provider: Provider) -> Option<&str> {
+        find_external_id(&self.external_ids, provider)
+    }
+}
+
+fn find_external_id(external_ids: &[ExternalId], provider: Provider) -> Option<&str> {
+    external_ids
+        .iter()
+        .find(|external_id| external_id.provider == provider)
+        .map(|external_id| external_id.id.as_str())
+}
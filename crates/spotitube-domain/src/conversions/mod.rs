@@ -0,0 +1,224 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::providers::Provider;
+
+pub mod requests;
+pub mod responses;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversionStatus {
+    Pending,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl ConversionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConversionStatus::Pending => "pending",
+            ConversionStatus::Running => "running",
+            ConversionStatus::Completed => "completed",
+            ConversionStatus::Failed => "failed",
+        }
+    }
+}
+
+impl Display for ConversionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ConversionStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "pending" => Ok(ConversionStatus::Pending),
+            "running" => Ok(ConversionStatus::Running),
+            "completed" => Ok(ConversionStatus::Completed),
+            "failed" => Ok(ConversionStatus::Failed),
+            _ => Err(format!("unknown conversion status {}", value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrackStatus {
+    Pending,
+    Matched,
+    NeedsReview,
+    Resolving,
+    NotFound,
+    Skipped,
+    Failed,
+    Rejected,
+}
+
+impl TrackStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrackStatus::Pending => "pending",
+            TrackStatus::Matched => "matched",
+            TrackStatus::NeedsReview => "needs_review",
+            TrackStatus::Resolving => "resolving",
+            TrackStatus::NotFound => "not_found",
+            TrackStatus::Skipped => "skipped",
+            TrackStatus::Failed => "failed",
+            TrackStatus::Rejected => "rejected",
+        }
+    }
+}
+
+impl Display for TrackStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TrackStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "pending" => Ok(TrackStatus::Pending),
+            "matched" => Ok(TrackStatus::Matched),
+            "needs_review" => Ok(TrackStatus::NeedsReview),
+            "resolving" => Ok(TrackStatus::Resolving),
+            "not_found" => Ok(TrackStatus::NotFound),
+            "skipped" => Ok(TrackStatus::Skipped),
+            "failed" => Ok(TrackStatus::Failed),
+            "rejected" => Ok(TrackStatus::Rejected),
+            _ => Err(format!("unknown track status {}", value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversionDto {
+    pub id: Uuid,
+    pub source_provider: Provider,
+    pub source_playlist_id: String,
+    pub destination_provider: Provider,
+    pub destination_playlist_id: Option<String>,
+    pub name: Option<String>,
+    pub status: ConversionStatus,
+    pub error: Option<String>,
+    pub total_tracks: i32,
+    pub tracks: Vec<ConversionTrackDto>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub completed_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversionTrackDto {
+    pub position: i32,
+    pub source_id: Option<String>,
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub isrc: Option<String>,
+    pub status: TrackStatus,
+    pub destination_id: Option<String>,
+    pub destination_title: Option<String>,
+    pub confidence: Option<f64>,
+    pub match_reasons: Vec<String>,
+    pub error: Option<String>,
+}
+
+/// A track the matcher was unsure about, with the candidates it considered.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrackReviewDto {
+    pub track: ConversionTrackDto,
+    pub candidates: Vec<MatchCandidateDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MatchCandidateDto {
+    pub id: Uuid,
+    pub destination_id: String,
+    pub title: String,
+    pub confidence: f64,
+    pub match_reasons: Vec<String>,
+}
+
+/// Progress of a conversion, streamed to clients while it runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConversionEvent {
+    /// Current state of the conversion, sent when a stream can not resume from earlier events.
+    Snapshot {
+        conversion: ConversionDto,
+    },
+    StatusChanged {
+        status: ConversionStatus,
+        error: Option<String>,
+    },
+    TracksFetched {
+        name: String,
+        total_tracks: i32,
+    },
+    TrackMatched {
+        position: i32,
+        destination_id: String,
+        destination_title: String,
+        confidence: Option<f64>,
+    },
+    TrackInserted {
+        position: i32,
+        destination_id: String,
+    },
+    /// A track that did not make it into the destination playlist, at least not yet.
+    TrackFailed {
+        position: i32,
+        status: TrackStatus,
+        error: Option<String>,
+    },
+}
+
+impl ConversionEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConversionEvent::Snapshot { .. } => "snapshot",
+            ConversionEvent::StatusChanged { .. } => "status_changed",
+            ConversionEvent::TracksFetched { .. } => "tracks_fetched",
+            ConversionEvent::TrackMatched { .. } => "track_matched",
+            ConversionEvent::TrackInserted { .. } => "track_inserted",
+            ConversionEvent::TrackFailed { .. } => "track_failed",
+        }
+    }
+
+    /// Whether nothing follows this event until the conversion is started again.
+    pub fn is_final(&self) -> bool {
+        match self {
+            ConversionEvent::Snapshot { conversion } => matches!(
+                conversion.status,
+                ConversionStatus::Completed | ConversionStatus::Failed
+            ),
+            ConversionEvent::StatusChanged { status, .. } => {
+                matches!(status, ConversionStatus::Completed | ConversionStatus::Failed)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversionEventDto {
+    pub id: Option<String>,
+    pub conversion_id: Uuid,
+    #[serde(flatten)]
+    pub event: ConversionEvent,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::providers::Provider;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateConversionRequest {
+    /// Provider the source playlist lives on, Spotify unless given; the destination is the
+    /// other provider.
+    pub source_provider: Option<Provider>,
+    #[validate(required, length(min = 1))]
+    pub source_playlist_id: Option<String>,
+    #[validate(length(min = 1, max = 150))]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrackReviewsQuery {
+    /// How many candidates to return per track, all stored candidates unless given.
+    pub candidates: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConversionEventsQuery {
+    /// Stands in for the `Last-Event-ID` header, which WebSocket clients can not send.
+    pub last_event_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct AcceptCandidateRequest {
+    #[validate(required)]
+    pub candidate_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ManualMatchRequest {
+    /// Video id for YouTube destinations, track id for Spotify destinations.
+    #[validate(required, length(min = 1))]
+    pub destination_id: Option<String>,
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use super::{ConversionDto, ConversionTrackDto, TrackReviewDto};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConversionResponse {
+    pub conversion: ConversionDto,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrackReviewsResponse {
+    pub reviews: Vec<TrackReviewDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConversionTrackResponse {
+    pub track: ConversionTrackDto,
+}
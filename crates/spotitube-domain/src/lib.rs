@@ -2,21 +2,35 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
+pub mod accounts;
+pub mod catalog;
+pub mod conversions;
+pub mod providers;
+pub mod snapshots;
+pub mod syncs;
 pub mod users;
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ApiError {
+    pub code: String,
     pub errors: HashMap<String, Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 impl ApiError {
-    pub fn from_str(error: &str) -> Self {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(code: &str, error: &str) -> Self {
         let mut error_map: HashMap<String, Vec<String>> = HashMap::new();
         error_map.insert(String::from("message"), vec![String::from(error)]);
-        Self { errors: error_map }
+        Self::from_map(code, error_map)
     }
 
-    pub fn from_map(errors: HashMap<String, Vec<String>>) -> Self {
-        Self { errors }
+    pub fn from_map(code: &str, errors: HashMap<String, Vec<String>>) -> Self {
+        Self {
+            code: String::from(code),
+            errors,
+            request_id: None,
+        }
     }
 }
@@ -0,0 +1,101 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::providers::Provider;
+
+pub mod requests;
+pub mod responses;
+
+/// What the playlist looked like at one point, kept unchanged once recorded.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlaylistSnapshotDto {
+    pub id: Uuid,
+    pub provider: Provider,
+    pub playlist_id: String,
+    /// Counts up from 1 for every version of the playlist.
+    pub version: i32,
+    pub origin: SnapshotOrigin,
+    pub name: String,
+    pub description: Option<String>,
+    /// Spotify snapshot id or YouTube etag the playlist had when it was recorded.
+    pub source_version: Option<String>,
+    pub track_count: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tracks: Option<Vec<SnapshotTrackDto>>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotTrackDto {
+    pub position: i32,
+    /// Spotify track id or YouTube video id, missing for local files and unavailable videos.
+    pub provider_id: Option<String>,
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: Option<String>,
+    pub duration_ms: Option<i64>,
+    pub isrc: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnapshotOrigin {
+    /// Requested by the user or read as the source of a conversion.
+    Import,
+    /// Written by a conversion.
+    Conversion,
+    Sync,
+    Restore,
+}
+
+impl SnapshotOrigin {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SnapshotOrigin::Import => "import",
+            SnapshotOrigin::Conversion => "conversion",
+            SnapshotOrigin::Sync => "sync",
+            SnapshotOrigin::Restore => "restore",
+        }
+    }
+}
+
+impl Display for SnapshotOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SnapshotOrigin {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "import" => Ok(SnapshotOrigin::Import),
+            "conversion" => Ok(SnapshotOrigin::Conversion),
+            "sync" => Ok(SnapshotOrigin::Sync),
+            "restore" => Ok(SnapshotOrigin::Restore),
+            _ => Err(format!("unknown snapshot origin {}", value)),
+        }
+    }
+}
+
+/// Changes from one version of a playlist to a later or earlier one. Positions of added and
+/// moved tracks are in `to`, positions of removed tracks in `from`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnapshotDiffDto {
+    pub from: Uuid,
+    pub to: Uuid,
+    pub added: Vec<SnapshotTrackDto>,
+    pub removed: Vec<SnapshotTrackDto>,
+    pub moved: Vec<MovedTrackDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MovedTrackDto {
+    pub from_position: i32,
+    pub track: SnapshotTrackDto,
+}
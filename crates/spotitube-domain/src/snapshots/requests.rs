@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::providers::Provider;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateSnapshotRequest {
+    #[validate(required)]
+    pub provider: Option<Provider>,
+    #[validate(required, length(min = 1))]
+    pub playlist_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlaylistSnapshotsQuery {
+    pub provider: Provider,
+    pub playlist_id: String,
+}
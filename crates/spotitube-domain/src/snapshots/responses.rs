@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use super::{PlaylistSnapshotDto, SnapshotDiffDto};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlaylistSnapshotResponse {
+    pub snapshot: PlaylistSnapshotDto,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlaylistSnapshotsResponse {
+    pub snapshots: Vec<PlaylistSnapshotDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SnapshotDiffResponse {
+    pub diff: SnapshotDiffDto,
+}
@@ -0,0 +1,73 @@
+use std::{fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+pub mod requests;
+pub mod responses;
+
+/// A Spotify playlist and a YouTube playlist kept in sync with each other.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlaylistSyncDto {
+    pub id: Uuid,
+    pub spotify_playlist_id: String,
+    pub youtube_playlist_id: String,
+    pub interval_seconds: i64,
+    pub error: Option<String>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_synced_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub next_sync_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncConflictKind {
+    /// Both playlists changed the same tracks differently; everything either side had was kept.
+    Position,
+    /// A track added on one side has no confident match on the other and was not copied.
+    Unmatched,
+}
+
+impl SyncConflictKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncConflictKind::Position => "position",
+            SyncConflictKind::Unmatched => "unmatched",
+        }
+    }
+}
+
+impl Display for SyncConflictKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SyncConflictKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "position" => Ok(SyncConflictKind::Position),
+            "unmatched" => Ok(SyncConflictKind::Unmatched),
+            _ => Err(format!("unknown sync conflict kind {}", value)),
+        }
+    }
+}
+
+/// Titles of the tracks involved, as they were in the last synced version and on each side.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncConflictDto {
+    pub id: Uuid,
+    pub kind: SyncConflictKind,
+    pub position: i32,
+    pub base: Vec<String>,
+    pub spotify: Vec<String>,
+    pub youtube: Vec<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
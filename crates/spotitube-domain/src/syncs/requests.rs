@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreatePlaylistSyncRequest {
+    #[validate(required, length(min = 1))]
+    pub spotify_playlist_id: Option<String>,
+    #[validate(required, length(min = 1))]
+    pub youtube_playlist_id: Option<String>,
+    /// Seconds between two syncs, the configured default unless given.
+    #[validate(range(min = 60, max = 604800))]
+    pub interval_seconds: Option<i64>,
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use super::{PlaylistSyncDto, SyncConflictDto};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlaylistSyncResponse {
+    pub sync: PlaylistSyncDto,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlaylistSyncsResponse {
+    pub syncs: Vec<PlaylistSyncDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SyncConflictsResponse {
+    pub conflicts: Vec<SyncConflictDto>,
+}
@@ -9,4 +9,6 @@ pub struct UserDto {
     pub id: Uuid,
     pub username: String,
     pub token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
 }
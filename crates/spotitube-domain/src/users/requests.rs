@@ -13,6 +13,12 @@ pub struct LoginUserRequest {
     pub user: LoginUserDto,
 }
 
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateUserRequest {
+    #[validate]
+    pub user: UpdateUserDto,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct RegisterUserDto {
     #[validate(required, length(min = 1))]
@@ -28,3 +34,17 @@ pub struct LoginUserDto {
     #[validate(required, length(min = 8))]
     pub password: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct UpdateUserDto {
+    #[validate(length(min = 1))]
+    pub username: Option<String>,
+    #[validate(length(min = 8))]
+    pub password: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct RefreshTokenRequest {
+    #[validate(required, length(min = 1))]
+    pub refresh_token: Option<String>,
+}
@@ -12,9 +12,10 @@ impl UserAuthResponse {
     pub fn new(id: Uuid, username: String, token: String) -> Self {
         Self {
             user: UserDto {
-                id: id,
-                username: username,
-                token: token,
+                id,
+                username,
+                token,
+                refresh_token: None,
             },
         }
     }
@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS linked_accounts(
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    provider VARCHAR NOT NULL,
+    external_user_id VARCHAR NOT NULL,
+    display_name VARCHAR,
+    access_token VARCHAR NOT NULL,
+    refresh_token VARCHAR,
+    scopes VARCHAR NOT NULL DEFAULT '',
+    expires_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    UNIQUE (user_id, provider)
+);
+
+CREATE INDEX IF NOT EXISTS linked_accounts_user_id_idx on linked_accounts (user_id);
@@ -0,0 +1,2 @@
+ALTER TABLE linked_accounts ADD COLUMN IF NOT EXISTS needs_relink BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE linked_accounts ADD COLUMN IF NOT EXISTS refresh_locked_until TIMESTAMPTZ;
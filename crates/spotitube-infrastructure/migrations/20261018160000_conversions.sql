@@ -0,0 +1,36 @@
+CREATE TABLE IF NOT EXISTS conversions(
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    source_provider VARCHAR NOT NULL,
+    source_playlist_id VARCHAR NOT NULL,
+    destination_provider VARCHAR NOT NULL,
+    destination_playlist_id VARCHAR,
+    name VARCHAR,
+    status VARCHAR NOT NULL DEFAULT 'pending',
+    error VARCHAR,
+    total_tracks INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS conversions_user_id_idx on conversions (user_id);
+
+CREATE TABLE IF NOT EXISTS conversion_tracks(
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    conversion_id UUID NOT NULL REFERENCES conversions (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    source_id VARCHAR,
+    title VARCHAR NOT NULL,
+    artists VARCHAR[] NOT NULL DEFAULT '{}',
+    album VARCHAR,
+    duration_ms BIGINT,
+    isrc VARCHAR,
+    status VARCHAR NOT NULL DEFAULT 'pending',
+    destination_id VARCHAR,
+    destination_title VARCHAR,
+    error VARCHAR,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    UNIQUE (conversion_id, position)
+);
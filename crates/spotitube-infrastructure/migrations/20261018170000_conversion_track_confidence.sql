@@ -0,0 +1,2 @@
+ALTER TABLE conversion_tracks ADD COLUMN IF NOT EXISTS confidence DOUBLE PRECISION;
+ALTER TABLE conversion_tracks ADD COLUMN IF NOT EXISTS match_reasons VARCHAR[] NOT NULL DEFAULT '{}';
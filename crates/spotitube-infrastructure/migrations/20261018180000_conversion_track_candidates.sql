@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS conversion_track_candidates(
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    conversion_track_id UUID NOT NULL REFERENCES conversion_tracks (id) ON DELETE CASCADE,
+    rank INTEGER NOT NULL,
+    destination_id VARCHAR NOT NULL,
+    title VARCHAR NOT NULL,
+    confidence DOUBLE PRECISION NOT NULL,
+    match_reasons VARCHAR[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    UNIQUE (conversion_track_id, rank)
+);
@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS track_matches(
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    source_provider VARCHAR NOT NULL,
+    source_id VARCHAR NOT NULL,
+    isrc VARCHAR,
+    destination_provider VARCHAR NOT NULL,
+    destination_id VARCHAR NOT NULL,
+    destination_title VARCHAR NOT NULL,
+    confidence DOUBLE PRECISION NOT NULL,
+    confirmed_by UUID REFERENCES users (id) ON DELETE SET NULL,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    UNIQUE (source_provider, source_id, destination_provider)
+);
+
+CREATE INDEX IF NOT EXISTS track_matches_isrc_idx on track_matches (isrc, destination_provider);
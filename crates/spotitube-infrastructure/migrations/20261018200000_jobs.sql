@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS jobs(
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    kind VARCHAR NOT NULL,
+    payload JSONB NOT NULL DEFAULT '{}',
+    status VARCHAR NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL,
+    run_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    locked_by VARCHAR,
+    locked_until TIMESTAMPTZ,
+    last_error VARCHAR,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    completed_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS jobs_claimable_idx on jobs (run_at) WHERE status IN ('pending', 'running');
@@ -0,0 +1,39 @@
+CREATE TABLE IF NOT EXISTS playlist_syncs(
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    spotify_playlist_id VARCHAR NOT NULL,
+    youtube_playlist_id VARCHAR NOT NULL,
+    interval_seconds BIGINT NOT NULL,
+    error VARCHAR,
+    next_sync_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    syncing_since TIMESTAMPTZ,
+    last_synced_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    UNIQUE (user_id, spotify_playlist_id, youtube_playlist_id)
+);
+
+CREATE INDEX IF NOT EXISTS playlist_syncs_next_sync_at_idx on playlist_syncs (next_sync_at);
+
+CREATE TABLE IF NOT EXISTS playlist_sync_entries(
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    sync_id UUID NOT NULL REFERENCES playlist_syncs (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    spotify_track_id VARCHAR,
+    youtube_video_id VARCHAR,
+    title VARCHAR NOT NULL,
+    UNIQUE (sync_id, position)
+);
+
+CREATE TABLE IF NOT EXISTS playlist_sync_conflicts(
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    sync_id UUID NOT NULL REFERENCES playlist_syncs (id) ON DELETE CASCADE,
+    kind VARCHAR NOT NULL,
+    position INTEGER NOT NULL,
+    base_titles VARCHAR[] NOT NULL DEFAULT '{}',
+    spotify_titles VARCHAR[] NOT NULL DEFAULT '{}',
+    youtube_titles VARCHAR[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp
+);
+
+CREATE INDEX IF NOT EXISTS playlist_sync_conflicts_sync_id_idx on playlist_sync_conflicts (sync_id);
@@ -0,0 +1,27 @@
+CREATE TABLE IF NOT EXISTS playlist_snapshots(
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    user_id UUID NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    provider VARCHAR NOT NULL,
+    playlist_id VARCHAR NOT NULL,
+    version INTEGER NOT NULL,
+    origin VARCHAR NOT NULL,
+    name VARCHAR NOT NULL,
+    description VARCHAR,
+    source_version VARCHAR,
+    track_count INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp,
+    UNIQUE (user_id, provider, playlist_id, version)
+);
+
+CREATE TABLE IF NOT EXISTS playlist_snapshot_tracks(
+    id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+    snapshot_id UUID NOT NULL REFERENCES playlist_snapshots (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    provider_id VARCHAR,
+    title VARCHAR NOT NULL,
+    artists VARCHAR[] NOT NULL DEFAULT '{}',
+    album VARCHAR,
+    duration_ms BIGINT,
+    isrc VARCHAR,
+    UNIQUE (snapshot_id, position)
+);
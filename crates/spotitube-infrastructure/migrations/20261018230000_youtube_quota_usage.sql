@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS youtube_quota_usage(
+    quota_day DATE NOT NULL PRIMARY KEY,
+    used BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT current_timestamp
+);
@@ -0,0 +1,27 @@
+ALTER TABLE track_matches DROP CONSTRAINT IF EXISTS track_matches_source_provider_source_id_destination_provide_key;
+
+CREATE UNIQUE INDEX IF NOT EXISTS track_matches_automatic_idx on track_matches (source_provider, source_id, destination_provider) WHERE confirmed_by IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS track_matches_confirmed_idx on track_matches (source_provider, source_id, destination_provider, confirmed_by) WHERE confirmed_by IS NOT NULL;
+
+CREATE OR REPLACE FUNCTION expire_track_match_confirmations() RETURNS TRIGGER AS $$
+BEGIN
+    DELETE FROM track_matches confirmed
+    WHERE confirmed.confirmed_by = OLD.id
+        AND EXISTS (
+            SELECT 1 FROM track_matches automatic
+            WHERE automatic.confirmed_by IS NULL
+                AND automatic.source_provider = confirmed.source_provider
+                AND automatic.source_id = confirmed.source_id
+                AND automatic.destination_provider = confirmed.destination_provider
+        );
+
+    UPDATE track_matches SET confirmed_by = NULL, expires_at = current_timestamp, updated_at = current_timestamp
+    WHERE confirmed_by = OLD.id;
+
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS users_expire_track_match_confirmations ON users;
+CREATE TRIGGER users_expire_track_match_confirmations BEFORE DELETE ON users
+    FOR EACH ROW EXECUTE FUNCTION expire_track_match_confirmations();
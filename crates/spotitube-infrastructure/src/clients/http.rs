@@ -0,0 +1,89 @@
+use reqwest::{Response, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use spotitube_core::errors::{SpotitubeError, SpotitubeResult};
+use tracing::error;
+
+/// Appends escaped path segments to a configured base URL such as `https://api.spotify.com/v1`.
+pub fn endpoint(provider: &str, base_url: &str, segments: &[&str]) -> SpotitubeResult<Url> {
+    let mut url = Url::parse(base_url).map_err(|err| {
+        error!("invalid {} base url {:?}: {:?}", provider, base_url, err);
+        SpotitubeError::InternalServerError
+    })?;
+
+    url.path_segments_mut()
+        .map_err(|_| {
+            error!("{} base url {:?} cannot be a base", provider, base_url);
+            SpotitubeError::InternalServerError
+        })?
+        .pop_if_empty()
+        .extend(segments);
+
+    Ok(url)
+}
+
+pub fn request_error(provider: &str, err: reqwest::Error) -> SpotitubeError {
+    error!("{} request failed: {:?}", provider, err);
+    SpotitubeError::ProviderError(format!("{} request failed", provider))
+}
+
+pub async fn parse_response<T>(provider: &str, response: Response) -> SpotitubeResult<T>
+where
+    T: DeserializeOwned,
+{
+    ensure_success(provider, response)
+        .await?
+        .json::<T>()
+        .await
+        .map_err(|err| request_error(provider, err))
+}
+
+/// Maps error statuses to errors, for responses whose body is not needed.
+pub async fn ensure_success(provider: &str, response: Response) -> SpotitubeResult<Response> {
+    let status = response.status();
+    if status == StatusCode::UNAUTHORIZED {
+        return Err(SpotitubeError::ProviderUnauthorized(format!(
+            "{} rejected the access token",
+            provider
+        )));
+    }
+
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        error!("{} responded with {}: {}", provider, status, body);
+
+        if body.contains("quotaExceeded") {
+            return Err(SpotitubeError::ProviderQuotaExceeded(format!(
+                "{} quota exceeded",
+                provider
+            )));
+        }
+
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            return Err(SpotitubeError::ProviderRateLimited(format!(
+                "{} rate limit exceeded",
+                provider
+            )));
+        }
+
+        if status == StatusCode::NOT_FOUND {
+            return Err(SpotitubeError::NotFound(format!(
+                "{} resource not found",
+                provider
+            )));
+        }
+
+        if body.contains("invalid_grant") {
+            return Err(SpotitubeError::ProviderUnauthorized(format!(
+                "{} rejected the refresh token",
+                provider
+            )));
+        }
+
+        return Err(SpotitubeError::ProviderError(format!(
+            "{} responded with {}",
+            provider, status
+        )));
+    }
+
+    Ok(response)
+}
@@ -0,0 +1,6 @@
+pub mod http;
+pub mod pkce;
+pub mod spotify_client;
+#[cfg(test)]
+pub mod test_support;
+pub mod youtube_client;
@@ -0,0 +1,42 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
+use sha2::{Digest, Sha256};
+
+const VERIFIER_LENGTH: usize = 64;
+const STATE_LENGTH: usize = 32;
+
+/// Code verifier and S256 challenge for an OAuth authorization-code + PKCE flow (RFC 7636).
+pub struct PkceChallenge {
+    pub code_verifier: String,
+    pub code_challenge: String,
+}
+
+impl PkceChallenge {
+    pub fn new() -> Self {
+        let code_verifier = random_string(VERIFIER_LENGTH);
+        let code_challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()));
+
+        Self {
+            code_verifier,
+            code_challenge,
+        }
+    }
+}
+
+impl Default for PkceChallenge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn new_state() -> String {
+    random_string(STATE_LENGTH)
+}
+
+fn random_string(length: usize) -> String {
+    OsRng
+        .sample_iter(&Alphanumeric)
+        .take(length)
+        .map(char::from)
+        .collect()
+}
@@ -0,0 +1,608 @@
+use std::{cmp::Reverse, sync::Arc};
+
+use async_trait::async_trait;
+use reqwest::{Client, Method, Url};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::json;
+use spotitube_core::{
+    config::AppConfig,
+    errors::{SpotitubeError, SpotitubeResult},
+    oauth::client::{OAuthClient, OAuthTokens},
+    spotify::{
+        client::SpotifyClient,
+        models::{
+            SpotifyPage, SpotifyPlaylist, SpotifyPlaylistTrack, SpotifySavedTrack,
+            SpotifySearchResponse, SpotifySnapshotResponse, SpotifyTrack, SpotifyUser,
+        },
+    },
+};
+use tracing::error;
+
+use super::http::{endpoint, parse_response, request_error};
+
+const PROVIDER: &str = "spotify";
+const MAX_TRACKS_PER_REQUEST: usize = 100;
+
+pub struct SpotifyWebApiClient {
+    http: Client,
+    config: Arc<AppConfig>,
+}
+
+impl SpotifyWebApiClient {
+    pub fn new(config: Arc<AppConfig>) -> Self {
+        Self {
+            http: Client::new(),
+            config,
+        }
+    }
+
+    fn api_url(&self, segments: &[&str]) -> SpotitubeResult<Url> {
+        endpoint(PROVIDER, &self.config.spotify_api_url, segments)
+    }
+
+    async fn get<T>(
+        &self,
+        access_token: &str,
+        url: Url,
+        query: &[(&str, String)],
+    ) -> SpotitubeResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(access_token)
+            .query(query)
+            .send()
+            .await
+            .map_err(|err| request_error(PROVIDER, err))?;
+
+        parse_response(PROVIDER, response).await
+    }
+
+    async fn post<B, T>(&self, access_token: &str, url: Url, body: &B) -> SpotitubeResult<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let response = self
+            .http
+            .post(url)
+            .bearer_auth(access_token)
+            .json(body)
+            .send()
+            .await
+            .map_err(|err| request_error(PROVIDER, err))?;
+
+        parse_response(PROVIDER, response).await
+    }
+
+    async fn send<B, T>(
+        &self,
+        access_token: &str,
+        method: Method,
+        url: Url,
+        body: &B,
+    ) -> SpotitubeResult<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        let response = self
+            .http
+            .request(method, url)
+            .bearer_auth(access_token)
+            .json(body)
+            .send()
+            .await
+            .map_err(|err| request_error(PROVIDER, err))?;
+
+        parse_response(PROVIDER, response).await
+    }
+
+    async fn request_tokens(&self, params: &[(&str, &str)]) -> SpotitubeResult<OAuthTokens> {
+        let url = endpoint(
+            PROVIDER,
+            &self.config.spotify_accounts_url,
+            &["api", "token"],
+        )?;
+
+        let mut request = self.http.post(url).form(params);
+        if let Some(client_secret) = &self.config.spotify_client_secret {
+            request = request.basic_auth(&self.config.spotify_client_id, Some(client_secret));
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|err| request_error(PROVIDER, err))?;
+
+        parse_response(PROVIDER, response).await
+    }
+}
+
+#[async_trait]
+impl OAuthClient for SpotifyWebApiClient {
+    fn authorize_url(&self, state: &str, code_challenge: &str) -> SpotitubeResult<String> {
+        let mut url = endpoint(PROVIDER, &self.config.spotify_accounts_url, &["authorize"])?;
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.config.spotify_client_id)
+            .append_pair("response_type", "code")
+            .append_pair("redirect_uri", &self.config.spotify_redirect_uri)
+            .append_pair("scope", &self.config.spotify_scopes)
+            .append_pair("state", state)
+            .append_pair("code_challenge_method", "S256")
+            .append_pair("code_challenge", code_challenge);
+
+        Ok(url.into())
+    }
+
+    async fn exchange_code(&self, code: &str, code_verifier: &str) -> SpotitubeResult<OAuthTokens> {
+        self.request_tokens(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", &self.config.spotify_redirect_uri),
+            ("client_id", &self.config.spotify_client_id),
+            ("code_verifier", code_verifier),
+        ])
+        .await
+    }
+
+    async fn refresh_access_token(&self, refresh_token: &str) -> SpotitubeResult<OAuthTokens> {
+        self.request_tokens(&[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+            ("client_id", &self.config.spotify_client_id),
+        ])
+        .await
+    }
+}
+
+#[async_trait]
+impl SpotifyClient for SpotifyWebApiClient {
+    async fn get_current_user(&self, access_token: &str) -> SpotitubeResult<SpotifyUser> {
+        self.get(access_token, self.api_url(&["me"])?, &[]).await
+    }
+
+    async fn get_current_user_playlists(
+        &self,
+        access_token: &str,
+        limit: u32,
+        offset: u32,
+    ) -> SpotitubeResult<SpotifyPage<SpotifyPlaylist>> {
+        self.get(
+            access_token,
+            self.api_url(&["me", "playlists"])?,
+            &[("limit", limit.to_string()), ("offset", offset.to_string())],
+        )
+        .await
+    }
+
+    async fn get_playlist(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+    ) -> SpotitubeResult<SpotifyPlaylist> {
+        self.get(
+            access_token,
+            self.api_url(&["playlists", playlist_id])?,
+            &[],
+        )
+        .await
+    }
+
+    async fn get_playlist_tracks(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        limit: u32,
+        offset: u32,
+    ) -> SpotitubeResult<SpotifyPage<SpotifyPlaylistTrack>> {
+        self.get(
+            access_token,
+            self.api_url(&["playlists", playlist_id, "tracks"])?,
+            &[
+                ("limit", limit.to_string()),
+                ("offset", offset.to_string()),
+                ("additional_types", String::from("track")),
+            ],
+        )
+        .await
+    }
+
+    async fn get_saved_tracks(
+        &self,
+        access_token: &str,
+        limit: u32,
+        offset: u32,
+    ) -> SpotitubeResult<SpotifyPage<SpotifySavedTrack>> {
+        self.get(
+            access_token,
+            self.api_url(&["me", "tracks"])?,
+            &[("limit", limit.to_string()), ("offset", offset.to_string())],
+        )
+        .await
+    }
+
+    async fn get_track(&self, access_token: &str, track_id: &str) -> SpotitubeResult<SpotifyTrack> {
+        self.get(access_token, self.api_url(&["tracks", track_id])?, &[])
+            .await
+    }
+
+    async fn search_tracks(
+        &self,
+        access_token: &str,
+        query: &str,
+        limit: u32,
+    ) -> SpotitubeResult<Vec<SpotifyTrack>> {
+        let response: SpotifySearchResponse = self
+            .get(
+                access_token,
+                self.api_url(&["search"])?,
+                &[
+                    ("q", String::from(query)),
+                    ("type", String::from("track")),
+                    ("limit", limit.to_string()),
+                ],
+            )
+            .await?;
+
+        Ok(response.tracks.items)
+    }
+
+    async fn create_playlist(
+        &self,
+        access_token: &str,
+        user_id: &str,
+        name: &str,
+        description: &str,
+        public: bool,
+    ) -> SpotitubeResult<SpotifyPlaylist> {
+        self.post(
+            access_token,
+            self.api_url(&["users", user_id, "playlists"])?,
+            &json!({
+                "name": name,
+                "description": description,
+                "public": public,
+            }),
+        )
+        .await
+    }
+
+    async fn add_tracks_to_playlist(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        uris: &[String],
+        position: Option<u32>,
+    ) -> SpotitubeResult<String> {
+        let url = self.api_url(&["playlists", playlist_id, "tracks"])?;
+
+        let mut snapshot_id = None;
+        for (index, chunk) in uris.chunks(MAX_TRACKS_PER_REQUEST).enumerate() {
+            let body = match position {
+                Some(position) => json!({
+                    "uris": chunk,
+                    "position": position as usize + index * MAX_TRACKS_PER_REQUEST,
+                }),
+                None => json!({ "uris": chunk }),
+            };
+            let response: SpotifySnapshotResponse =
+                self.post(access_token, url.clone(), &body).await?;
+            snapshot_id = Some(response.snapshot_id);
+        }
+
+        snapshot_id.ok_or_else(|| {
+            error!("no tracks were added to spotify playlist {:?}", playlist_id);
+            SpotitubeError::BadRequest(String::from("no tracks to add"))
+        })
+    }
+
+    async fn remove_tracks_from_playlist(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        tracks: &[(String, u32)],
+        snapshot_id: Option<&str>,
+    ) -> SpotitubeResult<String> {
+        let url = self.api_url(&["playlists", playlist_id, "tracks"])?;
+
+        // Last positions first, so those of later chunks still hold in the new snapshot.
+        let mut tracks = tracks.to_vec();
+        tracks.sort_unstable_by_key(|(_, position)| Reverse(*position));
+
+        let mut snapshot_id = snapshot_id.map(String::from);
+        let mut removed = false;
+        for chunk in tracks.chunks(MAX_TRACKS_PER_REQUEST) {
+            let tracks: Vec<_> = chunk
+                .iter()
+                .map(|(uri, position)| json!({ "uri": uri, "positions": [position] }))
+                .collect();
+            let body = match &snapshot_id {
+                Some(snapshot_id) => json!({ "tracks": tracks, "snapshot_id": snapshot_id }),
+                None => json!({ "tracks": tracks }),
+            };
+            let response: SpotifySnapshotResponse = self
+                .send(access_token, Method::DELETE, url.clone(), &body)
+                .await?;
+            snapshot_id = Some(response.snapshot_id);
+            removed = true;
+        }
+
+        snapshot_id.filter(|_| removed).ok_or_else(|| {
+            error!(
+                "no tracks were removed from spotify playlist {:?}",
+                playlist_id
+            );
+            SpotitubeError::BadRequest(String::from("no tracks to remove"))
+        })
+    }
+
+    async fn reorder_playlist_track(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        range_start: u32,
+        insert_before: u32,
+    ) -> SpotitubeResult<String> {
+        let response: SpotifySnapshotResponse = self
+            .send(
+                access_token,
+                Method::PUT,
+                self.api_url(&["playlists", playlist_id, "tracks"])?,
+                &json!({ "range_start": range_start, "insert_before": insert_before }),
+            )
+            .await?;
+
+        Ok(response.snapshot_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use spotitube_core::{
+        errors::SpotitubeError, oauth::client::OAuthClient, spotify::client::SpotifyClient,
+    };
+    use wiremock::{
+        matchers::{body_json, body_string_contains, header, method, path, query_param},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::SpotifyWebApiClient;
+    use crate::clients::test_support::stub_config;
+
+    async fn client() -> (MockServer, SpotifyWebApiClient) {
+        let server = MockServer::start().await;
+        let client = SpotifyWebApiClient::new(stub_config(&server.uri(), &[]));
+        (server, client)
+    }
+
+    fn tokens() -> serde_json::Value {
+        json!({
+            "access_token": "access",
+            "refresh_token": "refresh",
+            "expires_in": 3600,
+            "scope": "playlist-read-private",
+        })
+    }
+
+    fn track(id: &str) -> serde_json::Value {
+        json!({
+            "added_at": "2024-01-01T00:00:00Z",
+            "track": {
+                "id": id,
+                "name": id,
+                "uri": format!("spotify:track:{}", id),
+                "duration_ms": 200000,
+                "artists": [{ "id": "artist", "name": "Artist" }],
+                "album": null,
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn exchanges_code_with_verifier_and_client_credentials() {
+        let (server, client) = client().await;
+        Mock::given(method("POST"))
+            .and(path("/accounts/api/token"))
+            .and(header(
+                "authorization",
+                "Basic c3BvdGlmeS1jbGllbnQ6c3BvdGlmeS1zZWNyZXQ=",
+            ))
+            .and(body_string_contains("grant_type=authorization_code"))
+            .and(body_string_contains("code=the-code"))
+            .and(body_string_contains("code_verifier=the-verifier"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(tokens()))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let tokens = client
+            .exchange_code("the-code", "the-verifier")
+            .await
+            .unwrap();
+
+        assert_eq!(tokens.access_token, "access");
+        assert_eq!(tokens.refresh_token.as_deref(), Some("refresh"));
+        assert_eq!(tokens.expires_in, 3600);
+    }
+
+    #[tokio::test]
+    async fn refreshes_access_token() {
+        let (server, client) = client().await;
+        Mock::given(method("POST"))
+            .and(path("/accounts/api/token"))
+            .and(body_string_contains("grant_type=refresh_token"))
+            .and(body_string_contains("refresh_token=old-refresh"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "access_token": "fresh",
+                "expires_in": 3600,
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let tokens = client.refresh_access_token("old-refresh").await.unwrap();
+
+        assert_eq!(tokens.access_token, "fresh");
+        assert_eq!(tokens.refresh_token, None);
+    }
+
+    #[tokio::test]
+    async fn maps_revoked_refresh_token_to_provider_unauthorized() {
+        let (server, client) = client().await;
+        Mock::given(method("POST"))
+            .and(path("/accounts/api/token"))
+            .respond_with(
+                ResponseTemplate::new(400).set_body_json(json!({ "error": "invalid_grant" })),
+            )
+            .mount(&server)
+            .await;
+
+        let result = client.refresh_access_token("revoked").await;
+
+        assert!(matches!(
+            result,
+            Err(SpotitubeError::ProviderUnauthorized(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn requests_playlist_tracks_page_by_offset() {
+        let (server, client) = client().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/playlists/playlist/tracks"))
+            .and(header("authorization", "Bearer token"))
+            .and(query_param("limit", "2"))
+            .and(query_param("offset", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": [track("t3"), track("t4")],
+                "limit": 2,
+                "offset": 2,
+                "total": 5,
+                "next": "https://api.spotify.com/v1/playlists/playlist/tracks?offset=4&limit=2",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let page = client
+            .get_playlist_tracks("token", "playlist", 2, 2)
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = page
+            .items
+            .iter()
+            .map(|item| item.track.as_ref().unwrap().id.as_deref().unwrap())
+            .collect();
+        assert_eq!(ids, ["t3", "t4"]);
+        assert_eq!(page.total, 5);
+        assert!(page.next.is_some());
+    }
+
+    #[tokio::test]
+    async fn adds_tracks_in_chunks_of_one_hundred() {
+        let (server, client) = client().await;
+        let uris: Vec<String> = (0..150)
+            .map(|index| format!("spotify:track:{}", index))
+            .collect();
+        Mock::given(method("POST"))
+            .and(path("/v1/playlists/playlist/tracks"))
+            .and(body_json(json!({ "uris": uris[..100], "position": 0 })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "snapshot_id": "s1" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/playlists/playlist/tracks"))
+            .and(body_json(json!({ "uris": uris[100..], "position": 100 })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "snapshot_id": "s2" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let snapshot_id = client
+            .add_tracks_to_playlist("token", "playlist", &uris, Some(0))
+            .await
+            .unwrap();
+
+        assert_eq!(snapshot_id, "s2");
+    }
+
+    #[tokio::test]
+    async fn removes_tracks_by_position_from_the_last_one() {
+        let (server, client) = client().await;
+        let tracks: Vec<(String, u32)> = (0..101)
+            .map(|position| (String::from("spotify:track:dup"), position))
+            .collect();
+        let removal = |positions: std::ops::RangeInclusive<u32>| -> Vec<serde_json::Value> {
+            positions
+                .rev()
+                .map(|position| json!({ "uri": "spotify:track:dup", "positions": [position] }))
+                .collect()
+        };
+        Mock::given(method("DELETE"))
+            .and(path("/v1/playlists/playlist/tracks"))
+            .and(body_json(
+                json!({ "tracks": removal(1..=100), "snapshot_id": "s0" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "snapshot_id": "s1" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/v1/playlists/playlist/tracks"))
+            .and(body_json(
+                json!({ "tracks": removal(0..=0), "snapshot_id": "s1" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "snapshot_id": "s2" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let snapshot_id = client
+            .remove_tracks_from_playlist("token", "playlist", &tracks, Some("s0"))
+            .await
+            .unwrap();
+
+        assert_eq!(snapshot_id, "s2");
+    }
+
+    #[tokio::test]
+    async fn maps_error_statuses() {
+        let (server, client) = client().await;
+        for (track_id, status) in [
+            ("limited", 429),
+            ("missing", 404),
+            ("expired", 401),
+            ("broken", 500),
+        ] {
+            Mock::given(method("GET"))
+                .and(path(format!("/v1/tracks/{}", track_id)))
+                .respond_with(ResponseTemplate::new(status))
+                .mount(&server)
+                .await;
+        }
+
+        assert!(matches!(
+            client.get_track("token", "limited").await,
+            Err(SpotitubeError::ProviderRateLimited(_))
+        ));
+        assert!(matches!(
+            client.get_track("token", "missing").await,
+            Err(SpotitubeError::NotFound(_))
+        ));
+        assert!(matches!(
+            client.get_track("token", "expired").await,
+            Err(SpotitubeError::ProviderUnauthorized(_))
+        ));
+        assert!(matches!(
+            client.get_track("token", "broken").await,
+            Err(SpotitubeError::ProviderError(_))
+        ));
+    }
+}
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use clap::Parser;
+use spotitube_core::config::AppConfig;
+
+/// Configuration pointing the provider clients at a local stub server.
+pub fn stub_config(stub_url: &str, extra_args: &[&str]) -> Arc<AppConfig> {
+    let mut args = vec![
+        String::from("spotitube"),
+        String::from("--database-url=postgres://localhost/spotitube"),
+        String::from("--rust-log=info"),
+        String::from("--token-secret=secret"),
+        String::from("--port=0"),
+        String::from("--cors-origing=http://localhost"),
+        String::from("--spotify-client-id=spotify-client"),
+        String::from("--spotify-client-secret=spotify-secret"),
+        String::from("--spotify-redirect-uri=http://localhost/callback"),
+        format!("--spotify-api-url={}/v1", stub_url),
+        format!("--spotify-accounts-url={}/accounts", stub_url),
+        String::from("--youtube-client-id=youtube-client"),
+        String::from("--youtube-client-secret=youtube-secret"),
+        format!("--youtube-api-url={}/youtube/v3", stub_url),
+        format!("--youtube-token-url={}/token", stub_url),
+    ];
+    args.extend(extra_args.iter().map(|arg| String::from(*arg)));
+
+    Arc::new(AppConfig::parse_from(args))
+}
@@ -0,0 +1,663 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use reqwest::{Client, Url};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::json;
+use spotitube_core::{
+    config::AppConfig,
+    errors::{SpotitubeError, SpotitubeResult},
+    oauth::client::{OAuthClient, OAuthTokens},
+    youtube::{
+        client::YouTubeClient,
+        models::{
+            YouTubeChannel, YouTubePage, YouTubePlaylist, YouTubePlaylistItem, YouTubeQuotaUsage,
+            YouTubeSearchResult, YouTubeVideo,
+        },
+        repository::DynYouTubeQuotaRepository,
+    },
+};
+use tracing::debug;
+
+use super::http::{endpoint, ensure_success, parse_response, request_error};
+
+const PROVIDER: &str = "youtube";
+
+/// Quota costs from https://developers.google.com/youtube/v3/determine_quota_cost
+const LIST_COST: u64 = 1;
+const SEARCH_COST: u64 = 100;
+const INSERT_COST: u64 = 50;
+const UPDATE_COST: u64 = 50;
+const DELETE_COST: u64 = 50;
+
+const MAX_VIDEOS_PER_REQUEST: usize = 50;
+
+pub struct YouTubeDataApiClient {
+    http: Client,
+    config: Arc<AppConfig>,
+    quota_repository: DynYouTubeQuotaRepository,
+}
+
+impl YouTubeDataApiClient {
+    pub fn new(config: Arc<AppConfig>, quota_repository: DynYouTubeQuotaRepository) -> Self {
+        Self {
+            http: Client::new(),
+            config,
+            quota_repository,
+        }
+    }
+
+    /// Spends quota units against the daily limit shared by every instance.
+    async fn reserve_quota(&self, cost: u64) -> SpotitubeResult<()> {
+        let reserved = self
+            .quota_repository
+            .reserve_quota(cost as i64, self.config.youtube_daily_quota as i64)
+            .await?;
+        if !reserved {
+            return Err(SpotitubeError::ProviderQuotaExceeded(String::from(
+                "youtube daily quota exhausted",
+            )));
+        }
+
+        debug!("reserved {} youtube quota units", cost);
+        Ok(())
+    }
+
+    fn api_url(&self, segments: &[&str]) -> SpotitubeResult<Url> {
+        endpoint(PROVIDER, &self.config.youtube_api_url, segments)
+    }
+
+    async fn get<T>(
+        &self,
+        access_token: &str,
+        url: Url,
+        query: &[(&str, String)],
+        cost: u64,
+    ) -> SpotitubeResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.reserve_quota(cost).await?;
+
+        let response = self
+            .http
+            .get(url)
+            .bearer_auth(access_token)
+            .query(query)
+            .send()
+            .await
+            .map_err(|err| request_error(PROVIDER, err))?;
+
+        parse_response(PROVIDER, response).await
+    }
+
+    async fn post<B, T>(
+        &self,
+        access_token: &str,
+        url: Url,
+        query: &[(&str, String)],
+        body: &B,
+        cost: u64,
+    ) -> SpotitubeResult<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        self.reserve_quota(cost).await?;
+
+        let response = self
+            .http
+            .post(url)
+            .bearer_auth(access_token)
+            .query(query)
+            .json(body)
+            .send()
+            .await
+            .map_err(|err| request_error(PROVIDER, err))?;
+
+        parse_response(PROVIDER, response).await
+    }
+
+    async fn put<B, T>(
+        &self,
+        access_token: &str,
+        url: Url,
+        query: &[(&str, String)],
+        body: &B,
+        cost: u64,
+    ) -> SpotitubeResult<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        self.reserve_quota(cost).await?;
+
+        let response = self
+            .http
+            .put(url)
+            .bearer_auth(access_token)
+            .query(query)
+            .json(body)
+            .send()
+            .await
+            .map_err(|err| request_error(PROVIDER, err))?;
+
+        parse_response(PROVIDER, response).await
+    }
+
+    async fn delete(
+        &self,
+        access_token: &str,
+        url: Url,
+        query: &[(&str, String)],
+        cost: u64,
+    ) -> SpotitubeResult<()> {
+        self.reserve_quota(cost).await?;
+
+        let response = self
+            .http
+            .delete(url)
+            .bearer_auth(access_token)
+            .query(query)
+            .send()
+            .await
+            .map_err(|err| request_error(PROVIDER, err))?;
+
+        ensure_success(PROVIDER, response).await?;
+        Ok(())
+    }
+
+    async fn request_tokens(&self, params: &[(&str, &str)]) -> SpotitubeResult<OAuthTokens> {
+        let url = endpoint(PROVIDER, &self.config.youtube_token_url, &[])?;
+
+        let mut params = params.to_vec();
+        params.push(("client_id", &self.config.youtube_client_id));
+        if let Some(client_secret) = &self.config.youtube_client_secret {
+            params.push(("client_secret", client_secret));
+        }
+
+        let response = self
+            .http
+            .post(url)
+            .form(&params)
+            .send()
+            .await
+            .map_err(|err| request_error(PROVIDER, err))?;
+
+        parse_response(PROVIDER, response).await
+    }
+}
+
+#[async_trait]
+impl OAuthClient for YouTubeDataApiClient {
+    fn authorize_url(&self, state: &str, code_challenge: &str) -> SpotitubeResult<String> {
+        let mut url = endpoint(PROVIDER, &self.config.youtube_authorize_url, &[])?;
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.config.youtube_client_id)
+            .append_pair("response_type", "code")
+            .append_pair("redirect_uri", &self.config.youtube_redirect_uri)
+            .append_pair("scope", &self.config.youtube_scopes)
+            .append_pair("state", state)
+            .append_pair("code_challenge_method", "S256")
+            .append_pair("code_challenge", code_challenge)
+            .append_pair("access_type", "offline")
+            .append_pair("prompt", "consent");
+
+        Ok(url.into())
+    }
+
+    async fn exchange_code(&self, code: &str, code_verifier: &str) -> SpotitubeResult<OAuthTokens> {
+        self.request_tokens(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", &self.config.youtube_redirect_uri),
+            ("code_verifier", code_verifier),
+        ])
+        .await
+    }
+
+    async fn refresh_access_token(&self, refresh_token: &str) -> SpotitubeResult<OAuthTokens> {
+        self.request_tokens(&[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+        ])
+        .await
+    }
+}
+
+#[async_trait]
+impl YouTubeClient for YouTubeDataApiClient {
+    async fn get_my_channel(&self, access_token: &str) -> SpotitubeResult<YouTubeChannel> {
+        let page: YouTubePage<YouTubeChannel> = self
+            .get(
+                access_token,
+                self.api_url(&["channels"])?,
+                &[
+                    ("part", String::from("snippet")),
+                    ("mine", String::from("true")),
+                ],
+                LIST_COST,
+            )
+            .await?;
+
+        page.items.into_iter().next().ok_or_else(|| {
+            SpotitubeError::ProviderError(String::from("youtube account has no channel"))
+        })
+    }
+
+    async fn list_playlists(
+        &self,
+        access_token: &str,
+        page_token: Option<&str>,
+        max_results: u32,
+    ) -> SpotitubeResult<YouTubePage<YouTubePlaylist>> {
+        let mut query = vec![
+            ("part", String::from("snippet,status,contentDetails")),
+            ("mine", String::from("true")),
+            ("maxResults", max_results.to_string()),
+        ];
+        if let Some(page_token) = page_token {
+            query.push(("pageToken", String::from(page_token)));
+        }
+
+        self.get(
+            access_token,
+            self.api_url(&["playlists"])?,
+            &query,
+            LIST_COST,
+        )
+        .await
+    }
+
+    async fn get_playlist(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+    ) -> SpotitubeResult<YouTubePlaylist> {
+        let page: YouTubePage<YouTubePlaylist> = self
+            .get(
+                access_token,
+                self.api_url(&["playlists"])?,
+                &[
+                    ("part", String::from("snippet,status,contentDetails")),
+                    ("id", String::from(playlist_id)),
+                ],
+                LIST_COST,
+            )
+            .await?;
+
+        page.items.into_iter().next().ok_or_else(|| {
+            SpotitubeError::NotFound(format!("youtube playlist {} not found", playlist_id))
+        })
+    }
+
+    async fn list_playlist_items(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        page_token: Option<&str>,
+        max_results: u32,
+    ) -> SpotitubeResult<YouTubePage<YouTubePlaylistItem>> {
+        let mut query = vec![
+            ("part", String::from("snippet")),
+            ("playlistId", String::from(playlist_id)),
+            ("maxResults", max_results.to_string()),
+        ];
+        if let Some(page_token) = page_token {
+            query.push(("pageToken", String::from(page_token)));
+        }
+
+        self.get(
+            access_token,
+            self.api_url(&["playlistItems"])?,
+            &query,
+            LIST_COST,
+        )
+        .await
+    }
+
+    async fn search_videos(
+        &self,
+        access_token: &str,
+        query: &str,
+        max_results: u32,
+    ) -> SpotitubeResult<Vec<YouTubeSearchResult>> {
+        let page: YouTubePage<YouTubeSearchResult> = self
+            .get(
+                access_token,
+                self.api_url(&["search"])?,
+                &[
+                    ("part", String::from("snippet")),
+                    ("type", String::from("video")),
+                    ("q", String::from(query)),
+                    ("maxResults", max_results.to_string()),
+                ],
+                SEARCH_COST,
+            )
+            .await?;
+
+        Ok(page.items)
+    }
+
+    async fn get_videos(
+        &self,
+        access_token: &str,
+        video_ids: &[String],
+    ) -> SpotitubeResult<Vec<YouTubeVideo>> {
+        let url = self.api_url(&["videos"])?;
+
+        let mut videos = Vec::with_capacity(video_ids.len());
+        for chunk in video_ids.chunks(MAX_VIDEOS_PER_REQUEST) {
+            let page: YouTubePage<YouTubeVideo> = self
+                .get(
+                    access_token,
+                    url.clone(),
+                    &[
+                        ("part", String::from("snippet,contentDetails")),
+                        ("id", chunk.join(",")),
+                    ],
+                    LIST_COST,
+                )
+                .await?;
+            videos.extend(page.items);
+        }
+
+        Ok(videos)
+    }
+
+    async fn create_playlist(
+        &self,
+        access_token: &str,
+        title: &str,
+        description: &str,
+        privacy_status: &str,
+    ) -> SpotitubeResult<YouTubePlaylist> {
+        self.post(
+            access_token,
+            self.api_url(&["playlists"])?,
+            &[("part", String::from("snippet,status"))],
+            &json!({
+                "snippet": { "title": title, "description": description },
+                "status": { "privacyStatus": privacy_status },
+            }),
+            INSERT_COST,
+        )
+        .await
+    }
+
+    async fn insert_playlist_item(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        video_id: &str,
+        position: Option<u32>,
+    ) -> SpotitubeResult<YouTubePlaylistItem> {
+        let mut snippet = json!({
+            "playlistId": playlist_id,
+            "resourceId": { "kind": "youtube#video", "videoId": video_id },
+        });
+        if let Some(position) = position {
+            snippet["position"] = json!(position);
+        }
+
+        self.post(
+            access_token,
+            self.api_url(&["playlistItems"])?,
+            &[("part", String::from("snippet"))],
+            &json!({ "snippet": snippet }),
+            INSERT_COST,
+        )
+        .await
+    }
+
+    async fn update_playlist_item(
+        &self,
+        access_token: &str,
+        item_id: &str,
+        playlist_id: &str,
+        video_id: &str,
+        position: u32,
+    ) -> SpotitubeResult<YouTubePlaylistItem> {
+        self.put(
+            access_token,
+            self.api_url(&["playlistItems"])?,
+            &[("part", String::from("snippet"))],
+            &json!({
+                "id": item_id,
+                "snippet": {
+                    "playlistId": playlist_id,
+                    "resourceId": { "kind": "youtube#video", "videoId": video_id },
+                    "position": position,
+                },
+            }),
+            UPDATE_COST,
+        )
+        .await
+    }
+
+    async fn delete_playlist_item(&self, access_token: &str, item_id: &str) -> SpotitubeResult<()> {
+        self.delete(
+            access_token,
+            self.api_url(&["playlistItems"])?,
+            &[("id", String::from(item_id))],
+            DELETE_COST,
+        )
+        .await
+    }
+
+    async fn quota_usage(&self) -> SpotitubeResult<YouTubeQuotaUsage> {
+        let used = self.quota_repository.get_quota_used().await?;
+
+        Ok(YouTubeQuotaUsage {
+            used: used as u64,
+            limit: self.config.youtube_daily_quota,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use serde_json::json;
+    use spotitube_core::{
+        errors::{SpotitubeError, SpotitubeResult},
+        oauth::client::OAuthClient,
+        youtube::{client::YouTubeClient, repository::YouTubeQuotaRepository},
+    };
+    use time::{Duration, OffsetDateTime};
+    use wiremock::{
+        matchers::{body_string_contains, method, path, query_param, query_param_is_missing},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::YouTubeDataApiClient;
+    use crate::clients::test_support::stub_config;
+
+    #[derive(Default)]
+    struct InMemoryQuotaRepository {
+        used: Mutex<i64>,
+    }
+
+    #[async_trait]
+    impl YouTubeQuotaRepository for InMemoryQuotaRepository {
+        async fn reserve_quota(&self, cost: i64, limit: i64) -> SpotitubeResult<bool> {
+            let mut used = self.used.lock().unwrap();
+            if *used + cost > limit {
+                return Ok(false);
+            }
+            *used += cost;
+            Ok(true)
+        }
+
+        async fn get_quota_used(&self) -> SpotitubeResult<i64> {
+            Ok(*self.used.lock().unwrap())
+        }
+
+        async fn get_quota_reset(&self) -> SpotitubeResult<OffsetDateTime> {
+            Ok(OffsetDateTime::now_utc() + Duration::days(1))
+        }
+    }
+
+    async fn client(daily_quota: u64) -> (MockServer, YouTubeDataApiClient) {
+        let server = MockServer::start().await;
+        let quota = format!("--youtube-daily-quota={}", daily_quota);
+        let client = YouTubeDataApiClient::new(
+            stub_config(&server.uri(), &[&quota]),
+            Arc::new(InMemoryQuotaRepository::default()),
+        );
+        (server, client)
+    }
+
+    fn playlist_item(id: &str) -> serde_json::Value {
+        json!({
+            "id": format!("item-{}", id),
+            "snippet": {
+                "title": id,
+                "playlistId": "playlist",
+                "position": 0,
+                "resourceId": { "kind": "youtube#video", "videoId": id },
+                "videoOwnerChannelTitle": "Channel",
+            },
+        })
+    }
+
+    #[tokio::test]
+    async fn refreshes_access_token_with_client_credentials() {
+        let (server, client) = client(100).await;
+        Mock::given(method("POST"))
+            .and(path("/token"))
+            .and(body_string_contains("grant_type=refresh_token"))
+            .and(body_string_contains("refresh_token=old-refresh"))
+            .and(body_string_contains("client_id=youtube-client"))
+            .and(body_string_contains("client_secret=youtube-secret"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "access_token": "fresh",
+                "expires_in": 3599,
+                "scope": "https://www.googleapis.com/auth/youtube",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let tokens = client.refresh_access_token("old-refresh").await.unwrap();
+
+        assert_eq!(tokens.access_token, "fresh");
+        assert_eq!(tokens.expires_in, 3599);
+    }
+
+    #[tokio::test]
+    async fn follows_playlist_item_page_tokens() {
+        let (server, client) = client(100).await;
+        Mock::given(method("GET"))
+            .and(path("/youtube/v3/playlistItems"))
+            .and(query_param("playlistId", "playlist"))
+            .and(query_param_is_missing("pageToken"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": [playlist_item("v1")],
+                "nextPageToken": "page-2",
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/youtube/v3/playlistItems"))
+            .and(query_param("pageToken", "page-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": [playlist_item("v2")],
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let first = client
+            .list_playlist_items("token", "playlist", None, 1)
+            .await
+            .unwrap();
+        let second = client
+            .list_playlist_items("token", "playlist", first.next_page_token.as_deref(), 1)
+            .await
+            .unwrap();
+
+        assert_eq!(first.items[0].snippet.available_video_id(), Some("v1"));
+        assert_eq!(second.items[0].snippet.available_video_id(), Some("v2"));
+        assert_eq!(second.next_page_token, None);
+    }
+
+    #[tokio::test]
+    async fn requests_videos_in_batches_of_fifty() {
+        let (server, client) = client(100).await;
+        Mock::given(method("GET"))
+            .and(path("/youtube/v3/videos"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "items": [] })))
+            .expect(2)
+            .mount(&server)
+            .await;
+        let video_ids: Vec<String> = (0..60).map(|index| format!("v{}", index)).collect();
+
+        client.get_videos("token", &video_ids).await.unwrap();
+
+        let usage = client.quota_usage().await.unwrap();
+        assert_eq!(usage.used, 2);
+    }
+
+    #[tokio::test]
+    async fn refuses_requests_over_the_daily_quota() {
+        let (server, client) = client(150).await;
+        Mock::given(method("GET"))
+            .and(path("/youtube/v3/search"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "items": [] })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        client.search_videos("token", "song", 5).await.unwrap();
+        let result = client.search_videos("token", "song", 5).await;
+
+        assert!(matches!(
+            result,
+            Err(SpotitubeError::ProviderQuotaExceeded(_))
+        ));
+        let usage = client.quota_usage().await.unwrap();
+        assert_eq!((usage.used, usage.limit), (100, 150));
+    }
+
+    #[tokio::test]
+    async fn maps_error_responses() {
+        let (server, client) = client(100).await;
+        Mock::given(method("GET"))
+            .and(path("/youtube/v3/playlists"))
+            .and(query_param("id", "exhausted"))
+            .respond_with(ResponseTemplate::new(403).set_body_json(json!({
+                "error": { "errors": [{ "reason": "quotaExceeded" }] },
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/youtube/v3/playlists"))
+            .and(query_param("id", "missing"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "items": [] })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/youtube/v3/playlists"))
+            .and(query_param("id", "expired"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        assert!(matches!(
+            client.get_playlist("token", "exhausted").await,
+            Err(SpotitubeError::ProviderQuotaExceeded(_))
+        ));
+        assert!(matches!(
+            client.get_playlist("token", "missing").await,
+            Err(SpotitubeError::NotFound(_))
+        ));
+        assert!(matches!(
+            client.get_playlist("token", "expired").await,
+            Err(SpotitubeError::ProviderUnauthorized(_))
+        ));
+    }
+}
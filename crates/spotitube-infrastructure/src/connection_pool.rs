@@ -23,4 +23,9 @@ impl SpotitubeConnectionPoolManager {
 
         Ok(pool)
     }
+
+    pub async fn close_pool(pool: &SpotitubeConnectionPool) {
+        info!("closing connection pool...");
+        pool.close().await;
+    }
 }
@@ -1,4 +1,7 @@
+pub mod clients;
 pub mod connection_pool;
+pub mod reencryption_service;
 pub mod repositories;
+pub mod seed_service;
 pub mod service_register;
-pub mod services;
\ No newline at end of file
+pub mod services;
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use spotitube_core::{
+    accounts::repository::DynLinkedAccountsRepository, config::AppConfig, errors::SpotitubeResult,
+    utils::encryption_service::DynEncryptionService,
+};
+use tracing::{info, warn};
+
+use crate::{
+    connection_pool::SpotitubeConnectionPool,
+    repositories::linked_accounts_repository::PostgresLinkedAccountsRepository,
+    services::utils::aes_encryption_service::AesEncryptionService,
+};
+
+const BATCH_SIZE: i64 = 100;
+
+/// Re-seals stored provider tokens under the active encryption key, so retired key versions
+/// can be removed from the configuration afterwards.
+pub struct SpotitubeReencryptionService {
+    linked_accounts_repository: DynLinkedAccountsRepository,
+    encryption_service: DynEncryptionService,
+}
+
+impl SpotitubeReencryptionService {
+    pub fn new(pool: SpotitubeConnectionPool, config: Arc<AppConfig>) -> SpotitubeResult<Self> {
+        Ok(Self {
+            linked_accounts_repository: Arc::new(PostgresLinkedAccountsRepository::new(pool)),
+            encryption_service: Arc::new(AesEncryptionService::new(config)?),
+        })
+    }
+
+    pub async fn reencrypt(&self) -> SpotitubeResult<()> {
+        info!("re-encrypting linked account tokens...");
+
+        let mut after = None;
+        let mut reencrypted = 0;
+        loop {
+            let accounts = self
+                .linked_accounts_repository
+                .get_linked_accounts_after(after, BATCH_SIZE)
+                .await?;
+            let Some(last) = accounts.last() else {
+                break;
+            };
+            after = Some(last.id);
+
+            for account in accounts {
+                let refresh_token_outdated = account
+                    .refresh_token
+                    .as_deref()
+                    .is_some_and(|token| self.encryption_service.needs_reencryption(token));
+                if !self
+                    .encryption_service
+                    .needs_reencryption(&account.access_token)
+                    && !refresh_token_outdated
+                {
+                    continue;
+                }
+
+                let access_token = self.encryption_service.reencrypt(&account.access_token)?;
+                let refresh_token = account
+                    .refresh_token
+                    .as_deref()
+                    .map(|token| self.encryption_service.reencrypt(token))
+                    .transpose()?;
+
+                if self
+                    .linked_accounts_repository
+                    .replace_linked_account_tokens(
+                        &account.id,
+                        &account.access_token,
+                        &access_token,
+                        refresh_token.as_deref(),
+                    )
+                    .await?
+                {
+                    reencrypted += 1;
+                } else {
+                    warn!(
+                        "linked account {:?} changed while re-encrypting, skipping",
+                        account.id
+                    );
+                }
+            }
+        }
+
+        info!("re-encrypted tokens of {} linked accounts", reencrypted);
+        Ok(())
+    }
+}
@@ -0,0 +1,336 @@
+use async_trait::async_trait;
+use spotitube_core::{
+    conversions::{
+        repository::{
+            ConversionEntity, ConversionTrackEntity, ConversionsRepository, NewConversionTrack,
+            NewTrackCandidate, TrackCandidateEntity, TrackOutcome,
+        },
+        runner::{ConversionJob, CONVERSION_JOB},
+    },
+    errors::{SpotitubeError, SpotitubeResult},
+};
+use spotitube_domain::conversions::TrackStatus;
+use time::OffsetDateTime;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::connection_pool::SpotitubeConnectionPool;
+
+#[derive(Clone)]
+pub struct PostgresConversionsRepository {
+    pool: SpotitubeConnectionPool,
+}
+
+impl PostgresConversionsRepository {
+    pub fn new(pool: SpotitubeConnectionPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ConversionsRepository for PostgresConversionsRepository {
+    async fn create_conversion(
+        &self,
+        user_id: &Uuid,
+        source_provider: &str,
+        source_playlist_id: &str,
+        destination_provider: &str,
+        name: Option<&str>,
+        max_attempts: i32,
+    ) -> SpotitubeResult<ConversionEntity> {
+        let mut transaction = self.pool.begin().await?;
+        let conversion = sqlx::query_as!(
+            ConversionEntity,
+            r#"INSERT INTO conversions (user_id, source_provider, source_playlist_id, destination_provider, name) values ($1, $2::varchar, $3::varchar, $4::varchar, $5::varchar) returning *"#,
+            user_id,
+            source_provider,
+            source_playlist_id,
+            destination_provider,
+            name
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+
+        let payload = serde_json::to_value(ConversionJob {
+            conversion_id: conversion.id,
+        })
+        .map_err(|err| {
+            error!("failed to serialize conversion job: {:?}", err);
+            SpotitubeError::InternalServerError
+        })?;
+        sqlx::query!(
+            r#"INSERT INTO jobs (kind, payload, max_attempts) values ($1::varchar, $2, $3)"#,
+            CONVERSION_JOB,
+            payload,
+            max_attempts
+        )
+        .execute(&mut *transaction)
+        .await?;
+        transaction.commit().await?;
+
+        Ok(conversion)
+    }
+
+    async fn get_conversion(&self, id: &Uuid) -> SpotitubeResult<Option<ConversionEntity>> {
+        let conversion = sqlx::query_as!(
+            ConversionEntity,
+            r#"SELECT * FROM conversions WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(conversion)
+    }
+
+    async fn update_conversion_status(
+        &self,
+        id: &Uuid,
+        status: &str,
+        error: Option<&str>,
+    ) -> SpotitubeResult<()> {
+        sqlx::query!(
+            r#"UPDATE conversions SET
+                status = $2::varchar,
+                error = $3::varchar,
+                completed_at = CASE WHEN $2 IN ('completed', 'failed') THEN current_timestamp END,
+                updated_at = current_timestamp
+            WHERE id = $1"#,
+            id,
+            status,
+            error
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_conversion_source(
+        &self,
+        id: &Uuid,
+        name: &str,
+        tracks: &[NewConversionTrack],
+    ) -> SpotitubeResult<Vec<ConversionTrackEntity>> {
+        let mut transaction = self.pool.begin().await?;
+        sqlx::query!(
+            r#"UPDATE conversions SET name = COALESCE(name, $2::varchar), total_tracks = $3, updated_at = current_timestamp WHERE id = $1"#,
+            id,
+            name,
+            tracks.len() as i32
+        )
+        .execute(&mut *transaction)
+        .await?;
+
+        let mut conversion_tracks = Vec::with_capacity(tracks.len());
+        for track in tracks {
+            let conversion_track = sqlx::query_as!(
+                ConversionTrackEntity,
+                r#"INSERT INTO conversion_tracks (conversion_id, position, source_id, title, artists, album, duration_ms, isrc)
+                values ($1, $2, $3::varchar, $4::varchar, $5::varchar[], $6::varchar, $7, $8::varchar)
+                returning *"#,
+                id,
+                track.position,
+                track.source_id,
+                track.title,
+                &track.artists,
+                track.album,
+                track.duration_ms,
+                track.isrc
+            )
+            .fetch_one(&mut *transaction)
+            .await?;
+            conversion_tracks.push(conversion_track);
+        }
+        transaction.commit().await?;
+
+        Ok(conversion_tracks)
+    }
+
+    async fn update_conversion_destination(
+        &self,
+        id: &Uuid,
+        destination_playlist_id: &str,
+    ) -> SpotitubeResult<()> {
+        sqlx::query!(
+            r#"UPDATE conversions SET destination_playlist_id = $2::varchar, updated_at = current_timestamp WHERE id = $1"#,
+            id,
+            destination_playlist_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_conversion_tracks(
+        &self,
+        conversion_id: &Uuid,
+    ) -> SpotitubeResult<Vec<ConversionTrackEntity>> {
+        let conversion_tracks = sqlx::query_as!(
+            ConversionTrackEntity,
+            r#"SELECT * FROM conversion_tracks WHERE conversion_id = $1 ORDER BY position"#,
+            conversion_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(conversion_tracks)
+    }
+
+    async fn get_conversion_track(
+        &self,
+        conversion_id: &Uuid,
+        position: i32,
+    ) -> SpotitubeResult<Option<ConversionTrackEntity>> {
+        let conversion_track = sqlx::query_as!(
+            ConversionTrackEntity,
+            r#"SELECT * FROM conversion_tracks WHERE conversion_id = $1 AND position = $2"#,
+            conversion_id,
+            position
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(conversion_track)
+    }
+
+    async fn update_conversion_track(
+        &self,
+        id: &Uuid,
+        outcome: TrackOutcome<'_>,
+    ) -> SpotitubeResult<()> {
+        sqlx::query!(
+            r#"UPDATE conversion_tracks SET
+                status = $2::varchar,
+                destination_id = $3::varchar,
+                destination_title = $4::varchar,
+                confidence = $5,
+                match_reasons = $6::varchar[],
+                error = $7::varchar,
+                updated_at = current_timestamp
+            WHERE id = $1"#,
+            id,
+            outcome.status.as_str(),
+            outcome.destination_id,
+            outcome.destination_title,
+            outcome.confidence,
+            outcome.match_reasons,
+            outcome.error
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn claim_conversion_track(
+        &self,
+        id: &Uuid,
+        stale_before: OffsetDateTime,
+    ) -> SpotitubeResult<bool> {
+        let result = sqlx::query!(
+            r#"UPDATE conversion_tracks SET status = $2::varchar, updated_at = current_timestamp
+                WHERE id = $1 AND (status = $3 OR (status = $2 AND updated_at < $4))"#,
+            id,
+            TrackStatus::Resolving.as_str(),
+            TrackStatus::NeedsReview.as_str(),
+            stale_before
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn transition_conversion_track(
+        &self,
+        id: &Uuid,
+        from: TrackStatus,
+        to: TrackStatus,
+    ) -> SpotitubeResult<bool> {
+        let result = sqlx::query!(
+            r#"UPDATE conversion_tracks SET status = $3::varchar, updated_at = current_timestamp WHERE id = $1 AND status = $2"#,
+            id,
+            from.as_str(),
+            to.as_str()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn count_matched_tracks_before(
+        &self,
+        conversion_id: &Uuid,
+        position: i32,
+    ) -> SpotitubeResult<i64> {
+        let count = sqlx::query_scalar!(
+            r#"SELECT count(*) as "count!" FROM conversion_tracks WHERE conversion_id = $1 AND position < $2 AND status = $3"#,
+            conversion_id,
+            position,
+            TrackStatus::Matched.as_str()
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(count)
+    }
+
+    async fn create_track_candidate(
+        &self,
+        conversion_track_id: &Uuid,
+        candidate: &NewTrackCandidate,
+    ) -> SpotitubeResult<()> {
+        sqlx::query!(
+            r#"INSERT INTO conversion_track_candidates (conversion_track_id, rank, destination_id, title, confidence, match_reasons)
+            values ($1, $2, $3::varchar, $4::varchar, $5, $6::varchar[])"#,
+            conversion_track_id,
+            candidate.rank,
+            candidate.destination_id,
+            candidate.title,
+            candidate.confidence,
+            &candidate.match_reasons
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_track_candidates(
+        &self,
+        conversion_id: &Uuid,
+    ) -> SpotitubeResult<Vec<TrackCandidateEntity>> {
+        let candidates = sqlx::query_as!(
+            TrackCandidateEntity,
+            r#"SELECT candidates.* FROM conversion_track_candidates candidates
+            JOIN conversion_tracks tracks ON tracks.id = candidates.conversion_track_id
+            WHERE tracks.conversion_id = $1
+            ORDER BY tracks.position, candidates.rank"#,
+            conversion_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(candidates)
+    }
+
+    async fn get_track_candidate(
+        &self,
+        conversion_track_id: &Uuid,
+        id: &Uuid,
+    ) -> SpotitubeResult<Option<TrackCandidateEntity>> {
+        let candidate = sqlx::query_as!(
+            TrackCandidateEntity,
+            r#"SELECT * FROM conversion_track_candidates WHERE conversion_track_id = $1 AND id = $2"#,
+            conversion_track_id,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(candidate)
+    }
+}
@@ -0,0 +1,203 @@
+use async_trait::async_trait;
+use spotitube_core::{
+    errors::SpotitubeResult,
+    jobs::repository::{JobEntity, JobRepository, JobStatus},
+};
+use sqlx::types::time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::connection_pool::SpotitubeConnectionPool;
+
+#[derive(Clone)]
+pub struct PostgresJobRepository {
+    pool: SpotitubeConnectionPool,
+}
+
+impl PostgresJobRepository {
+    pub fn new(pool: SpotitubeConnectionPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl JobRepository for PostgresJobRepository {
+    async fn enqueue_job(
+        &self,
+        kind: &str,
+        payload: &serde_json::Value,
+        max_attempts: i32,
+    ) -> SpotitubeResult<JobEntity> {
+        let job = sqlx::query_as!(
+            JobEntity,
+            r#"INSERT INTO jobs (kind, payload, max_attempts) values ($1::varchar, $2, $3) returning *"#,
+            kind,
+            payload,
+            max_attempts
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn claim_job(
+        &self,
+        kinds: &[String],
+        worker_id: &str,
+        locked_until: OffsetDateTime,
+    ) -> SpotitubeResult<Option<JobEntity>> {
+        let job = sqlx::query_as!(
+            JobEntity,
+            r#"UPDATE jobs SET
+                status = $2::varchar,
+                attempts = attempts + 1,
+                locked_by = $3::varchar,
+                locked_until = $4,
+                updated_at = current_timestamp
+            WHERE id = (
+                SELECT id FROM jobs
+                WHERE kind = ANY($1)
+                    AND ((status = $5 AND run_at <= current_timestamp)
+                        OR (status = $2 AND locked_until < current_timestamp))
+                ORDER BY run_at
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            returning *"#,
+            kinds,
+            JobStatus::Running.as_str(),
+            worker_id,
+            locked_until,
+            JobStatus::Pending.as_str()
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(job)
+    }
+
+    async fn extend_job_lock(
+        &self,
+        id: &Uuid,
+        worker_id: &str,
+        locked_until: OffsetDateTime,
+    ) -> SpotitubeResult<bool> {
+        let result = sqlx::query!(
+            r#"UPDATE jobs SET locked_until = $3, updated_at = current_timestamp WHERE id = $1 AND locked_by = $2 AND status = $4"#,
+            id,
+            worker_id,
+            locked_until,
+            JobStatus::Running.as_str()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn complete_job(&self, id: &Uuid, worker_id: &str) -> SpotitubeResult<bool> {
+        let result = sqlx::query!(
+            r#"UPDATE jobs SET
+                status = $3::varchar,
+                locked_by = NULL,
+                locked_until = NULL,
+                completed_at = current_timestamp,
+                updated_at = current_timestamp
+            WHERE id = $1 AND locked_by = $2 AND status = $4"#,
+            id,
+            worker_id,
+            JobStatus::Completed.as_str(),
+            JobStatus::Running.as_str()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn retry_job(
+        &self,
+        id: &Uuid,
+        worker_id: &str,
+        run_at: OffsetDateTime,
+        error: &str,
+    ) -> SpotitubeResult<bool> {
+        let result = sqlx::query!(
+            r#"UPDATE jobs SET
+                status = $5::varchar,
+                run_at = $3,
+                last_error = $4::varchar,
+                locked_by = NULL,
+                locked_until = NULL,
+                updated_at = current_timestamp
+            WHERE id = $1 AND locked_by = $2 AND status = $6"#,
+            id,
+            worker_id,
+            run_at,
+            error,
+            JobStatus::Pending.as_str(),
+            JobStatus::Running.as_str()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn defer_job(
+        &self,
+        id: &Uuid,
+        worker_id: &str,
+        run_at: OffsetDateTime,
+        error: &str,
+    ) -> SpotitubeResult<bool> {
+        let result = sqlx::query!(
+            r#"UPDATE jobs SET
+                status = $5::varchar,
+                attempts = attempts - 1,
+                run_at = $3,
+                last_error = $4::varchar,
+                locked_by = NULL,
+                locked_until = NULL,
+                updated_at = current_timestamp
+            WHERE id = $1 AND locked_by = $2 AND status = $6"#,
+            id,
+            worker_id,
+            run_at,
+            error,
+            JobStatus::Pending.as_str(),
+            JobStatus::Running.as_str()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn dead_letter_job(
+        &self,
+        id: &Uuid,
+        worker_id: &str,
+        error: &str,
+    ) -> SpotitubeResult<bool> {
+        let result = sqlx::query!(
+            r#"UPDATE jobs SET
+                status = $4::varchar,
+                last_error = $3::varchar,
+                locked_by = NULL,
+                locked_until = NULL,
+                completed_at = current_timestamp,
+                updated_at = current_timestamp
+            WHERE id = $1 AND locked_by = $2 AND status = $5"#,
+            id,
+            worker_id,
+            error,
+            JobStatus::Dead.as_str(),
+            JobStatus::Running.as_str()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
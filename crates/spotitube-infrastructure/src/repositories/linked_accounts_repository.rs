@@ -0,0 +1,224 @@
+use async_trait::async_trait;
+use spotitube_core::{
+    accounts::repository::{LinkedAccountEntity, LinkedAccountsRepository, NewLinkedAccount},
+    errors::SpotitubeResult,
+};
+use sqlx::types::time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::connection_pool::SpotitubeConnectionPool;
+
+#[derive(Clone)]
+pub struct PostgresLinkedAccountsRepository {
+    pool: SpotitubeConnectionPool,
+}
+
+impl PostgresLinkedAccountsRepository {
+    pub fn new(pool: SpotitubeConnectionPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl LinkedAccountsRepository for PostgresLinkedAccountsRepository {
+    async fn upsert_linked_account(
+        &self,
+        account: NewLinkedAccount<'_>,
+    ) -> SpotitubeResult<LinkedAccountEntity> {
+        let linked_account = sqlx::query_as!(
+            LinkedAccountEntity,
+            r#"INSERT INTO linked_accounts (user_id, provider, external_user_id, display_name, access_token, refresh_token, scopes, expires_at)
+            values ($1, $2::varchar, $3::varchar, $4::varchar, $5::varchar, $6::varchar, $7::varchar, $8)
+            ON CONFLICT (user_id, provider) DO UPDATE SET
+                external_user_id = excluded.external_user_id,
+                display_name = excluded.display_name,
+                access_token = excluded.access_token,
+                refresh_token = COALESCE(excluded.refresh_token, linked_accounts.refresh_token),
+                scopes = excluded.scopes,
+                expires_at = excluded.expires_at,
+                needs_relink = false,
+                refresh_locked_until = NULL,
+                updated_at = current_timestamp
+            returning *"#,
+            account.user_id,
+            account.provider,
+            account.external_user_id,
+            account.display_name,
+            account.access_token,
+            account.refresh_token,
+            account.scopes,
+            account.expires_at
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(linked_account)
+    }
+
+    async fn get_linked_accounts(
+        &self,
+        user_id: &Uuid,
+    ) -> SpotitubeResult<Vec<LinkedAccountEntity>> {
+        let linked_accounts = sqlx::query_as!(
+            LinkedAccountEntity,
+            r#"SELECT * FROM linked_accounts WHERE user_id = $1 ORDER BY created_at"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(linked_accounts)
+    }
+
+    async fn get_linked_account(
+        &self,
+        user_id: &Uuid,
+        provider: &str,
+    ) -> SpotitubeResult<Option<LinkedAccountEntity>> {
+        let linked_account = sqlx::query_as!(
+            LinkedAccountEntity,
+            r#"SELECT * FROM linked_accounts WHERE user_id = $1 AND provider = $2::varchar"#,
+            user_id,
+            provider
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(linked_account)
+    }
+
+    async fn get_linked_account_by_id(
+        &self,
+        id: &Uuid,
+    ) -> SpotitubeResult<Option<LinkedAccountEntity>> {
+        let linked_account = sqlx::query_as!(
+            LinkedAccountEntity,
+            r#"SELECT * FROM linked_accounts WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(linked_account)
+    }
+
+    async fn acquire_refresh_lease(
+        &self,
+        id: &Uuid,
+        locked_until: OffsetDateTime,
+    ) -> SpotitubeResult<Option<LinkedAccountEntity>> {
+        let linked_account = sqlx::query_as!(
+            LinkedAccountEntity,
+            r#"UPDATE linked_accounts SET refresh_locked_until = $2
+            WHERE id = $1 AND (refresh_locked_until IS NULL OR refresh_locked_until <= current_timestamp)
+            returning *"#,
+            id,
+            locked_until
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(linked_account)
+    }
+
+    async fn release_refresh_lease(&self, id: &Uuid) -> SpotitubeResult<()> {
+        sqlx::query!(
+            r#"UPDATE linked_accounts SET refresh_locked_until = NULL WHERE id = $1"#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_refreshed_tokens(
+        &self,
+        id: &Uuid,
+        access_token: &str,
+        refresh_token: Option<&str>,
+        expires_at: OffsetDateTime,
+    ) -> SpotitubeResult<LinkedAccountEntity> {
+        let linked_account = sqlx::query_as!(
+            LinkedAccountEntity,
+            r#"UPDATE linked_accounts SET
+                access_token = $2::varchar,
+                refresh_token = COALESCE($3::varchar, refresh_token),
+                expires_at = $4,
+                needs_relink = false,
+                refresh_locked_until = NULL,
+                updated_at = current_timestamp
+            WHERE id = $1
+            returning *"#,
+            id,
+            access_token,
+            refresh_token,
+            expires_at
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(linked_account)
+    }
+
+    async fn mark_needs_relink(&self, id: &Uuid) -> SpotitubeResult<()> {
+        sqlx::query!(
+            r#"UPDATE linked_accounts SET needs_relink = true, refresh_locked_until = NULL, updated_at = current_timestamp WHERE id = $1"#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_linked_accounts_after(
+        &self,
+        after: Option<Uuid>,
+        limit: i64,
+    ) -> SpotitubeResult<Vec<LinkedAccountEntity>> {
+        let linked_accounts = sqlx::query_as!(
+            LinkedAccountEntity,
+            r#"SELECT * FROM linked_accounts WHERE $1::uuid IS NULL OR id > $1 ORDER BY id LIMIT $2"#,
+            after,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(linked_accounts)
+    }
+
+    async fn replace_linked_account_tokens(
+        &self,
+        id: &Uuid,
+        previous_access_token: &str,
+        access_token: &str,
+        refresh_token: Option<&str>,
+    ) -> SpotitubeResult<bool> {
+        let result = sqlx::query!(
+            r#"UPDATE linked_accounts SET access_token = $3::varchar, refresh_token = $4::varchar, updated_at = current_timestamp
+            WHERE id = $1 AND access_token = $2::varchar"#,
+            id,
+            previous_access_token,
+            access_token,
+            refresh_token
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_linked_account(&self, user_id: &Uuid, provider: &str) -> SpotitubeResult<bool> {
+        let result = sqlx::query!(
+            r#"DELETE FROM linked_accounts WHERE user_id = $1 AND provider = $2::varchar"#,
+            user_id,
+            provider
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
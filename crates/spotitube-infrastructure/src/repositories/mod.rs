@@ -1 +1,10 @@
+pub mod conversions_repository;
+pub mod job_repository;
+pub mod linked_accounts_repository;
+pub mod oauth_authorizations_repository;
+pub mod refresh_tokens_repository;
+pub mod snapshots_repository;
+pub mod syncs_repository;
+pub mod track_matches_repository;
 pub mod users_repository;
+pub mod youtube_quota_repository;
@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use spotitube_core::{
+    errors::SpotitubeResult,
+    oauth::repository::{OAuthAuthorizationEntity, OAuthAuthorizationsRepository},
+};
+use sqlx::types::time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::connection_pool::SpotitubeConnectionPool;
+
+#[derive(Clone)]
+pub struct PostgresOAuthAuthorizationsRepository {
+    pool: SpotitubeConnectionPool,
+}
+
+impl PostgresOAuthAuthorizationsRepository {
+    pub fn new(pool: SpotitubeConnectionPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl OAuthAuthorizationsRepository for PostgresOAuthAuthorizationsRepository {
+    async fn create_authorization(
+        &self,
+        state: &str,
+        user_id: &Uuid,
+        provider: &str,
+        code_verifier: &str,
+        expires_at: OffsetDateTime,
+    ) -> SpotitubeResult<OAuthAuthorizationEntity> {
+        let authorization = sqlx::query_as!(
+            OAuthAuthorizationEntity,
+            r#"INSERT INTO oauth_authorizations (state, user_id, provider, code_verifier, expires_at) values ($1::varchar, $2, $3::varchar, $4::varchar, $5) returning *"#,
+            state,
+            user_id,
+            provider,
+            code_verifier,
+            expires_at
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(authorization)
+    }
+
+    async fn take_authorization(
+        &self,
+        state: &str,
+    ) -> SpotitubeResult<Option<OAuthAuthorizationEntity>> {
+        let authorization = sqlx::query_as!(
+            OAuthAuthorizationEntity,
+            r#"DELETE FROM oauth_authorizations WHERE state = $1::varchar returning *"#,
+            state
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(authorization)
+    }
+}
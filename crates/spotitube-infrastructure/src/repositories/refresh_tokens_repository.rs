@@ -0,0 +1,79 @@
+use async_trait::async_trait;
+use spotitube_core::{
+    auth::repository::{RefreshTokenEntity, RefreshTokensRepository},
+    errors::SpotitubeResult,
+};
+use sqlx::types::time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::connection_pool::SpotitubeConnectionPool;
+
+#[derive(Clone)]
+pub struct PostgresRefreshTokensRepository {
+    pool: SpotitubeConnectionPool,
+}
+
+impl PostgresRefreshTokensRepository {
+    pub fn new(pool: SpotitubeConnectionPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RefreshTokensRepository for PostgresRefreshTokensRepository {
+    async fn create_refresh_token(
+        &self,
+        user_id: &Uuid,
+        token_hash: &str,
+        expires_at: OffsetDateTime,
+    ) -> SpotitubeResult<RefreshTokenEntity> {
+        let refresh_token = sqlx::query_as!(
+            RefreshTokenEntity,
+            r#"INSERT INTO refresh_tokens (user_id, token_hash, expires_at) values ($1, $2::varchar, $3) returning *"#,
+            user_id,
+            token_hash,
+            expires_at
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(refresh_token)
+    }
+
+    async fn get_refresh_token_by_hash(
+        &self,
+        token_hash: &str,
+    ) -> SpotitubeResult<Option<RefreshTokenEntity>> {
+        let refresh_token = sqlx::query_as!(
+            RefreshTokenEntity,
+            r#"SELECT * FROM refresh_tokens WHERE token_hash = $1::varchar"#,
+            token_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(refresh_token)
+    }
+
+    async fn revoke_refresh_token(&self, id: &Uuid) -> SpotitubeResult<bool> {
+        let result = sqlx::query!(
+            r#"UPDATE refresh_tokens SET revoked_at = current_timestamp WHERE id = $1 AND revoked_at IS NULL"#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn revoke_user_refresh_tokens(&self, user_id: &Uuid) -> SpotitubeResult<()> {
+        sqlx::query!(
+            r#"UPDATE refresh_tokens SET revoked_at = current_timestamp WHERE user_id = $1 AND revoked_at IS NULL"#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,161 @@
+use async_trait::async_trait;
+use spotitube_core::{
+    errors::SpotitubeResult,
+    snapshots::repository::{
+        NewPlaylistSnapshot, NewSnapshotTrack, PlaylistSnapshotEntity, SnapshotTrackEntity,
+        SnapshotsRepository,
+    },
+};
+use uuid::Uuid;
+
+use crate::connection_pool::SpotitubeConnectionPool;
+
+#[derive(Clone)]
+pub struct PostgresSnapshotsRepository {
+    pool: SpotitubeConnectionPool,
+}
+
+impl PostgresSnapshotsRepository {
+    pub fn new(pool: SpotitubeConnectionPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SnapshotsRepository for PostgresSnapshotsRepository {
+    async fn create_snapshot(
+        &self,
+        snapshot: &NewPlaylistSnapshot,
+        tracks: &[NewSnapshotTrack],
+    ) -> SpotitubeResult<PlaylistSnapshotEntity> {
+        let positions: Vec<i32> = (0..tracks.len() as i32).collect();
+        let provider_ids: Vec<Option<String>> = tracks
+            .iter()
+            .map(|track| track.provider_id.clone())
+            .collect();
+        let titles: Vec<String> = tracks.iter().map(|track| track.title.clone()).collect();
+        // Artists go as JSON since Postgres arrays of arrays must all have the same length.
+        let artists: Vec<serde_json::Value> = tracks
+            .iter()
+            .map(|track| serde_json::json!(track.artists))
+            .collect();
+        let albums: Vec<Option<String>> = tracks.iter().map(|track| track.album.clone()).collect();
+        let durations: Vec<Option<i64>> = tracks.iter().map(|track| track.duration_ms).collect();
+        let isrcs: Vec<Option<String>> = tracks.iter().map(|track| track.isrc.clone()).collect();
+
+        let mut transaction = self.pool.begin().await?;
+        // Snapshots of the same playlist taken concurrently would otherwise both get the next
+        // version and one of them would fail on the unique constraint.
+        sqlx::query!(
+            r#"SELECT pg_advisory_xact_lock(hashtextextended($1::uuid || '/' || $2::text || '/' || $3::text, 0))"#,
+            snapshot.user_id,
+            snapshot.provider,
+            snapshot.playlist_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+        let created = sqlx::query_as!(
+            PlaylistSnapshotEntity,
+            r#"INSERT INTO playlist_snapshots (user_id, provider, playlist_id, version, origin, name, description, source_version, track_count)
+            SELECT $1, $2::varchar, $3::varchar, coalesce(max(version), 0) + 1, $4::varchar, $5::varchar, $6::varchar, $7::varchar, $8
+            FROM playlist_snapshots WHERE user_id = $1 AND provider = $2 AND playlist_id = $3
+            returning *"#,
+            snapshot.user_id,
+            snapshot.provider,
+            snapshot.playlist_id,
+            snapshot.origin,
+            snapshot.name,
+            snapshot.description,
+            snapshot.source_version,
+            tracks.len() as i32
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+        sqlx::query!(
+            r#"INSERT INTO playlist_snapshot_tracks (snapshot_id, position, provider_id, title, artists, album, duration_ms, isrc)
+            SELECT $1, track.position, track.provider_id, track.title, ARRAY(SELECT jsonb_array_elements_text(track.artists)), track.album, track.duration_ms, track.isrc
+            FROM UNNEST($2::int[], $3::varchar[], $4::varchar[], $5::jsonb[], $6::varchar[], $7::bigint[], $8::varchar[])
+                AS track(position, provider_id, title, artists, album, duration_ms, isrc)"#,
+            created.id,
+            &positions,
+            &provider_ids as &[Option<String>],
+            &titles,
+            &artists,
+            &albums as &[Option<String>],
+            &durations as &[Option<i64>],
+            &isrcs as &[Option<String>]
+        )
+        .execute(&mut *transaction)
+        .await?;
+        transaction.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn get_snapshot(&self, id: &Uuid) -> SpotitubeResult<Option<PlaylistSnapshotEntity>> {
+        let snapshot = sqlx::query_as!(
+            PlaylistSnapshotEntity,
+            r#"SELECT * FROM playlist_snapshots WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(snapshot)
+    }
+
+    async fn get_latest_snapshot(
+        &self,
+        user_id: &Uuid,
+        provider: &str,
+        playlist_id: &str,
+    ) -> SpotitubeResult<Option<PlaylistSnapshotEntity>> {
+        let snapshot = sqlx::query_as!(
+            PlaylistSnapshotEntity,
+            r#"SELECT * FROM playlist_snapshots WHERE user_id = $1 AND provider = $2 AND playlist_id = $3
+            ORDER BY version DESC LIMIT 1"#,
+            user_id,
+            provider,
+            playlist_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(snapshot)
+    }
+
+    async fn get_playlist_snapshots(
+        &self,
+        user_id: &Uuid,
+        provider: &str,
+        playlist_id: &str,
+    ) -> SpotitubeResult<Vec<PlaylistSnapshotEntity>> {
+        let snapshots = sqlx::query_as!(
+            PlaylistSnapshotEntity,
+            r#"SELECT * FROM playlist_snapshots WHERE user_id = $1 AND provider = $2 AND playlist_id = $3
+            ORDER BY version DESC"#,
+            user_id,
+            provider,
+            playlist_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(snapshots)
+    }
+
+    async fn get_snapshot_tracks(
+        &self,
+        snapshot_id: &Uuid,
+    ) -> SpotitubeResult<Vec<SnapshotTrackEntity>> {
+        let tracks = sqlx::query_as!(
+            SnapshotTrackEntity,
+            r#"SELECT * FROM playlist_snapshot_tracks WHERE snapshot_id = $1 ORDER BY position"#,
+            snapshot_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tracks)
+    }
+}
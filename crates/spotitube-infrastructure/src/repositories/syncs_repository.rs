@@ -0,0 +1,259 @@
+use async_trait::async_trait;
+use spotitube_core::{
+    errors::{SpotitubeError, SpotitubeResult},
+    syncs::{
+        repository::{
+            NewSyncConflict, NewSyncEntry, PlaylistSyncEntity, SyncConflictEntity, SyncEntryEntity,
+            SyncsRepository,
+        },
+        runner::{SyncJob, SYNC_JOB},
+    },
+};
+use sqlx::types::time::OffsetDateTime;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::connection_pool::SpotitubeConnectionPool;
+
+#[derive(Clone)]
+pub struct PostgresSyncsRepository {
+    pool: SpotitubeConnectionPool,
+}
+
+impl PostgresSyncsRepository {
+    pub fn new(pool: SpotitubeConnectionPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SyncsRepository for PostgresSyncsRepository {
+    async fn create_sync(
+        &self,
+        user_id: &Uuid,
+        spotify_playlist_id: &str,
+        youtube_playlist_id: &str,
+        interval_seconds: i64,
+    ) -> SpotitubeResult<PlaylistSyncEntity> {
+        let sync = sqlx::query_as!(
+            PlaylistSyncEntity,
+            r#"INSERT INTO playlist_syncs (user_id, spotify_playlist_id, youtube_playlist_id, interval_seconds)
+            values ($1, $2::varchar, $3::varchar, $4) returning *"#,
+            user_id,
+            spotify_playlist_id,
+            youtube_playlist_id,
+            interval_seconds
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(sync)
+    }
+
+    async fn get_sync(&self, id: &Uuid) -> SpotitubeResult<Option<PlaylistSyncEntity>> {
+        let sync = sqlx::query_as!(
+            PlaylistSyncEntity,
+            r#"SELECT * FROM playlist_syncs WHERE id = $1"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(sync)
+    }
+
+    async fn get_user_syncs(&self, user_id: &Uuid) -> SpotitubeResult<Vec<PlaylistSyncEntity>> {
+        let syncs = sqlx::query_as!(
+            PlaylistSyncEntity,
+            r#"SELECT * FROM playlist_syncs WHERE user_id = $1 ORDER BY created_at"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(syncs)
+    }
+
+    async fn delete_sync(&self, id: &Uuid) -> SpotitubeResult<()> {
+        sqlx::query!(r#"DELETE FROM playlist_syncs WHERE id = $1"#, id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn schedule_sync(&self, id: &Uuid, next_sync_at: OffsetDateTime) -> SpotitubeResult<()> {
+        sqlx::query!(
+            r#"UPDATE playlist_syncs SET next_sync_at = $2, updated_at = current_timestamp WHERE id = $1"#,
+            id,
+            next_sync_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn claim_due_syncs(
+        &self,
+        limit: i64,
+        stale_before: OffsetDateTime,
+        max_attempts: i32,
+    ) -> SpotitubeResult<Vec<PlaylistSyncEntity>> {
+        let mut transaction = self.pool.begin().await?;
+        let syncs = sqlx::query_as!(
+            PlaylistSyncEntity,
+            r#"UPDATE playlist_syncs SET
+                next_sync_at = current_timestamp + interval_seconds * interval '1 second',
+                updated_at = current_timestamp
+            WHERE id IN (
+                SELECT id FROM playlist_syncs
+                WHERE next_sync_at <= current_timestamp
+                    AND (syncing_since IS NULL OR syncing_since < $2)
+                ORDER BY next_sync_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            returning *"#,
+            limit,
+            stale_before
+        )
+        .fetch_all(&mut *transaction)
+        .await?;
+
+        let payloads = syncs
+            .iter()
+            .map(|sync| serde_json::to_value(SyncJob { sync_id: sync.id }))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| {
+                error!("failed to serialize sync job: {:?}", err);
+                SpotitubeError::InternalServerError
+            })?;
+        sqlx::query!(
+            r#"INSERT INTO jobs (kind, payload, max_attempts)
+            SELECT $1::varchar, payload, $3 FROM UNNEST($2::jsonb[]) AS payload"#,
+            SYNC_JOB,
+            &payloads,
+            max_attempts
+        )
+        .execute(&mut *transaction)
+        .await?;
+        transaction.commit().await?;
+
+        Ok(syncs)
+    }
+
+    async fn start_sync(&self, id: &Uuid, stale_before: OffsetDateTime) -> SpotitubeResult<bool> {
+        let result = sqlx::query!(
+            r#"UPDATE playlist_syncs SET syncing_since = current_timestamp, updated_at = current_timestamp
+            WHERE id = $1 AND (syncing_since IS NULL OR syncing_since < $2)"#,
+            id,
+            stale_before
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn finish_sync(&self, id: &Uuid, error: Option<&str>) -> SpotitubeResult<()> {
+        sqlx::query!(
+            r#"UPDATE playlist_syncs SET
+                syncing_since = NULL,
+                error = $2::varchar,
+                last_synced_at = CASE WHEN $2::varchar IS NULL THEN current_timestamp ELSE last_synced_at END,
+                updated_at = current_timestamp
+            WHERE id = $1"#,
+            id,
+            error
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_sync_entries(&self, sync_id: &Uuid) -> SpotitubeResult<Vec<SyncEntryEntity>> {
+        let entries = sqlx::query_as!(
+            SyncEntryEntity,
+            r#"SELECT * FROM playlist_sync_entries WHERE sync_id = $1 ORDER BY position"#,
+            sync_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    async fn replace_sync_entries(
+        &self,
+        sync_id: &Uuid,
+        entries: &[NewSyncEntry],
+    ) -> SpotitubeResult<()> {
+        let positions: Vec<i32> = (0..entries.len() as i32).collect();
+        let spotify_track_ids: Vec<Option<String>> = entries
+            .iter()
+            .map(|entry| entry.spotify_track_id.clone())
+            .collect();
+        let youtube_video_ids: Vec<Option<String>> = entries
+            .iter()
+            .map(|entry| entry.youtube_video_id.clone())
+            .collect();
+        let titles: Vec<String> = entries.iter().map(|entry| entry.title.clone()).collect();
+
+        let mut transaction = self.pool.begin().await?;
+        sqlx::query!(
+            r#"DELETE FROM playlist_sync_entries WHERE sync_id = $1"#,
+            sync_id
+        )
+        .execute(&mut *transaction)
+        .await?;
+        sqlx::query!(
+            r#"INSERT INTO playlist_sync_entries (sync_id, position, spotify_track_id, youtube_video_id, title)
+            SELECT $1, * FROM UNNEST($2::int[], $3::varchar[], $4::varchar[], $5::varchar[])"#,
+            sync_id,
+            &positions,
+            &spotify_track_ids as &[Option<String>],
+            &youtube_video_ids as &[Option<String>],
+            &titles
+        )
+        .execute(&mut *transaction)
+        .await?;
+        transaction.commit().await?;
+
+        Ok(())
+    }
+
+    async fn create_sync_conflict(
+        &self,
+        sync_id: &Uuid,
+        conflict: &NewSyncConflict,
+    ) -> SpotitubeResult<()> {
+        sqlx::query!(
+            r#"INSERT INTO playlist_sync_conflicts (sync_id, kind, position, base_titles, spotify_titles, youtube_titles)
+            values ($1, $2::varchar, $3, $4, $5, $6)"#,
+            sync_id,
+            conflict.kind.as_str(),
+            conflict.position,
+            &conflict.base_titles,
+            &conflict.spotify_titles,
+            &conflict.youtube_titles
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_sync_conflicts(&self, sync_id: &Uuid) -> SpotitubeResult<Vec<SyncConflictEntity>> {
+        let conflicts = sqlx::query_as!(
+            SyncConflictEntity,
+            r#"SELECT * FROM playlist_sync_conflicts WHERE sync_id = $1 ORDER BY created_at DESC, position"#,
+            sync_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(conflicts)
+    }
+}
@@ -0,0 +1,119 @@
+use async_trait::async_trait;
+use spotitube_core::{
+    errors::SpotitubeResult,
+    matching::repository::{NewTrackMatch, TrackMatchEntity, TrackMatchesRepository},
+};
+
+use uuid::Uuid;
+
+use crate::connection_pool::SpotitubeConnectionPool;
+
+#[derive(Clone)]
+pub struct PostgresTrackMatchesRepository {
+    pool: SpotitubeConnectionPool,
+}
+
+impl PostgresTrackMatchesRepository {
+    pub fn new(pool: SpotitubeConnectionPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TrackMatchesRepository for PostgresTrackMatchesRepository {
+    async fn find_track_match(
+        &self,
+        user_id: &Uuid,
+        confirmations: i64,
+        source_provider: &str,
+        source_id: &str,
+        isrc: Option<&str>,
+        destination_provider: &str,
+    ) -> SpotitubeResult<Option<TrackMatchEntity>> {
+        let track_match = sqlx::query_as!(
+            TrackMatchEntity,
+            r#"SELECT matches.* FROM track_matches matches,
+                LATERAL (
+                    SELECT count(DISTINCT agreeing.confirmed_by) AS confirmations FROM track_matches agreeing
+                    WHERE agreeing.destination_provider = matches.destination_provider
+                        AND agreeing.destination_id = matches.destination_id
+                        AND ((agreeing.source_provider = $1 AND agreeing.source_id = $2) OR agreeing.isrc = $3)
+                        AND (agreeing.expires_at IS NULL OR agreeing.expires_at > current_timestamp)
+                ) agreement
+            WHERE matches.destination_provider = $4
+                AND ((matches.source_provider = $1 AND matches.source_id = $2) OR matches.isrc = $3)
+                AND (matches.expires_at IS NULL OR matches.expires_at > current_timestamp)
+                AND (matches.confirmed_by IS NULL OR matches.confirmed_by = $5 OR agreement.confirmations >= $6)
+            ORDER BY coalesce(matches.confirmed_by = $5, false) DESC,
+                matches.confirmed_by IS NULL,
+                agreement.confirmations DESC,
+                (matches.source_provider = $1 AND matches.source_id = $2) DESC,
+                matches.updated_at DESC
+            LIMIT 1"#,
+            source_provider,
+            source_id,
+            isrc,
+            destination_provider,
+            user_id,
+            confirmations
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(track_match)
+    }
+
+    async fn upsert_track_match(&self, track_match: NewTrackMatch<'_>) -> SpotitubeResult<()> {
+        match track_match.confirmed_by {
+            Some(confirmed_by) => {
+                sqlx::query!(
+                    r#"INSERT INTO track_matches (source_provider, source_id, isrc, destination_provider, destination_id, destination_title, confidence, confirmed_by, expires_at)
+                    values ($1::varchar, $2::varchar, $3::varchar, $4::varchar, $5::varchar, $6::varchar, $7, $8, $9)
+                    ON CONFLICT (source_provider, source_id, destination_provider, confirmed_by) WHERE confirmed_by IS NOT NULL DO UPDATE SET
+                        isrc = COALESCE(EXCLUDED.isrc, track_matches.isrc),
+                        destination_id = EXCLUDED.destination_id,
+                        destination_title = EXCLUDED.destination_title,
+                        confidence = EXCLUDED.confidence,
+                        expires_at = EXCLUDED.expires_at,
+                        updated_at = current_timestamp"#,
+                    track_match.source_provider,
+                    track_match.source_id,
+                    track_match.isrc,
+                    track_match.destination_provider,
+                    track_match.destination_id,
+                    track_match.destination_title,
+                    track_match.confidence,
+                    confirmed_by,
+                    track_match.expires_at
+                )
+                .execute(&self.pool)
+                .await?;
+            }
+            None => {
+                sqlx::query!(
+                    r#"INSERT INTO track_matches (source_provider, source_id, isrc, destination_provider, destination_id, destination_title, confidence, expires_at)
+                    values ($1::varchar, $2::varchar, $3::varchar, $4::varchar, $5::varchar, $6::varchar, $7, $8)
+                    ON CONFLICT (source_provider, source_id, destination_provider) WHERE confirmed_by IS NULL DO UPDATE SET
+                        isrc = COALESCE(EXCLUDED.isrc, track_matches.isrc),
+                        destination_id = EXCLUDED.destination_id,
+                        destination_title = EXCLUDED.destination_title,
+                        confidence = EXCLUDED.confidence,
+                        expires_at = EXCLUDED.expires_at,
+                        updated_at = current_timestamp"#,
+                    track_match.source_provider,
+                    track_match.source_id,
+                    track_match.isrc,
+                    track_match.destination_provider,
+                    track_match.destination_id,
+                    track_match.destination_title,
+                    track_match.confidence,
+                    track_match.expires_at
+                )
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -56,4 +56,34 @@ impl UsersRepository for PostgresUsersRepository {
 
         Ok(user)
     }
+
+    async fn update_user(
+        &self,
+        user_id: &Uuid,
+        username: &str,
+        hashed_password: &str,
+        revoke_refresh_tokens: bool,
+    ) -> SpotitubeResult<UserEntity> {
+        let mut transaction = self.pool.begin().await?;
+        let user = sqlx::query_as!(
+            UserEntity,
+            r#"UPDATE users SET username = $1::varchar, password = $2::varchar, updated_at = current_timestamp WHERE id = $3 returning *"#,
+            username,
+            hashed_password,
+            user_id
+        )
+        .fetch_one(&mut *transaction)
+        .await?;
+        if revoke_refresh_tokens {
+            sqlx::query!(
+                r#"UPDATE refresh_tokens SET revoked_at = current_timestamp WHERE user_id = $1 AND revoked_at IS NULL"#,
+                user_id
+            )
+            .execute(&mut *transaction)
+            .await?;
+        }
+        transaction.commit().await?;
+
+        Ok(user)
+    }
 }
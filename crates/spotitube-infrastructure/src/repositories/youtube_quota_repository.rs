@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use spotitube_core::{errors::SpotitubeResult, youtube::repository::YouTubeQuotaRepository};
+use time::OffsetDateTime;
+
+use crate::connection_pool::SpotitubeConnectionPool;
+
+#[derive(Clone)]
+pub struct PostgresYouTubeQuotaRepository {
+    pool: SpotitubeConnectionPool,
+}
+
+impl PostgresYouTubeQuotaRepository {
+    pub fn new(pool: SpotitubeConnectionPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl YouTubeQuotaRepository for PostgresYouTubeQuotaRepository {
+    async fn reserve_quota(&self, cost: i64, limit: i64) -> SpotitubeResult<bool> {
+        let reserved = sqlx::query!(
+            r#"INSERT INTO youtube_quota_usage (quota_day, used)
+            SELECT (current_timestamp AT TIME ZONE 'America/Los_Angeles')::date, $1::bigint
+            WHERE $1::bigint <= $2::bigint
+            ON CONFLICT (quota_day) DO UPDATE SET
+                used = youtube_quota_usage.used + excluded.used,
+                updated_at = current_timestamp
+            WHERE youtube_quota_usage.used + excluded.used <= $2::bigint
+            returning used"#,
+            cost,
+            limit
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(reserved.is_some())
+    }
+
+    async fn get_quota_used(&self) -> SpotitubeResult<i64> {
+        let used = sqlx::query_scalar!(
+            r#"SELECT used FROM youtube_quota_usage
+            WHERE quota_day = (current_timestamp AT TIME ZONE 'America/Los_Angeles')::date"#
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(used.unwrap_or_default())
+    }
+
+    async fn get_quota_reset(&self) -> SpotitubeResult<OffsetDateTime> {
+        let reset = sqlx::query_scalar!(
+            r#"SELECT (date_trunc('day', current_timestamp AT TIME ZONE 'America/Los_Angeles') + interval '1 day')
+                AT TIME ZONE 'America/Los_Angeles' as "reset!""#
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(reset)
+    }
+}
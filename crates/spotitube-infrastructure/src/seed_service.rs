@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use rand::{distributions::Alphanumeric, rngs::OsRng, Rng};
+use spotitube_core::{
+    config::AppConfig,
+    errors::{SpotitubeError, SpotitubeResult},
+    users::repository::DynUsersRepository,
+    utils::security_service::DynSecurityService,
+};
+use tracing::{error, info};
+
+use crate::{
+    connection_pool::SpotitubeConnectionPool,
+    repositories::users_repository::PostgresUsersRepository,
+    services::utils::argon_security_service::ArgonSecurityService,
+};
+
+const DEVELOPMENT_ENV: &str = "development";
+const GENERATED_PASSWORD_LENGTH: usize = 24;
+
+pub struct SpotitubeSeedService {
+    users_repository: DynUsersRepository,
+    security_service: DynSecurityService,
+    config: Arc<AppConfig>,
+}
+
+impl SpotitubeSeedService {
+    pub fn new(pool: SpotitubeConnectionPool, config: Arc<AppConfig>) -> Self {
+        Self {
+            users_repository: Arc::new(PostgresUsersRepository::new(pool)),
+            security_service: Arc::new(ArgonSecurityService::new(config.clone())),
+            config,
+        }
+    }
+
+    /// Creates the `username` or `username:password` entries of `SEED_USERS`; users listed
+    /// without a password get a random one, printed once to stdout when the user is created.
+    pub async fn seed(&self) -> SpotitubeResult<()> {
+        if self.config.app_env != DEVELOPMENT_ENV {
+            error!(
+                "refusing to seed the database outside of development, APP_ENV is {:?}",
+                self.config.app_env
+            );
+            return Err(SpotitubeError::AppStartup);
+        }
+
+        info!("seeding database...");
+
+        for entry in &self.config.seed_users {
+            let (username, password) = match entry.split_once(':') {
+                Some((username, password)) => (username, Some(password)),
+                None => (entry.as_str(), None),
+            };
+
+            if self
+                .users_repository
+                .get_user_by_username(username)
+                .await?
+                .is_some()
+            {
+                info!("seed user {:?} already exists, skipping", username);
+                continue;
+            }
+
+            let password = password.map(String::from).unwrap_or_else(generate_password);
+            let hashed_password = self.security_service.hash_password(&password).await?;
+            self.users_repository
+                .create_user(username, &hashed_password)
+                .await?;
+            info!("created seed user {:?}", username);
+            // Printed rather than traced so the password never reaches the collected logs.
+            if !entry.contains(':') {
+                println!("seed user {} has password {}", username, password);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn generate_password() -> String {
+    OsRng
+        .sample_iter(&Alphanumeric)
+        .take(GENERATED_PASSWORD_LENGTH)
+        .map(char::from)
+        .collect()
+}
@@ -1,38 +1,261 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use spotitube_core::{
-    config::AppConfig, users::service::DynUsersService, utils::token_service::DynTokenService,
+    accounts::{service::DynAccountsService, token_manager::DynProviderTokenManager},
+    auth::service::DynAuthService,
+    config::AppConfig,
+    conversions::{
+        events::DynConversionEvents, review_service::DynConversionReviewService,
+        runner::CONVERSION_JOB, service::DynConversionsService,
+    },
+    errors::SpotitubeResult,
+    jobs::{handler::DynJobHandler, repository::DynJobRepository},
+    matching::{
+        matcher::DynTrackMatcher, resolver::DynTrackResolver, scoring_matcher::ScoringTrackMatcher,
+    },
+    oauth::{client::DynOAuthClient, service::DynOAuthService},
+    playlists::editor::DynPlaylistEditor,
+    snapshots::{
+        recorder::DynSnapshotRecorder, repository::DynSnapshotsRepository,
+        service::DynSnapshotsService,
+    },
+    spotify::client::DynSpotifyClient,
+    syncs::{
+        repository::DynSyncsRepository,
+        runner::{DynSyncRunner, SYNC_JOB},
+        service::DynSyncsService,
+    },
+    users::service::DynUsersService,
+    utils::{encryption_service::DynEncryptionService, token_service::DynTokenService},
+    youtube::{client::DynYouTubeClient, repository::DynYouTubeQuotaRepository},
 };
+use spotitube_domain::providers::Provider;
 
 use crate::{
+    clients::{spotify_client::SpotifyWebApiClient, youtube_client::YouTubeDataApiClient},
     connection_pool::SpotitubeConnectionPool,
-    repositories::users_repository::PostgresUsersRepository,
+    repositories::{
+        conversions_repository::PostgresConversionsRepository,
+        job_repository::PostgresJobRepository,
+        linked_accounts_repository::PostgresLinkedAccountsRepository,
+        oauth_authorizations_repository::PostgresOAuthAuthorizationsRepository,
+        refresh_tokens_repository::PostgresRefreshTokensRepository,
+        snapshots_repository::PostgresSnapshotsRepository,
+        syncs_repository::PostgresSyncsRepository,
+        track_matches_repository::PostgresTrackMatchesRepository,
+        users_repository::PostgresUsersRepository,
+        youtube_quota_repository::PostgresYouTubeQuotaRepository,
+    },
     services::{
+        accounts_service::SpotitubeAccountsService,
+        auth_service::SpotitubeAuthService,
+        conversion_events::InProcessConversionEvents,
+        conversion_job_handler::ConversionJobHandler,
+        conversion_review_service::SpotitubeConversionReviewService,
+        conversion_runner::SpotitubeConversionRunner,
+        conversions_service::SpotitubeConversionsService,
+        job_worker::SpotitubeJobWorker,
+        oauth_service::SpotitubeOAuthService,
+        playlist_editor::SpotitubePlaylistEditor,
+        provider_token_manager::SpotitubeProviderTokenManager,
+        snapshot_recorder::SpotitubeSnapshotRecorder,
+        snapshots_service::SpotitubeSnapshotsService,
+        sync_job_handler::SyncJobHandler,
+        sync_runner::SpotitubeSyncRunner,
+        sync_scheduler::SpotitubeSyncScheduler,
+        syncs_service::SpotitubeSyncsService,
+        track_resolver::SpotitubeTrackResolver,
         users_service::SpotitubeUsersService,
-        utils::{argon_security_service::ArgonSecurityService, jwt_service::JwtService},
+        utils::{
+            aes_encryption_service::AesEncryptionService,
+            argon_security_service::ArgonSecurityService, jwt_service::JwtService,
+        },
     },
 };
 
 pub struct ServiceRegister {
+    pub pool: SpotitubeConnectionPool,
     pub users_service: DynUsersService,
+    pub auth_service: DynAuthService,
     pub token_service: DynTokenService,
+    pub encryption_service: DynEncryptionService,
+    pub oauth_service: DynOAuthService,
+    pub accounts_service: DynAccountsService,
+    pub provider_token_manager: DynProviderTokenManager,
+    pub conversions_service: DynConversionsService,
+    pub conversion_review_service: DynConversionReviewService,
+    pub syncs_service: DynSyncsService,
+    pub snapshots_service: DynSnapshotsService,
+    pub job_worker: Arc<SpotitubeJobWorker>,
+    pub sync_scheduler: Arc<SpotitubeSyncScheduler>,
+    pub spotify_client: DynSpotifyClient,
+    pub youtube_client: DynYouTubeClient,
 }
 
 impl ServiceRegister {
-    pub fn new(pool: SpotitubeConnectionPool, config: Arc<AppConfig>) -> Self {
+    pub fn new(pool: SpotitubeConnectionPool, config: Arc<AppConfig>) -> SpotitubeResult<Self> {
         let security_service = Arc::new(ArgonSecurityService::new(config.clone()));
-        let token_service = Arc::new(JwtService::new(config));
+        let token_service = Arc::new(JwtService::new(config.clone())?);
+        let encryption_service =
+            Arc::new(AesEncryptionService::new(config.clone())?) as DynEncryptionService;
+        let spotify_client = Arc::new(SpotifyWebApiClient::new(config.clone()));
+        let youtube_quota_repository = Arc::new(PostgresYouTubeQuotaRepository::new(pool.clone()))
+            as DynYouTubeQuotaRepository;
+        let youtube_client = Arc::new(YouTubeDataApiClient::new(
+            config.clone(),
+            youtube_quota_repository.clone(),
+        ));
+
+        let users_repository = Arc::new(PostgresUsersRepository::new(pool.clone()));
+        let refresh_tokens_repository =
+            Arc::new(PostgresRefreshTokensRepository::new(pool.clone()));
+        let oauth_authorizations_repository =
+            Arc::new(PostgresOAuthAuthorizationsRepository::new(pool.clone()));
+        let linked_accounts_repository =
+            Arc::new(PostgresLinkedAccountsRepository::new(pool.clone()));
+        let conversions_repository = Arc::new(PostgresConversionsRepository::new(pool.clone()));
+        let job_repository = Arc::new(PostgresJobRepository::new(pool.clone())) as DynJobRepository;
+        let track_matches_repository = Arc::new(PostgresTrackMatchesRepository::new(pool.clone()));
+        let syncs_repository =
+            Arc::new(PostgresSyncsRepository::new(pool.clone())) as DynSyncsRepository;
+        let snapshots_repository =
+            Arc::new(PostgresSnapshotsRepository::new(pool.clone())) as DynSnapshotsRepository;
 
-        let users_repository = Arc::new(PostgresUsersRepository::new(pool));
+        let auth_service = Arc::new(SpotitubeAuthService::new(
+            refresh_tokens_repository,
+            users_repository.clone(),
+            token_service.clone(),
+            config.clone(),
+        )) as DynAuthService;
         let users_service = Arc::new(SpotitubeUsersService::new(
             users_repository,
             security_service,
             token_service.clone(),
+            auth_service.clone(),
         )) as DynUsersService;
 
-        Self {
-            users_service: users_service,
+        let oauth_clients = HashMap::from([
+            (Provider::Spotify, spotify_client.clone() as DynOAuthClient),
+            (Provider::YouTube, youtube_client.clone() as DynOAuthClient),
+        ]);
+        let oauth_service = Arc::new(SpotitubeOAuthService::new(
+            oauth_authorizations_repository,
+            oauth_clients.clone(),
+            config.clone(),
+        )) as DynOAuthService;
+        let provider_token_manager = Arc::new(SpotitubeProviderTokenManager::new(
+            linked_accounts_repository.clone(),
+            encryption_service.clone(),
+            oauth_clients,
+        )) as DynProviderTokenManager;
+        let playlist_editor = Arc::new(SpotitubePlaylistEditor::new(
+            provider_token_manager.clone(),
+            spotify_client.clone(),
+            youtube_client.clone(),
+        )) as DynPlaylistEditor;
+        let snapshot_recorder = Arc::new(SpotitubeSnapshotRecorder::new(
+            snapshots_repository.clone(),
+            playlist_editor.clone(),
+        )) as DynSnapshotRecorder;
+        let snapshots_service = Arc::new(SpotitubeSnapshotsService::new(
+            snapshots_repository,
+            snapshot_recorder.clone(),
+            playlist_editor.clone(),
+        )) as DynSnapshotsService;
+        let conversion_events =
+            Arc::new(InProcessConversionEvents::new(config.clone())) as DynConversionEvents;
+        let track_matcher = Arc::new(ScoringTrackMatcher::default()) as DynTrackMatcher;
+        let track_resolver = Arc::new(SpotitubeTrackResolver::new(
+            provider_token_manager.clone(),
+            track_matcher,
+            track_matches_repository.clone(),
+            spotify_client.clone(),
+            youtube_client.clone(),
+            config.clone(),
+        )) as DynTrackResolver;
+        let conversion_runner = Arc::new(SpotitubeConversionRunner::new(
+            conversions_repository.clone(),
+            provider_token_manager.clone(),
+            track_resolver.clone(),
+            conversion_events.clone(),
+            spotify_client.clone(),
+            youtube_client.clone(),
+            config.clone(),
+        ));
+        let conversion_review_service = Arc::new(SpotitubeConversionReviewService::new(
+            conversions_repository.clone(),
+            track_matches_repository,
+            provider_token_manager.clone(),
+            spotify_client.clone(),
+            youtube_client.clone(),
+        )) as DynConversionReviewService;
+        let conversions_service = Arc::new(SpotitubeConversionsService::new(
+            conversions_repository.clone(),
+            linked_accounts_repository.clone(),
+            conversion_events,
+            config.clone(),
+        )) as DynConversionsService;
+        let sync_runner = Arc::new(SpotitubeSyncRunner::new(
+            syncs_repository.clone(),
+            track_resolver,
+            playlist_editor,
+            snapshot_recorder.clone(),
+            config.clone(),
+        )) as DynSyncRunner;
+        let syncs_service = Arc::new(SpotitubeSyncsService::new(
+            syncs_repository.clone(),
+            linked_accounts_repository.clone(),
+            config.clone(),
+        )) as DynSyncsService;
+        let sync_scheduler = Arc::new(SpotitubeSyncScheduler::new(
+            syncs_repository,
+            config.clone(),
+        ));
+        let job_handlers = HashMap::from([
+            (
+                String::from(CONVERSION_JOB),
+                Arc::new(ConversionJobHandler::new(
+                    conversion_runner,
+                    conversions_repository,
+                    snapshot_recorder,
+                )) as DynJobHandler,
+            ),
+            (
+                String::from(SYNC_JOB),
+                Arc::new(SyncJobHandler::new(sync_runner)) as DynJobHandler,
+            ),
+        ]);
+        let job_worker = Arc::new(SpotitubeJobWorker::new(
+            job_repository,
+            job_handlers,
+            youtube_quota_repository,
+            config,
+        ));
+        let accounts_service = Arc::new(SpotitubeAccountsService::new(
+            linked_accounts_repository,
+            oauth_service.clone(),
+            encryption_service.clone(),
+            spotify_client.clone(),
+            youtube_client.clone(),
+        )) as DynAccountsService;
+
+        Ok(Self {
+            pool,
+            users_service,
+            auth_service,
             token_service,
-        }
+            encryption_service,
+            oauth_service,
+            accounts_service,
+            provider_token_manager,
+            conversions_service,
+            conversion_review_service,
+            syncs_service,
+            snapshots_service,
+            job_worker,
+            sync_scheduler,
+            spotify_client,
+            youtube_client,
+        })
     }
 }
@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+use spotitube_core::{
+    accounts::{
+        repository::{DynLinkedAccountsRepository, NewLinkedAccount},
+        service::AccountsService,
+    },
+    errors::{SpotitubeError, SpotitubeResult},
+    oauth::service::DynOAuthService,
+    spotify::client::DynSpotifyClient,
+    utils::encryption_service::DynEncryptionService,
+    youtube::client::DynYouTubeClient,
+};
+use spotitube_domain::{accounts::LinkedAccountDto, providers::Provider};
+use time::{Duration, OffsetDateTime};
+use tracing::info;
+use uuid::Uuid;
+
+pub struct SpotitubeAccountsService {
+    repository: DynLinkedAccountsRepository,
+    oauth_service: DynOAuthService,
+    encryption_service: DynEncryptionService,
+    spotify_client: DynSpotifyClient,
+    youtube_client: DynYouTubeClient,
+}
+
+impl SpotitubeAccountsService {
+    pub fn new(
+        repository: DynLinkedAccountsRepository,
+        oauth_service: DynOAuthService,
+        encryption_service: DynEncryptionService,
+        spotify_client: DynSpotifyClient,
+        youtube_client: DynYouTubeClient,
+    ) -> Self {
+        Self {
+            repository,
+            oauth_service,
+            encryption_service,
+            spotify_client,
+            youtube_client,
+        }
+    }
+
+    /// Looks up the provider-side identity that the freshly issued access token belongs to.
+    async fn external_profile(
+        &self,
+        provider: Provider,
+        access_token: &str,
+    ) -> SpotitubeResult<(String, Option<String>)> {
+        match provider {
+            Provider::Spotify => {
+                let user = self.spotify_client.get_current_user(access_token).await?;
+                Ok((user.id, user.display_name))
+            }
+            Provider::YouTube => {
+                let channel = self.youtube_client.get_my_channel(access_token).await?;
+                Ok((channel.id, Some(channel.snippet.title)))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl AccountsService for SpotitubeAccountsService {
+    async fn list_accounts(&self, user_id: &Uuid) -> SpotitubeResult<Vec<LinkedAccountDto>> {
+        self.repository
+            .get_linked_accounts(user_id)
+            .await?
+            .into_iter()
+            .map(|account| account.into_dto())
+            .collect()
+    }
+
+    async fn begin_link(&self, user_id: &Uuid, provider: Provider) -> SpotitubeResult<String> {
+        self.oauth_service
+            .begin_authorization(user_id, provider)
+            .await
+    }
+
+    async fn complete_link(&self, state: &str, code: &str) -> SpotitubeResult<LinkedAccountDto> {
+        let authorization = self
+            .oauth_service
+            .complete_authorization(state, code)
+            .await?;
+        let tokens = authorization.tokens;
+
+        let (external_user_id, display_name) = self
+            .external_profile(authorization.provider, &tokens.access_token)
+            .await?;
+
+        let access_token = self.encryption_service.encrypt(&tokens.access_token)?;
+        let refresh_token = tokens
+            .refresh_token
+            .as_deref()
+            .map(|token| self.encryption_service.encrypt(token))
+            .transpose()?;
+
+        let account = self
+            .repository
+            .upsert_linked_account(NewLinkedAccount {
+                user_id: &authorization.user_id,
+                provider: authorization.provider.as_str(),
+                external_user_id: &external_user_id,
+                display_name: display_name.as_deref(),
+                access_token: &access_token,
+                refresh_token: refresh_token.as_deref(),
+                scopes: tokens.scope.as_deref().unwrap_or_default(),
+                expires_at: OffsetDateTime::now_utc() + Duration::seconds(tokens.expires_in),
+            })
+            .await?;
+
+        info!(
+            "linked {} account {:?} to user {:?}",
+            authorization.provider, external_user_id, authorization.user_id
+        );
+        account.into_dto()
+    }
+
+    async fn unlink(&self, user_id: &Uuid, provider: Provider) -> SpotitubeResult<()> {
+        if !self
+            .repository
+            .delete_linked_account(user_id, provider.as_str())
+            .await?
+        {
+            return Err(SpotitubeError::NotFound(format!(
+                "no {} account is linked",
+                provider
+            )));
+        }
+
+        info!("unlinked {} account from user {:?}", provider, user_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use spotitube_core::{
+    auth::{repository::DynRefreshTokensRepository, service::AuthService},
+    config::AppConfig,
+    errors::{SpotitubeError, SpotitubeResult},
+    users::repository::DynUsersRepository,
+    utils::token_service::DynTokenService,
+};
+use spotitube_domain::users::UserDto;
+use time::{Duration, OffsetDateTime};
+use tracing::{error, info};
+use uuid::Uuid;
+
+pub struct SpotitubeAuthService {
+    repository: DynRefreshTokensRepository,
+    users_repository: DynUsersRepository,
+    token_service: DynTokenService,
+    config: Arc<AppConfig>,
+}
+
+impl SpotitubeAuthService {
+    pub fn new(
+        repository: DynRefreshTokensRepository,
+        users_repository: DynUsersRepository,
+        token_service: DynTokenService,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        Self {
+            repository,
+            users_repository,
+            token_service,
+            config,
+        }
+    }
+
+    fn hash_refresh_token(refresh_token: &str) -> String {
+        format!("{:x}", Sha256::digest(refresh_token.as_bytes()))
+    }
+}
+
+#[async_trait]
+impl AuthService for SpotitubeAuthService {
+    async fn issue_refresh_token(&self, user_id: &Uuid) -> SpotitubeResult<String> {
+        let refresh_token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let expires_at =
+            OffsetDateTime::now_utc() + Duration::seconds(self.config.refresh_token_lifetime);
+
+        self.repository
+            .create_refresh_token(
+                user_id,
+                &Self::hash_refresh_token(&refresh_token),
+                expires_at,
+            )
+            .await?;
+
+        Ok(refresh_token)
+    }
+
+    async fn refresh(&self, refresh_token: &str) -> SpotitubeResult<UserDto> {
+        let stored_token = self
+            .repository
+            .get_refresh_token_by_hash(&Self::hash_refresh_token(refresh_token))
+            .await?
+            .ok_or(SpotitubeError::Unauthorized)?;
+
+        if stored_token.expires_at <= OffsetDateTime::now_utc() {
+            return Err(SpotitubeError::Unauthorized);
+        }
+
+        if stored_token.revoked_at.is_some()
+            || !self
+                .repository
+                .revoke_refresh_token(&stored_token.id)
+                .await?
+        {
+            error!(
+                "revoked refresh token reused for user {:?}, revoking all sessions",
+                stored_token.user_id
+            );
+            self.repository
+                .revoke_user_refresh_tokens(&stored_token.user_id)
+                .await?;
+            return Err(SpotitubeError::Unauthorized);
+        }
+
+        let user = self
+            .users_repository
+            .get_user_by_id(&stored_token.user_id)
+            .await?;
+        let token = self.token_service.new_token(&user.id, &user.username)?;
+        let refresh_token = self.issue_refresh_token(&user.id).await?;
+
+        let mut user = user.into_dto(token);
+        user.refresh_token = Some(refresh_token);
+        Ok(user)
+    }
+
+    async fn logout(&self, refresh_token: &str) -> SpotitubeResult<()> {
+        if let Some(stored_token) = self
+            .repository
+            .get_refresh_token_by_hash(&Self::hash_refresh_token(refresh_token))
+            .await?
+        {
+            info!("revoking refresh token for user {:?}", stored_token.user_id);
+            self.repository
+                .revoke_refresh_token(&stored_token.id)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
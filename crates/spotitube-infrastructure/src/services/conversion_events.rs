@@ -0,0 +1,160 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use spotitube_core::{
+    config::AppConfig,
+    conversions::events::{ConversionEventSubscription, ConversionEvents},
+};
+use spotitube_domain::conversions::{ConversionEvent, ConversionEventDto};
+use time::OffsetDateTime;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+const CHANNEL_CAPACITY: usize = 256;
+
+struct ConversionChannel {
+    sender: broadcast::Sender<ConversionEventDto>,
+    history: VecDeque<(u64, ConversionEventDto)>,
+    next_sequence: u64,
+}
+
+impl ConversionChannel {
+    fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            sender,
+            history: VecDeque::new(),
+            next_sequence: 1,
+        }
+    }
+
+    /// Events published after `sequence`, unless some of them were dropped from the history.
+    fn events_after(&self, sequence: u64) -> Option<Vec<ConversionEventDto>> {
+        let first_retained = self
+            .history
+            .front()
+            .map(|(first, _)| *first)
+            .unwrap_or(self.next_sequence);
+        if sequence >= self.next_sequence || sequence + 1 < first_retained {
+            return None;
+        }
+
+        Some(
+            self.history
+                .iter()
+                .filter(|(event_sequence, _)| *event_sequence > sequence)
+                .map(|(_, event)| event.clone())
+                .collect(),
+        )
+    }
+}
+
+/// Broadcasts conversion events to subscribers in this process and keeps the latest events of
+/// each conversion so reconnecting clients can catch up on what they missed.
+pub struct InProcessConversionEvents {
+    /// Tells event ids handed out by this process apart from ids issued before a restart.
+    epoch: String,
+    channels: Arc<Mutex<HashMap<Uuid, ConversionChannel>>>,
+    config: Arc<AppConfig>,
+}
+
+impl InProcessConversionEvents {
+    pub fn new(config: Arc<AppConfig>) -> Self {
+        let epoch = Uuid::new_v4().simple().to_string()[..8].to_owned();
+        Self {
+            epoch,
+            channels: Arc::new(Mutex::new(HashMap::new())),
+            config,
+        }
+    }
+
+    fn event_id(&self, sequence: u64) -> String {
+        format!("{}-{}", self.epoch, sequence)
+    }
+
+    fn parse_event_id(&self, event_id: &str) -> Option<u64> {
+        let (epoch, sequence) = event_id.split_once('-')?;
+        if epoch != self.epoch {
+            return None;
+        }
+        sequence.parse().ok()
+    }
+
+    /// Drops the events of a finished conversion once clients had time to catch up, unless it
+    /// published something again in the meantime.
+    fn schedule_removal(&self, conversion_id: Uuid, next_sequence: u64) {
+        let channels = self.channels.clone();
+        let retention = Duration::from_secs(self.config.conversion_event_retention);
+        tokio::spawn(async move {
+            tokio::time::sleep(retention).await;
+            let mut channels = channels.lock().unwrap();
+            if channels
+                .get(&conversion_id)
+                .is_some_and(|channel| channel.next_sequence == next_sequence)
+            {
+                channels.remove(&conversion_id);
+            }
+        });
+    }
+}
+
+impl ConversionEvents for InProcessConversionEvents {
+    fn publish(&self, conversion_id: &Uuid, event: ConversionEvent) {
+        let is_final = event.is_final();
+        let mut channels = self.channels.lock().unwrap();
+        let channel = channels
+            .entry(*conversion_id)
+            .or_insert_with(ConversionChannel::new);
+
+        let sequence = channel.next_sequence;
+        channel.next_sequence += 1;
+        let event = ConversionEventDto {
+            id: Some(self.event_id(sequence)),
+            conversion_id: *conversion_id,
+            event,
+            created_at: OffsetDateTime::now_utc(),
+        };
+
+        channel.history.push_back((sequence, event.clone()));
+        while channel.history.len() > self.config.conversion_event_history {
+            channel.history.pop_front();
+        }
+        // Nobody listening is fine, the history still has the event.
+        let _ = channel.sender.send(event);
+
+        if is_final {
+            self.schedule_removal(*conversion_id, channel.next_sequence);
+        }
+    }
+
+    fn subscribe(
+        &self,
+        conversion_id: &Uuid,
+        last_event_id: Option<&str>,
+    ) -> ConversionEventSubscription {
+        let mut channels = self.channels.lock().unwrap();
+        // Channels opened only by subscribers are forgotten once they all went away.
+        channels.retain(|_, channel| {
+            !channel.history.is_empty() || channel.sender.receiver_count() > 0
+        });
+
+        let channel = channels
+            .entry(*conversion_id)
+            .or_insert_with(ConversionChannel::new);
+        let missed = last_event_id
+            .and_then(|event_id| self.parse_event_id(event_id))
+            .and_then(|sequence| channel.events_after(sequence));
+
+        ConversionEventSubscription {
+            last_event_id: channel
+                .history
+                .back()
+                .map(|(sequence, _)| self.event_id(*sequence)),
+            missed,
+            receiver: channel.sender.subscribe(),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use async_trait::async_trait;
+use spotitube_core::{
+    conversions::{
+        repository::DynConversionsRepository,
+        runner::{ConversionJob, DynConversionRunner},
+    },
+    errors::{SpotitubeError, SpotitubeResult},
+    jobs::{handler::JobHandler, repository::JobEntity},
+    snapshots::recorder::DynSnapshotRecorder,
+};
+use spotitube_domain::{conversions::ConversionStatus, snapshots::SnapshotOrigin};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+/// Runs conversions queued as background jobs and snapshots both playlists once done.
+pub struct ConversionJobHandler {
+    runner: DynConversionRunner,
+    repository: DynConversionsRepository,
+    snapshot_recorder: DynSnapshotRecorder,
+}
+
+impl ConversionJobHandler {
+    pub fn new(
+        runner: DynConversionRunner,
+        repository: DynConversionsRepository,
+        snapshot_recorder: DynSnapshotRecorder,
+    ) -> Self {
+        Self {
+            runner,
+            repository,
+            snapshot_recorder,
+        }
+    }
+
+    fn payload(job: &JobEntity) -> SpotitubeResult<ConversionJob> {
+        serde_json::from_value(job.payload.clone()).map_err(|err| {
+            error!("job {:?} has an invalid payload: {:?}", job.id, err);
+            SpotitubeError::BadRequest(String::from("invalid conversion job"))
+        })
+    }
+
+    async fn record_snapshots(&self, conversion_id: &Uuid) -> SpotitubeResult<()> {
+        let Some(conversion) = self.repository.get_conversion(conversion_id).await? else {
+            return Ok(());
+        };
+        if conversion.status()? != ConversionStatus::Completed {
+            return Ok(());
+        }
+
+        self.snapshot_recorder
+            .record(
+                &conversion.user_id,
+                conversion.source_provider()?,
+                &conversion.source_playlist_id,
+                SnapshotOrigin::Import,
+            )
+            .await?;
+        if let Some(destination_id) = &conversion.destination_playlist_id {
+            self.snapshot_recorder
+                .record(
+                    &conversion.user_id,
+                    conversion.destination_provider()?,
+                    destination_id,
+                    SnapshotOrigin::Conversion,
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl JobHandler for ConversionJobHandler {
+    async fn handle(&self, job: &JobEntity) -> SpotitubeResult<()> {
+        let payload = Self::payload(job)?;
+        self.runner.run(&payload.conversion_id).await?;
+
+        // The conversion is done either way; running it again would not bring the snapshots.
+        if let Err(err) = self.record_snapshots(&payload.conversion_id).await {
+            warn!(
+                "failed to snapshot the playlists of conversion {:?}: {:?}",
+                payload.conversion_id, err
+            );
+        }
+        Ok(())
+    }
+
+    async fn dead_lettered(&self, job: &JobEntity, err: &SpotitubeError) -> SpotitubeResult<()> {
+        let payload = Self::payload(job)?;
+        self.runner.fail(&payload.conversion_id, err).await
+    }
+}
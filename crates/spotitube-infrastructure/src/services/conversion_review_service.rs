@@ -0,0 +1,401 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use spotitube_core::{
+    accounts::token_manager::DynProviderTokenManager,
+    conversions::{
+        repository::{
+            ConversionEntity, ConversionTrackEntity, DynConversionsRepository, TrackOutcome,
+        },
+        review_service::ConversionReviewService,
+    },
+    errors::{SpotitubeError, SpotitubeResult},
+    matching::repository::{DynTrackMatchesRepository, NewTrackMatch},
+    spotify::client::DynSpotifyClient,
+    youtube::client::DynYouTubeClient,
+};
+use spotitube_domain::{
+    conversions::{ConversionStatus, ConversionTrackDto, TrackReviewDto, TrackStatus},
+    providers::Provider,
+};
+use time::{Duration, OffsetDateTime};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// How long a track may stay resolving before it is considered abandoned and open for review
+/// again, e.g. after the server stopped in the middle of patching the destination playlist.
+const RESOLVE_TIMEOUT: Duration = Duration::minutes(5);
+
+pub struct SpotitubeConversionReviewService {
+    repository: DynConversionsRepository,
+    track_matches_repository: DynTrackMatchesRepository,
+    token_manager: DynProviderTokenManager,
+    spotify_client: DynSpotifyClient,
+    youtube_client: DynYouTubeClient,
+}
+
+impl SpotitubeConversionReviewService {
+    pub fn new(
+        repository: DynConversionsRepository,
+        track_matches_repository: DynTrackMatchesRepository,
+        token_manager: DynProviderTokenManager,
+        spotify_client: DynSpotifyClient,
+        youtube_client: DynYouTubeClient,
+    ) -> Self {
+        Self {
+            repository,
+            track_matches_repository,
+            token_manager,
+            spotify_client,
+            youtube_client,
+        }
+    }
+
+    async fn get_conversion(
+        &self,
+        user_id: &Uuid,
+        conversion_id: &Uuid,
+    ) -> SpotitubeResult<ConversionEntity> {
+        self.repository
+            .get_conversion(conversion_id)
+            .await?
+            .filter(|conversion| conversion.user_id == *user_id)
+            .ok_or_else(|| SpotitubeError::NotFound(String::from("conversion not found")))
+    }
+
+    /// Loads a track awaiting review on a conversion that is done writing its playlist.
+    async fn get_review(
+        &self,
+        user_id: &Uuid,
+        conversion_id: &Uuid,
+        position: i32,
+    ) -> SpotitubeResult<(ConversionEntity, ConversionTrackEntity)> {
+        let conversion = self.get_conversion(user_id, conversion_id).await?;
+        if matches!(
+            conversion.status()?,
+            ConversionStatus::Pending | ConversionStatus::Running
+        ) {
+            return Err(SpotitubeError::Conflict(String::from(
+                "conversion is still running",
+            )));
+        }
+
+        let track = self
+            .repository
+            .get_conversion_track(conversion_id, position)
+            .await?
+            .ok_or_else(|| SpotitubeError::NotFound(String::from("track not found")))?;
+        if !awaits_review(&track) {
+            return Err(SpotitubeError::Conflict(String::from(
+                "track is not awaiting review",
+            )));
+        }
+
+        Ok((conversion, track))
+    }
+
+    async fn track_dto(
+        &self,
+        track: &ConversionTrackEntity,
+    ) -> SpotitubeResult<ConversionTrackDto> {
+        self.repository
+            .get_conversion_track(&track.conversion_id, track.position)
+            .await?
+            .ok_or_else(|| SpotitubeError::NotFound(String::from("track not found")))?
+            .into_dto()
+    }
+
+    /// Adds the chosen match to the destination playlist where the track belongs and records
+    /// it, putting the track back up for review if the playlist could not be patched.
+    async fn resolve(
+        &self,
+        user_id: &Uuid,
+        conversion: &ConversionEntity,
+        track: &ConversionTrackEntity,
+        outcome: TrackOutcome<'_>,
+    ) -> SpotitubeResult<ConversionTrackDto> {
+        self.claim(track).await?;
+
+        let destination_id = outcome.destination_id.unwrap_or_default();
+        if let Err(err) = self
+            .add_to_destination(conversion, track, destination_id)
+            .await
+        {
+            warn!(
+                "failed to add {:?} to the destination of conversion {:?}: {:?}",
+                destination_id, conversion.id, err
+            );
+            self.repository
+                .transition_conversion_track(
+                    &track.id,
+                    TrackStatus::Resolving,
+                    TrackStatus::NeedsReview,
+                )
+                .await?;
+            return Err(err);
+        }
+
+        if let Some(source_id) = track.source_id.as_deref() {
+            let confirmed = self
+                .track_matches_repository
+                .upsert_track_match(NewTrackMatch {
+                    source_provider: &conversion.source_provider,
+                    source_id,
+                    isrc: track.isrc.as_deref(),
+                    destination_provider: &conversion.destination_provider,
+                    destination_id,
+                    destination_title: outcome.destination_title.unwrap_or_default(),
+                    confidence: outcome.confidence.unwrap_or(1.0),
+                    confirmed_by: Some(user_id),
+                    expires_at: None,
+                })
+                .await;
+            if let Err(err) = confirmed {
+                warn!("failed to remember the match of {:?}: {:?}", source_id, err);
+            }
+        }
+
+        self.repository
+            .update_conversion_track(&track.id, outcome)
+            .await?;
+        info!(
+            "resolved track {} of conversion {:?} with {:?}",
+            track.position, conversion.id, destination_id
+        );
+
+        self.track_dto(track).await
+    }
+
+    /// Marks a track as being resolved so that concurrent reviews of it are refused.
+    async fn claim(&self, track: &ConversionTrackEntity) -> SpotitubeResult<()> {
+        if !self
+            .repository
+            .claim_conversion_track(&track.id, OffsetDateTime::now_utc() - RESOLVE_TIMEOUT)
+            .await?
+        {
+            return Err(SpotitubeError::Conflict(String::from(
+                "track is not awaiting review",
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn add_to_destination(
+        &self,
+        conversion: &ConversionEntity,
+        track: &ConversionTrackEntity,
+        destination_id: &str,
+    ) -> SpotitubeResult<()> {
+        let playlist_id = conversion
+            .destination_playlist_id
+            .as_deref()
+            .ok_or_else(|| {
+                SpotitubeError::Conflict(String::from("conversion has no destination playlist"))
+            })?;
+        let position = self
+            .repository
+            .count_matched_tracks_before(&conversion.id, track.position)
+            .await? as u32;
+
+        let provider = conversion.destination_provider()?;
+        let access_token = self
+            .token_manager
+            .access_token(&conversion.user_id, provider)
+            .await?;
+        match provider {
+            Provider::YouTube => {
+                self.youtube_client
+                    .insert_playlist_item(
+                        &access_token,
+                        playlist_id,
+                        destination_id,
+                        Some(position),
+                    )
+                    .await?;
+            }
+            Provider::Spotify => {
+                self.spotify_client
+                    .add_tracks_to_playlist(
+                        &access_token,
+                        playlist_id,
+                        &[format!("spotify:track:{}", destination_id)],
+                        Some(position),
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Title of a destination id the user typed in, which also checks that it exists.
+    async fn destination_title(
+        &self,
+        conversion: &ConversionEntity,
+        destination_id: &str,
+    ) -> SpotitubeResult<String> {
+        let provider = conversion.destination_provider()?;
+        let access_token = self
+            .token_manager
+            .access_token(&conversion.user_id, provider)
+            .await?;
+        let not_found = || {
+            SpotitubeError::BadRequest(format!(
+                "{:?} was not found on {}",
+                destination_id, provider
+            ))
+        };
+
+        match provider {
+            Provider::YouTube => self
+                .youtube_client
+                .get_videos(&access_token, &[String::from(destination_id)])
+                .await?
+                .into_iter()
+                .next()
+                .map(|video| video.snippet.title)
+                .ok_or_else(not_found),
+            Provider::Spotify => {
+                match self
+                    .spotify_client
+                    .get_track(&access_token, destination_id)
+                    .await
+                {
+                    Ok(track) => Ok(track.display_title()),
+                    Err(SpotitubeError::NotFound(_)) => Err(not_found()),
+                    Err(err) => Err(err),
+                }
+            }
+        }
+    }
+}
+
+/// Whether a track needs review, counting one whose resolution was abandoned.
+fn awaits_review(track: &ConversionTrackEntity) -> bool {
+    track.status == TrackStatus::NeedsReview.as_str()
+        || (track.status == TrackStatus::Resolving.as_str()
+            && track.updated_at < OffsetDateTime::now_utc() - RESOLVE_TIMEOUT)
+}
+
+#[async_trait]
+impl ConversionReviewService for SpotitubeConversionReviewService {
+    async fn list_reviews(
+        &self,
+        user_id: &Uuid,
+        conversion_id: &Uuid,
+        candidates: Option<usize>,
+    ) -> SpotitubeResult<Vec<TrackReviewDto>> {
+        self.get_conversion(user_id, conversion_id).await?;
+
+        let mut candidates_by_track = HashMap::new();
+        for candidate in self.repository.get_track_candidates(conversion_id).await? {
+            candidates_by_track
+                .entry(candidate.conversion_track_id)
+                .or_insert_with(Vec::new)
+                .push(candidate.into_dto());
+        }
+
+        self.repository
+            .get_conversion_tracks(conversion_id)
+            .await?
+            .into_iter()
+            .filter(awaits_review)
+            .map(|track| {
+                let mut track_candidates =
+                    candidates_by_track.remove(&track.id).unwrap_or_default();
+                if let Some(limit) = candidates {
+                    track_candidates.truncate(limit);
+                }
+
+                Ok(TrackReviewDto {
+                    track: track.into_dto()?,
+                    candidates: track_candidates,
+                })
+            })
+            .collect()
+    }
+
+    async fn accept_candidate(
+        &self,
+        user_id: &Uuid,
+        conversion_id: &Uuid,
+        position: i32,
+        candidate_id: &Uuid,
+    ) -> SpotitubeResult<ConversionTrackDto> {
+        let (conversion, track) = self.get_review(user_id, conversion_id, position).await?;
+        let candidate = self
+            .repository
+            .get_track_candidate(&track.id, candidate_id)
+            .await?
+            .ok_or_else(|| SpotitubeError::NotFound(String::from("candidate not found")))?;
+
+        let mut match_reasons = candidate.match_reasons;
+        match_reasons.push(String::from("accepted during review"));
+        self.resolve(
+            user_id,
+            &conversion,
+            &track,
+            TrackOutcome {
+                status: TrackStatus::Matched,
+                destination_id: Some(&candidate.destination_id),
+                destination_title: Some(&candidate.title),
+                confidence: Some(candidate.confidence),
+                match_reasons: &match_reasons,
+                error: None,
+            },
+        )
+        .await
+    }
+
+    async fn reject_candidates(
+        &self,
+        user_id: &Uuid,
+        conversion_id: &Uuid,
+        position: i32,
+    ) -> SpotitubeResult<ConversionTrackDto> {
+        let (_, track) = self.get_review(user_id, conversion_id, position).await?;
+        self.claim(&track).await?;
+        if !self
+            .repository
+            .transition_conversion_track(&track.id, TrackStatus::Resolving, TrackStatus::Rejected)
+            .await?
+        {
+            return Err(SpotitubeError::Conflict(String::from(
+                "track is not awaiting review",
+            )));
+        }
+
+        info!(
+            "rejected the candidates of track {} of conversion {:?}",
+            position, conversion_id
+        );
+        self.track_dto(&track).await
+    }
+
+    async fn set_manual_match(
+        &self,
+        user_id: &Uuid,
+        conversion_id: &Uuid,
+        position: i32,
+        destination_id: &str,
+    ) -> SpotitubeResult<ConversionTrackDto> {
+        let (conversion, track) = self.get_review(user_id, conversion_id, position).await?;
+        let title = self.destination_title(&conversion, destination_id).await?;
+
+        self.resolve(
+            user_id,
+            &conversion,
+            &track,
+            TrackOutcome {
+                status: TrackStatus::Matched,
+                destination_id: Some(destination_id),
+                destination_title: Some(&title),
+                confidence: None,
+                match_reasons: &[String::from("chosen manually")],
+                error: None,
+            },
+        )
+        .await
+    }
+}
@@ -0,0 +1,664 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use spotitube_core::{
+    accounts::token_manager::DynProviderTokenManager,
+    config::AppConfig,
+    conversions::{
+        events::DynConversionEvents,
+        repository::{
+            ConversionEntity, ConversionTrackEntity, DynConversionsRepository, NewConversionTrack,
+            NewTrackCandidate, TrackOutcome,
+        },
+        runner::ConversionRunner,
+    },
+    errors::{SpotitubeError, SpotitubeResult},
+    matching::{
+        matcher::MatchScore,
+        resolver::{DynTrackResolver, ResolvedTrack, SourceTrack, TrackResolution},
+    },
+    spotify::client::DynSpotifyClient,
+    youtube::{client::DynYouTubeClient, models::YouTubePlaylistItem},
+};
+use spotitube_domain::{
+    conversions::{ConversionEvent, ConversionStatus, TrackStatus},
+    providers::Provider,
+};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+const SPOTIFY_PAGE_SIZE: u32 = 100;
+const YOUTUBE_PAGE_SIZE: u32 = 50;
+const DESTINATION_PRIVACY: &str = "private";
+
+pub struct SpotitubeConversionRunner {
+    repository: DynConversionsRepository,
+    token_manager: DynProviderTokenManager,
+    resolver: DynTrackResolver,
+    events: DynConversionEvents,
+    spotify_client: DynSpotifyClient,
+    youtube_client: DynYouTubeClient,
+    config: Arc<AppConfig>,
+}
+
+impl SpotitubeConversionRunner {
+    pub fn new(
+        repository: DynConversionsRepository,
+        token_manager: DynProviderTokenManager,
+        resolver: DynTrackResolver,
+        events: DynConversionEvents,
+        spotify_client: DynSpotifyClient,
+        youtube_client: DynYouTubeClient,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        Self {
+            repository,
+            token_manager,
+            resolver,
+            events,
+            spotify_client,
+            youtube_client,
+            config,
+        }
+    }
+
+    /// Errors that will fail every remaining track as well, so the conversion stops early.
+    fn is_fatal(err: &SpotitubeError) -> bool {
+        matches!(
+            err,
+            SpotitubeError::ProviderUnauthorized(_)
+                | SpotitubeError::ProviderQuotaExceeded(_)
+                | SpotitubeError::ProviderRateLimited(_)
+        )
+    }
+
+    async fn convert(&self, conversion: &ConversionEntity) -> SpotitubeResult<()> {
+        match (
+            conversion.source_provider()?,
+            conversion.destination_provider()?,
+        ) {
+            (Provider::Spotify, Provider::YouTube) => self.spotify_to_youtube(conversion).await,
+            (Provider::YouTube, Provider::Spotify) => self.youtube_to_spotify(conversion).await,
+            (source, destination) => Err(SpotitubeError::BadRequest(format!(
+                "converting from {} to {} is not supported",
+                source, destination
+            ))),
+        }
+    }
+
+    async fn spotify_to_youtube(&self, conversion: &ConversionEntity) -> SpotitubeResult<()> {
+        let mut tracks = self
+            .repository
+            .get_conversion_tracks(&conversion.id)
+            .await?;
+        let destination_id = match &conversion.destination_playlist_id {
+            Some(destination_id) if !tracks.is_empty() => destination_id.clone(),
+            destination_id => {
+                let access_token = self
+                    .token_manager
+                    .access_token(&conversion.user_id, Provider::Spotify)
+                    .await?;
+                let playlist = self
+                    .spotify_client
+                    .get_playlist(&access_token, &conversion.source_playlist_id)
+                    .await?;
+                if tracks.is_empty() {
+                    let source_tracks = self
+                        .read_spotify_tracks(&access_token, &conversion.source_playlist_id)
+                        .await?;
+                    tracks = self
+                        .repository
+                        .update_conversion_source(&conversion.id, &playlist.name, &source_tracks)
+                        .await?;
+                    self.events.publish(
+                        &conversion.id,
+                        ConversionEvent::TracksFetched {
+                            name: playlist.name.clone(),
+                            total_tracks: source_tracks.len() as i32,
+                        },
+                    );
+                }
+
+                match destination_id {
+                    Some(destination_id) => destination_id.clone(),
+                    None => {
+                        self.create_youtube_destination(conversion, &playlist.name)
+                            .await?
+                    }
+                }
+            }
+        };
+
+        for track in Self::unconverted(&tracks) {
+            if track.source_id.is_none() {
+                self.record_outcome(
+                    track,
+                    TrackStatus::Skipped,
+                    None,
+                    None,
+                    Some("local files cannot be converted"),
+                )
+                .await?;
+                continue;
+            }
+
+            if let Err(err) = self
+                .convert_to_youtube(&conversion.user_id, &destination_id, track)
+                .await
+            {
+                if Self::is_fatal(&err) {
+                    return Err(err);
+                }
+                warn!("failed to convert track {:?}: {:?}", track.id, err);
+                self.record_outcome(
+                    track,
+                    TrackStatus::Failed,
+                    None,
+                    None,
+                    Some(&err.user_message()),
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn youtube_to_spotify(&self, conversion: &ConversionEntity) -> SpotitubeResult<()> {
+        let mut tracks = self
+            .repository
+            .get_conversion_tracks(&conversion.id)
+            .await?;
+        let destination_id = match &conversion.destination_playlist_id {
+            Some(destination_id) if !tracks.is_empty() => destination_id.clone(),
+            destination_id => {
+                let access_token = self
+                    .token_manager
+                    .access_token(&conversion.user_id, Provider::YouTube)
+                    .await?;
+                let playlist = self
+                    .youtube_client
+                    .get_playlist(&access_token, &conversion.source_playlist_id)
+                    .await?;
+                if tracks.is_empty() {
+                    let source_tracks = self
+                        .read_youtube_tracks(&access_token, &conversion.source_playlist_id)
+                        .await?;
+                    tracks = self
+                        .repository
+                        .update_conversion_source(
+                            &conversion.id,
+                            &playlist.snippet.title,
+                            &source_tracks,
+                        )
+                        .await?;
+                    self.events.publish(
+                        &conversion.id,
+                        ConversionEvent::TracksFetched {
+                            name: playlist.snippet.title.clone(),
+                            total_tracks: source_tracks.len() as i32,
+                        },
+                    );
+                }
+
+                match destination_id {
+                    Some(destination_id) => destination_id.clone(),
+                    None => {
+                        self.create_spotify_destination(conversion, &playlist.snippet.title)
+                            .await?
+                    }
+                }
+            }
+        };
+
+        let mut matches = Vec::new();
+        for track in Self::unconverted(&tracks) {
+            if track.source_id.is_none() {
+                self.record_outcome(
+                    track,
+                    TrackStatus::Skipped,
+                    None,
+                    None,
+                    Some("video is unavailable"),
+                )
+                .await?;
+                continue;
+            }
+
+            match self.match_spotify_track(&conversion.user_id, track).await {
+                Ok(Some(resolved)) => {
+                    self.publish_match(track, &resolved);
+                    matches.push((track, resolved))
+                }
+                Ok(None) => {}
+                Err(err) if Self::is_fatal(&err) => return Err(err),
+                Err(err) => {
+                    warn!("failed to convert video {:?}: {:?}", track.source_id, err);
+                    self.record_outcome(
+                        track,
+                        TrackStatus::Failed,
+                        None,
+                        None,
+                        Some(&err.user_message()),
+                    )
+                    .await?
+                }
+            }
+        }
+
+        if !matches.is_empty() {
+            let access_token = self
+                .token_manager
+                .access_token(&conversion.user_id, Provider::Spotify)
+                .await?;
+            let uris: Vec<String> = matches
+                .iter()
+                .map(|(_, resolved)| format!("spotify:track:{}", resolved.id))
+                .collect();
+            self.spotify_client
+                .add_tracks_to_playlist(&access_token, &destination_id, &uris, None)
+                .await?;
+        }
+
+        for (track, resolved) in matches {
+            self.record_outcome(
+                track,
+                TrackStatus::Matched,
+                Some((&resolved.id, &resolved.title)),
+                Some(&resolved.score),
+                None,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Tracks an earlier run has not recorded an outcome for yet.
+    fn unconverted(
+        tracks: &[ConversionTrackEntity],
+    ) -> impl Iterator<Item = &ConversionTrackEntity> {
+        tracks
+            .iter()
+            .filter(|track| track.status == TrackStatus::Pending.as_str())
+    }
+
+    async fn create_youtube_destination(
+        &self,
+        conversion: &ConversionEntity,
+        source_name: &str,
+    ) -> SpotitubeResult<String> {
+        let access_token = self
+            .token_manager
+            .access_token(&conversion.user_id, Provider::YouTube)
+            .await?;
+        let destination = self
+            .youtube_client
+            .create_playlist(
+                &access_token,
+                conversion.name.as_deref().unwrap_or(source_name),
+                &format!("Converted from the Spotify playlist \"{}\"", source_name),
+                DESTINATION_PRIVACY,
+            )
+            .await?;
+        self.repository
+            .update_conversion_destination(&conversion.id, &destination.id)
+            .await?;
+
+        Ok(destination.id)
+    }
+
+    async fn create_spotify_destination(
+        &self,
+        conversion: &ConversionEntity,
+        source_name: &str,
+    ) -> SpotitubeResult<String> {
+        let access_token = self
+            .token_manager
+            .access_token(&conversion.user_id, Provider::Spotify)
+            .await?;
+        let spotify_user = self.spotify_client.get_current_user(&access_token).await?;
+        let destination = self
+            .spotify_client
+            .create_playlist(
+                &access_token,
+                &spotify_user.id,
+                conversion.name.as_deref().unwrap_or(source_name),
+                &format!("Converted from the YouTube playlist \"{}\"", source_name),
+                false,
+            )
+            .await?;
+        self.repository
+            .update_conversion_destination(&conversion.id, &destination.id)
+            .await?;
+
+        Ok(destination.id)
+    }
+
+    async fn read_spotify_tracks(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+    ) -> SpotitubeResult<Vec<NewConversionTrack>> {
+        let mut tracks = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = self
+                .spotify_client
+                .get_playlist_tracks(access_token, playlist_id, SPOTIFY_PAGE_SIZE, offset)
+                .await?;
+
+            for track in page
+                .items
+                .into_iter()
+                .filter_map(|item| Some(item.track?.into_track()))
+            {
+                tracks.push(NewConversionTrack {
+                    position: tracks.len() as i32,
+                    source_id: track.external_id(Provider::Spotify).map(String::from),
+                    artists: track.artist_names(),
+                    title: track.title,
+                    album: track.album.map(|album| album.title),
+                    duration_ms: track.duration_ms,
+                    isrc: track.isrc,
+                });
+            }
+
+            match page.next {
+                Some(_) => offset += SPOTIFY_PAGE_SIZE,
+                None => return Ok(tracks),
+            }
+        }
+    }
+
+    async fn read_youtube_tracks(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+    ) -> SpotitubeResult<Vec<NewConversionTrack>> {
+        let mut tracks = Vec::new();
+        let mut page_token = None;
+        loop {
+            let page = self
+                .youtube_client
+                .list_playlist_items(
+                    access_token,
+                    playlist_id,
+                    page_token.as_deref(),
+                    YOUTUBE_PAGE_SIZE,
+                )
+                .await?;
+
+            let video_ids: Vec<String> = page
+                .items
+                .iter()
+                .filter_map(|item| item.snippet.resource_id.video_id.clone())
+                .collect();
+            let durations: HashMap<String, i64> = self
+                .youtube_client
+                .get_videos(access_token, &video_ids)
+                .await?
+                .into_iter()
+                .filter_map(|video| {
+                    let duration_ms = video.content_details?.duration_ms()?;
+                    Some((video.id, duration_ms))
+                })
+                .collect();
+
+            for track in page.items.into_iter().map(YouTubePlaylistItem::into_track) {
+                let video_id = track.external_id(Provider::YouTube).map(String::from);
+                tracks.push(NewConversionTrack {
+                    position: tracks.len() as i32,
+                    duration_ms: video_id
+                        .as_ref()
+                        .and_then(|video_id| durations.get(video_id).copied()),
+                    source_id: video_id,
+                    artists: track.artist_names(),
+                    title: track.title,
+                    album: None,
+                    isrc: None,
+                });
+            }
+
+            match page.next_page_token {
+                Some(next_page_token) => page_token = Some(next_page_token),
+                None => return Ok(tracks),
+            }
+        }
+    }
+
+    /// Matches a Spotify track against YouTube search results and appends the best video to
+    /// the playlist when the match is confident enough.
+    async fn convert_to_youtube(
+        &self,
+        user_id: &Uuid,
+        playlist_id: &str,
+        track: &ConversionTrackEntity,
+    ) -> SpotitubeResult<()> {
+        let access_token = self
+            .token_manager
+            .access_token(user_id, Provider::YouTube)
+            .await?;
+
+        let resolved = match self
+            .resolver
+            .resolve(user_id, Provider::Spotify, &Self::source_track(track))
+            .await?
+        {
+            TrackResolution::Matched(resolved) => resolved,
+            TrackResolution::Unsure(candidates) => {
+                return self.record_review(track, candidates).await
+            }
+            TrackResolution::NotFound => {
+                return self
+                    .record_outcome(track, TrackStatus::NotFound, None, None, None)
+                    .await
+            }
+        };
+
+        self.publish_match(track, &resolved);
+        self.youtube_client
+            .insert_playlist_item(&access_token, playlist_id, &resolved.id, None)
+            .await?;
+        self.record_outcome(
+            track,
+            TrackStatus::Matched,
+            Some((&resolved.id, &resolved.title)),
+            Some(&resolved.score),
+            None,
+        )
+        .await
+    }
+
+    /// Finds the Spotify track for a video, returning it when the match can be added right
+    /// away and recording the track as not found or up for review otherwise.
+    async fn match_spotify_track(
+        &self,
+        user_id: &Uuid,
+        track: &ConversionTrackEntity,
+    ) -> SpotitubeResult<Option<ResolvedTrack>> {
+        match self
+            .resolver
+            .resolve(user_id, Provider::YouTube, &Self::source_track(track))
+            .await?
+        {
+            TrackResolution::Matched(resolved) => Ok(Some(resolved)),
+            TrackResolution::Unsure(candidates) => {
+                self.record_review(track, candidates).await?;
+                Ok(None)
+            }
+            TrackResolution::NotFound => {
+                self.record_outcome(track, TrackStatus::NotFound, None, None, None)
+                    .await?;
+                Ok(None)
+            }
+        }
+    }
+
+    fn source_track(track: &ConversionTrackEntity) -> SourceTrack<'_> {
+        SourceTrack {
+            id: track.source_id.as_deref(),
+            title: &track.title,
+            artists: &track.artists,
+            duration_ms: track.duration_ms,
+            isrc: track.isrc.as_deref(),
+        }
+    }
+
+    /// Keeps the best candidates of an unsure match so the user can pick one later.
+    async fn record_review(
+        &self,
+        track: &ConversionTrackEntity,
+        candidates: Vec<ResolvedTrack>,
+    ) -> SpotitubeResult<()> {
+        let best = candidates.first().map(|candidate| candidate.score.clone());
+        for (rank, candidate) in candidates
+            .into_iter()
+            .take(self.config.match_review_candidates)
+            .enumerate()
+        {
+            self.repository
+                .create_track_candidate(
+                    &track.id,
+                    &NewTrackCandidate {
+                        rank: rank as i32,
+                        destination_id: candidate.id,
+                        title: candidate.title,
+                        confidence: candidate.score.confidence,
+                        match_reasons: candidate.score.reasons,
+                    },
+                )
+                .await?;
+        }
+
+        self.record_outcome(track, TrackStatus::NeedsReview, None, best.as_ref(), None)
+            .await
+    }
+
+    fn publish_match(&self, track: &ConversionTrackEntity, resolved: &ResolvedTrack) {
+        self.events.publish(
+            &track.conversion_id,
+            ConversionEvent::TrackMatched {
+                position: track.position,
+                destination_id: resolved.id.clone(),
+                destination_title: resolved.title.clone(),
+                confidence: Some(resolved.score.confidence),
+            },
+        );
+    }
+
+    fn publish_status(&self, conversion_id: &Uuid, status: ConversionStatus, error: Option<&str>) {
+        self.events.publish(
+            conversion_id,
+            ConversionEvent::StatusChanged {
+                status,
+                error: error.map(String::from),
+            },
+        );
+    }
+
+    async fn record_outcome(
+        &self,
+        track: &ConversionTrackEntity,
+        status: TrackStatus,
+        destination: Option<(&str, &str)>,
+        score: Option<&MatchScore>,
+        error: Option<&str>,
+    ) -> SpotitubeResult<()> {
+        self.repository
+            .update_conversion_track(
+                &track.id,
+                TrackOutcome {
+                    status,
+                    destination_id: destination.map(|(id, _)| id),
+                    destination_title: destination.map(|(_, title)| title),
+                    confidence: score.map(|score| score.confidence),
+                    match_reasons: score
+                        .map(|score| score.reasons.as_slice())
+                        .unwrap_or_default(),
+                    error,
+                },
+            )
+            .await?;
+
+        let event = match (status, destination) {
+            (TrackStatus::Matched, Some((destination_id, _))) => ConversionEvent::TrackInserted {
+                position: track.position,
+                destination_id: destination_id.to_owned(),
+            },
+            _ => ConversionEvent::TrackFailed {
+                position: track.position,
+                status,
+                error: error.map(String::from),
+            },
+        };
+        self.events.publish(&track.conversion_id, event);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ConversionRunner for SpotitubeConversionRunner {
+    async fn run(&self, conversion_id: &Uuid) -> SpotitubeResult<()> {
+        let conversion = self
+            .repository
+            .get_conversion(conversion_id)
+            .await?
+            .ok_or_else(|| SpotitubeError::NotFound(String::from("conversion not found")))?;
+        if matches!(
+            conversion.status()?,
+            ConversionStatus::Completed | ConversionStatus::Failed
+        ) {
+            info!("conversion {:?} has already finished", conversion_id);
+            return Ok(());
+        }
+
+        info!("running conversion {:?}", conversion_id);
+        self.repository
+            .update_conversion_status(conversion_id, ConversionStatus::Running.as_str(), None)
+            .await?;
+        self.publish_status(conversion_id, ConversionStatus::Running, None);
+
+        match self.convert(&conversion).await {
+            Ok(()) => {
+                self.repository
+                    .update_conversion_status(
+                        conversion_id,
+                        ConversionStatus::Completed.as_str(),
+                        None,
+                    )
+                    .await?;
+                self.publish_status(conversion_id, ConversionStatus::Completed, None);
+                info!("completed conversion {:?}", conversion_id);
+                Ok(())
+            }
+            Err(err) => {
+                warn!("conversion {:?} stopped: {:?}", conversion_id, err);
+                let reason = err.user_message();
+                self.repository
+                    .update_conversion_status(
+                        conversion_id,
+                        ConversionStatus::Pending.as_str(),
+                        Some(&reason),
+                    )
+                    .await?;
+                self.publish_status(conversion_id, ConversionStatus::Pending, Some(&reason));
+                Err(err)
+            }
+        }
+    }
+
+    async fn fail(&self, conversion_id: &Uuid, err: &SpotitubeError) -> SpotitubeResult<()> {
+        error!("conversion {:?} failed: {:?}", conversion_id, err);
+        let reason = err.user_message();
+        self.repository
+            .update_conversion_status(
+                conversion_id,
+                ConversionStatus::Failed.as_str(),
+                Some(&reason),
+            )
+            .await?;
+        self.publish_status(conversion_id, ConversionStatus::Failed, Some(&reason));
+        Ok(())
+    }
+}
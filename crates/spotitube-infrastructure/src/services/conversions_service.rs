@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use spotitube_core::{
+    accounts::repository::DynLinkedAccountsRepository,
+    config::AppConfig,
+    conversions::{
+        events::DynConversionEvents,
+        repository::{ConversionEntity, DynConversionsRepository},
+        service::{ConversionEventStream, ConversionsService},
+    },
+    errors::{SpotitubeError, SpotitubeResult},
+};
+use spotitube_domain::{
+    conversions::{ConversionDto, ConversionEvent, ConversionEventDto, ConversionStatus},
+    providers::Provider,
+};
+use time::OffsetDateTime;
+use tracing::info;
+use uuid::Uuid;
+
+pub struct SpotitubeConversionsService {
+    repository: DynConversionsRepository,
+    linked_accounts_repository: DynLinkedAccountsRepository,
+    events: DynConversionEvents,
+    config: Arc<AppConfig>,
+}
+
+impl SpotitubeConversionsService {
+    pub fn new(
+        repository: DynConversionsRepository,
+        linked_accounts_repository: DynLinkedAccountsRepository,
+        events: DynConversionEvents,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        Self {
+            repository,
+            linked_accounts_repository,
+            events,
+            config,
+        }
+    }
+
+    async fn get_user_conversion(
+        &self,
+        user_id: &Uuid,
+        id: &Uuid,
+    ) -> SpotitubeResult<ConversionEntity> {
+        self.repository
+            .get_conversion(id)
+            .await?
+            .filter(|conversion| conversion.user_id == *user_id)
+            .ok_or_else(|| SpotitubeError::NotFound(String::from("conversion not found")))
+    }
+
+    async fn require_linked_account(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+    ) -> SpotitubeResult<()> {
+        match self
+            .linked_accounts_repository
+            .get_linked_account(user_id, provider.as_str())
+            .await?
+        {
+            Some(_) => Ok(()),
+            None => Err(SpotitubeError::BadRequest(format!(
+                "a {} account must be linked first",
+                provider
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl ConversionsService for SpotitubeConversionsService {
+    async fn create_conversion(
+        &self,
+        user_id: &Uuid,
+        source: Provider,
+        source_playlist_id: &str,
+        name: Option<&str>,
+    ) -> SpotitubeResult<ConversionDto> {
+        let destination = match source {
+            Provider::Spotify => Provider::YouTube,
+            Provider::YouTube => Provider::Spotify,
+        };
+        self.require_linked_account(user_id, source).await?;
+        self.require_linked_account(user_id, destination).await?;
+
+        let conversion = self
+            .repository
+            .create_conversion(
+                user_id,
+                source.as_str(),
+                source_playlist_id,
+                destination.as_str(),
+                name,
+                self.config.job_max_attempts,
+            )
+            .await?;
+        info!(
+            "created conversion {:?} of {} playlist {:?}",
+            conversion.id, source, source_playlist_id
+        );
+
+        conversion.into_dto(Vec::new())
+    }
+
+    async fn get_conversion(&self, user_id: &Uuid, id: &Uuid) -> SpotitubeResult<ConversionDto> {
+        let conversion = self.get_user_conversion(user_id, id).await?;
+        let tracks = self.repository.get_conversion_tracks(id).await?;
+        conversion.into_dto(tracks)
+    }
+
+    async fn watch_conversion(
+        &self,
+        user_id: &Uuid,
+        id: &Uuid,
+        last_event_id: Option<&str>,
+    ) -> SpotitubeResult<ConversionEventStream> {
+        self.get_user_conversion(user_id, id).await?;
+
+        // Subscribe before reading the conversion so no event falls in between.
+        let subscription = self.events.subscribe(id, last_event_id);
+        let conversion = self.get_user_conversion(user_id, id).await?;
+        let finished = matches!(
+            conversion.status()?,
+            ConversionStatus::Completed | ConversionStatus::Failed
+        );
+
+        let resumed = subscription.missed.is_some();
+        let mut initial = subscription.missed.unwrap_or_default();
+        if !resumed || (finished && !initial.iter().any(|event| event.event.is_final())) {
+            let tracks = self.repository.get_conversion_tracks(id).await?;
+            initial.push(ConversionEventDto {
+                id: subscription.last_event_id,
+                conversion_id: *id,
+                event: ConversionEvent::Snapshot {
+                    conversion: conversion.into_dto(tracks)?,
+                },
+                created_at: OffsetDateTime::now_utc(),
+            });
+        }
+
+        Ok(ConversionEventStream {
+            initial,
+            receiver: (!finished).then_some(subscription.receiver),
+        })
+    }
+}
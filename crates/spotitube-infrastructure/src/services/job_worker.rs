@@ -0,0 +1,252 @@
+use std::{collections::HashMap, sync::Arc, time::Duration as StdDuration};
+
+use spotitube_core::{
+    config::AppConfig,
+    errors::{SpotitubeError, SpotitubeResult},
+    jobs::{
+        handler::DynJobHandler,
+        repository::{DynJobRepository, JobEntity},
+    },
+    youtube::repository::DynYouTubeQuotaRepository,
+};
+use time::{Duration, OffsetDateTime};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Longest wait between two attempts of a job, however many times it failed.
+const MAX_RETRY_DELAY_SECONDS: i64 = 3600;
+
+/// Polls the jobs table and runs due jobs with the handler registered for their kind.
+pub struct SpotitubeJobWorker {
+    repository: DynJobRepository,
+    handlers: HashMap<String, DynJobHandler>,
+    quota_repository: DynYouTubeQuotaRepository,
+    config: Arc<AppConfig>,
+}
+
+impl SpotitubeJobWorker {
+    pub fn new(
+        repository: DynJobRepository,
+        handlers: HashMap<String, DynJobHandler>,
+        quota_repository: DynYouTubeQuotaRepository,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        Self {
+            repository,
+            handlers,
+            quota_repository,
+            config,
+        }
+    }
+
+    /// Spawns the configured number of worker tasks; once `shutdown` is cancelled they stop
+    /// claiming jobs and return after finishing the one at hand.
+    pub fn start(self: &Arc<Self>, shutdown: CancellationToken) -> Vec<JoinHandle<()>> {
+        let process_id = Uuid::new_v4();
+        info!("starting {} job workers", self.config.job_workers);
+        (0..self.config.job_workers)
+            .map(|index| {
+                let worker = self.clone();
+                let worker_id = format!("{}-{}", process_id, index);
+                let shutdown = shutdown.clone();
+                tokio::spawn(async move { worker.poll(worker_id, shutdown).await })
+            })
+            .collect()
+    }
+
+    async fn poll(&self, worker_id: String, shutdown: CancellationToken) {
+        let kinds: Vec<String> = self.handlers.keys().cloned().collect();
+        let poll_interval = StdDuration::from_secs(self.config.job_poll_interval);
+        while !shutdown.is_cancelled() {
+            match self
+                .repository
+                .claim_job(&kinds, &worker_id, self.locked_until())
+                .await
+            {
+                Ok(Some(job)) => {
+                    self.process(&worker_id, job).await;
+                    continue;
+                }
+                Ok(None) => {}
+                Err(err) => error!("worker {} failed to claim a job: {:?}", worker_id, err),
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(poll_interval) => {}
+                _ = shutdown.cancelled() => {}
+            }
+        }
+        info!("worker {} stopped", worker_id);
+    }
+
+    fn locked_until(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc() + Duration::seconds(self.config.job_visibility_timeout)
+    }
+
+    /// Exponential backoff starting at the configured retry delay.
+    fn retry_delay(&self, attempts: i32) -> Duration {
+        let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+        let seconds = self
+            .config
+            .job_retry_delay
+            .saturating_mul(2_i64.pow(exponent))
+            .min(MAX_RETRY_DELAY_SECONDS);
+        Duration::seconds(seconds)
+    }
+
+    /// Errors that may go away by themselves; anything else fails the job right away.
+    fn is_retryable(err: &SpotitubeError) -> bool {
+        matches!(
+            err,
+            SpotitubeError::ProviderError(_)
+                | SpotitubeError::ProviderRateLimited(_)
+                | SpotitubeError::SqlxError(_)
+        )
+    }
+
+    async fn process(&self, worker_id: &str, job: JobEntity) {
+        let Some(handler) = self.handlers.get(&job.kind) else {
+            self.dead_letter(worker_id, &job, None, "no handler for this kind of job")
+                .await;
+            return;
+        };
+
+        if job.attempts > job.max_attempts {
+            let err = SpotitubeError::InternalServerError;
+            self.dead_letter(
+                worker_id,
+                &job,
+                Some((handler, &err)),
+                "job was abandoned too often",
+            )
+            .await;
+            return;
+        }
+
+        info!(
+            "worker {} running {} job {:?}, attempt {}",
+            worker_id, job.kind, job.id, job.attempts
+        );
+        // Resolves once another worker may have claimed the job, which cancels the handler.
+        let heartbeat = async {
+            let period =
+                StdDuration::from_secs((self.config.job_visibility_timeout / 3).max(1) as u64);
+            loop {
+                tokio::time::sleep(period).await;
+                match self
+                    .repository
+                    .extend_job_lock(&job.id, worker_id, self.locked_until())
+                    .await
+                {
+                    Ok(true) => {}
+                    Ok(false) => return,
+                    Err(err) => warn!("failed to extend the lock on job {:?}: {:?}", job.id, err),
+                }
+            }
+        };
+        let result = tokio::select! {
+            result = handler.handle(&job) => result,
+            () = heartbeat => {
+                warn!("worker {} lost the lock on job {:?}, abandoning it", worker_id, job.id);
+                return;
+            }
+        };
+
+        let outcome = match result {
+            Ok(()) => self.repository.complete_job(&job.id, worker_id).await,
+            Err(err @ SpotitubeError::ProviderQuotaExceeded(_)) => {
+                self.defer_until_quota_reset(worker_id, &job, &err).await
+            }
+            Err(err) if Self::is_retryable(&err) && job.attempts < job.max_attempts => {
+                let delay = self.retry_delay(job.attempts);
+                warn!(
+                    "{} job {:?} failed, retrying in {}s: {:?}",
+                    job.kind,
+                    job.id,
+                    delay.whole_seconds(),
+                    err
+                );
+                self.repository
+                    .retry_job(
+                        &job.id,
+                        worker_id,
+                        OffsetDateTime::now_utc() + delay,
+                        &format!("{:?}", err),
+                    )
+                    .await
+            }
+            Err(err) => {
+                let reason = format!("{:?}", err);
+                self.dead_letter(worker_id, &job, Some((handler, &err)), &reason)
+                    .await;
+                return;
+            }
+        };
+
+        match outcome {
+            Ok(true) => {}
+            Ok(false) => warn!(
+                "worker {} no longer held job {:?} when it finished",
+                worker_id, job.id
+            ),
+            Err(err) => error!(
+                "failed to record the outcome of job {:?}: {:?}",
+                job.id, err
+            ),
+        }
+    }
+
+    /// Puts a job that ran out of provider quota back until the quota resets, without
+    /// spending one of its attempts, since retrying any sooner is bound to fail again.
+    async fn defer_until_quota_reset(
+        &self,
+        worker_id: &str,
+        job: &JobEntity,
+        err: &SpotitubeError,
+    ) -> SpotitubeResult<bool> {
+        let run_at = self.quota_repository.get_quota_reset().await?;
+        warn!(
+            "{} job {:?} ran out of quota, deferring it until {}",
+            job.kind, job.id, run_at
+        );
+        self.repository
+            .defer_job(&job.id, worker_id, run_at, &format!("{:?}", err))
+            .await
+    }
+
+    async fn dead_letter(
+        &self,
+        worker_id: &str,
+        job: &JobEntity,
+        handler: Option<(&DynJobHandler, &SpotitubeError)>,
+        reason: &str,
+    ) {
+        error!("dead-lettering {} job {:?}: {}", job.kind, job.id, reason);
+        match self
+            .repository
+            .dead_letter_job(&job.id, worker_id, reason)
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => {
+                warn!(
+                    "worker {} no longer held job {:?} when it failed",
+                    worker_id, job.id
+                );
+                return;
+            }
+            Err(err) => {
+                error!("failed to dead-letter job {:?}: {:?}", job.id, err);
+                return;
+            }
+        }
+
+        if let Some((handler, err)) = handler {
+            if let Err(err) = handler.dead_lettered(job, err).await {
+                error!("failed to clean up after job {:?}: {:?}", job.id, err);
+            }
+        }
+    }
+}
@@ -1,2 +1,20 @@
+pub mod accounts_service;
+pub mod auth_service;
+pub mod conversion_events;
+pub mod conversion_job_handler;
+pub mod conversion_review_service;
+pub mod conversion_runner;
+pub mod conversions_service;
+pub mod job_worker;
+pub mod oauth_service;
+pub mod playlist_editor;
+pub mod provider_token_manager;
+pub mod snapshot_recorder;
+pub mod snapshots_service;
+pub mod sync_job_handler;
+pub mod sync_runner;
+pub mod sync_scheduler;
+pub mod syncs_service;
+pub mod track_resolver;
 pub mod users_service;
 pub mod utils;
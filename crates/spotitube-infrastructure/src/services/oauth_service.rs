@@ -0,0 +1,112 @@
+use std::{collections::HashMap, str::FromStr, sync::Arc};
+
+use async_trait::async_trait;
+use spotitube_core::{
+    config::AppConfig,
+    errors::{SpotitubeError, SpotitubeResult},
+    oauth::{
+        client::DynOAuthClient,
+        repository::DynOAuthAuthorizationsRepository,
+        service::{CompletedAuthorization, OAuthService},
+    },
+};
+use spotitube_domain::providers::Provider;
+use time::{Duration, OffsetDateTime};
+use tracing::{error, info};
+use uuid::Uuid;
+
+use crate::clients::pkce::{new_state, PkceChallenge};
+
+pub struct SpotitubeOAuthService {
+    repository: DynOAuthAuthorizationsRepository,
+    clients: HashMap<Provider, DynOAuthClient>,
+    config: Arc<AppConfig>,
+}
+
+impl SpotitubeOAuthService {
+    pub fn new(
+        repository: DynOAuthAuthorizationsRepository,
+        clients: HashMap<Provider, DynOAuthClient>,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        Self {
+            repository,
+            clients,
+            config,
+        }
+    }
+
+    fn client(&self, provider: Provider) -> SpotitubeResult<&DynOAuthClient> {
+        self.clients
+            .get(&provider)
+            .ok_or_else(|| SpotitubeError::BadRequest(format!("{} is not supported", provider)))
+    }
+}
+
+#[async_trait]
+impl OAuthService for SpotitubeOAuthService {
+    async fn begin_authorization(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+    ) -> SpotitubeResult<String> {
+        let client = self.client(provider)?;
+        let pkce = PkceChallenge::new();
+        let state = new_state();
+        let expires_at =
+            OffsetDateTime::now_utc() + Duration::seconds(self.config.oauth_state_lifetime);
+
+        self.repository
+            .create_authorization(
+                &state,
+                user_id,
+                provider.as_str(),
+                &pkce.code_verifier,
+                expires_at,
+            )
+            .await?;
+
+        info!("started {} authorization for user {:?}", provider, user_id);
+        client.authorize_url(&state, &pkce.code_challenge)
+    }
+
+    async fn complete_authorization(
+        &self,
+        state: &str,
+        code: &str,
+    ) -> SpotitubeResult<CompletedAuthorization> {
+        let authorization = self
+            .repository
+            .take_authorization(state)
+            .await?
+            .ok_or_else(|| {
+                SpotitubeError::BadRequest(String::from("unknown authorization state"))
+            })?;
+
+        if authorization.expires_at <= OffsetDateTime::now_utc() {
+            return Err(SpotitubeError::BadRequest(String::from(
+                "authorization state has expired",
+            )));
+        }
+
+        let provider = Provider::from_str(&authorization.provider).map_err(|err| {
+            error!("stored authorization has {}", err);
+            SpotitubeError::InternalServerError
+        })?;
+
+        let tokens = self
+            .client(provider)?
+            .exchange_code(code, &authorization.code_verifier)
+            .await?;
+
+        info!(
+            "completed {} authorization for user {:?}",
+            provider, authorization.user_id
+        );
+        Ok(CompletedAuthorization {
+            user_id: authorization.user_id,
+            provider,
+            tokens,
+        })
+    }
+}
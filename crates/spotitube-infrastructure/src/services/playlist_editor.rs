@@ -0,0 +1,791 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use spotitube_core::{
+    accounts::token_manager::DynProviderTokenManager,
+    errors::SpotitubeResult,
+    playlists::editor::{PlaylistContents, PlaylistEditor, PlaylistItem},
+    spotify::client::DynSpotifyClient,
+    youtube::client::DynYouTubeClient,
+};
+use spotitube_domain::providers::Provider;
+use uuid::Uuid;
+
+const SPOTIFY_PAGE_SIZE: u32 = 100;
+const YOUTUBE_PAGE_SIZE: u32 = 50;
+
+pub struct SpotitubePlaylistEditor {
+    token_manager: DynProviderTokenManager,
+    spotify_client: DynSpotifyClient,
+    youtube_client: DynYouTubeClient,
+}
+
+impl SpotitubePlaylistEditor {
+    pub fn new(
+        token_manager: DynProviderTokenManager,
+        spotify_client: DynSpotifyClient,
+        youtube_client: DynYouTubeClient,
+    ) -> Self {
+        Self {
+            token_manager,
+            spotify_client,
+            youtube_client,
+        }
+    }
+
+    async fn apply_to_spotify(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        contents: &PlaylistContents,
+        target: &[&str],
+    ) -> SpotitubeResult<()> {
+        let mut wanted: HashMap<&str, usize> = HashMap::new();
+        for id in target {
+            *wanted.entry(id).or_default() += 1;
+        }
+
+        // Copies beyond those wanted go by position, keeping the first ones of a duplicate.
+        let mut slots: Vec<Option<&str>> = Vec::with_capacity(contents.items.len());
+        let mut removed: Vec<(String, u32)> = Vec::new();
+        for (position, item) in contents.items.iter().enumerate() {
+            let Some(id) = item.id.as_deref() else {
+                slots.push(None);
+                continue;
+            };
+            match wanted.get_mut(id) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    slots.push(Some(id));
+                }
+                _ => removed.push((format!("spotify:track:{}", id), position as u32)),
+            }
+        }
+        if !removed.is_empty() {
+            self.spotify_client
+                .remove_tracks_from_playlist(
+                    access_token,
+                    playlist_id,
+                    &removed,
+                    contents.source_version.as_deref(),
+                )
+                .await?;
+        }
+
+        let mut index = 0;
+        while index < target.len() {
+            let position = Self::slot_position(&slots, index);
+            if slots.get(position) == Some(&Some(target[index])) {
+                index += 1;
+                continue;
+            }
+
+            if let Some(from) =
+                (position + 1..slots.len()).find(|from| slots[*from] == Some(target[index]))
+            {
+                self.spotify_client
+                    .reorder_playlist_track(access_token, playlist_id, from as u32, position as u32)
+                    .await?;
+                let slot = slots.remove(from);
+                slots.insert(position, slot);
+                index += 1;
+                continue;
+            }
+
+            // Consecutive tracks that are missing altogether go in with a single request.
+            let added: Vec<&str> = target[index..]
+                .iter()
+                .take_while(|id| !slots[position..].contains(&Some(**id)))
+                .copied()
+                .collect();
+            let uris: Vec<String> = added
+                .iter()
+                .map(|id| format!("spotify:track:{}", id))
+                .collect();
+            self.spotify_client
+                .add_tracks_to_playlist(access_token, playlist_id, &uris, Some(position as u32))
+                .await?;
+            for (offset, id) in added.iter().enumerate() {
+                slots.insert(position + offset, Some(id));
+            }
+            index += added.len();
+        }
+
+        Ok(())
+    }
+
+    async fn apply_to_youtube(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+        contents: &PlaylistContents,
+        target: &[&str],
+    ) -> SpotitubeResult<()> {
+        let mut wanted: HashMap<&str, usize> = HashMap::new();
+        for id in target {
+            *wanted.entry(id).or_default() += 1;
+        }
+
+        let mut slots: Vec<(Option<&str>, String)> = Vec::with_capacity(contents.items.len());
+        for item in &contents.items {
+            let item_id = item.item_id.clone().unwrap_or_default();
+            let Some(id) = item.id.as_deref() else {
+                slots.push((None, item_id));
+                continue;
+            };
+            match wanted.get_mut(id) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    slots.push((Some(id), item_id));
+                }
+                _ => {
+                    self.youtube_client
+                        .delete_playlist_item(access_token, &item_id)
+                        .await?
+                }
+            }
+        }
+
+        for (index, id) in target.iter().enumerate() {
+            let ids: Vec<Option<&str>> = slots.iter().map(|(id, _)| *id).collect();
+            let position = Self::slot_position(&ids, index);
+            if ids.get(position) == Some(&Some(*id)) {
+                continue;
+            }
+
+            match (position + 1..slots.len()).find(|from| slots[*from].0 == Some(*id)) {
+                Some(from) => {
+                    let slot = slots.remove(from);
+                    self.youtube_client
+                        .update_playlist_item(
+                            access_token,
+                            &slot.1,
+                            playlist_id,
+                            id,
+                            position as u32,
+                        )
+                        .await?;
+                    slots.insert(position, slot);
+                }
+                None => {
+                    let item = self
+                        .youtube_client
+                        .insert_playlist_item(access_token, playlist_id, id, Some(position as u32))
+                        .await?;
+                    slots.insert(position, (Some(*id), item.id));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Index in the playlist of the `index`-th track that can be moved.
+    fn slot_position<T>(slots: &[Option<T>], index: usize) -> usize {
+        slots
+            .iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.is_some())
+            .nth(index)
+            .map_or(slots.len(), |(position, _)| position)
+    }
+
+    async fn read_spotify_items(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+    ) -> SpotitubeResult<Vec<PlaylistItem>> {
+        let mut items = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = self
+                .spotify_client
+                .get_playlist_tracks(access_token, playlist_id, SPOTIFY_PAGE_SIZE, offset)
+                .await?;
+
+            for track in page
+                .items
+                .into_iter()
+                .filter_map(|item| Some(item.track?.into_track()))
+            {
+                items.push(PlaylistItem {
+                    id: track.external_id(Provider::Spotify).map(String::from),
+                    item_id: None,
+                    artists: track.artist_names(),
+                    title: track.title,
+                    album: track.album.map(|album| album.title),
+                    duration_ms: track.duration_ms,
+                    isrc: track.isrc,
+                });
+            }
+
+            match page.next {
+                Some(_) => offset += SPOTIFY_PAGE_SIZE,
+                None => return Ok(items),
+            }
+        }
+    }
+
+    async fn read_youtube_items(
+        &self,
+        access_token: &str,
+        playlist_id: &str,
+    ) -> SpotitubeResult<Vec<PlaylistItem>> {
+        let mut items = Vec::new();
+        let mut page_token = None;
+        loop {
+            let page = self
+                .youtube_client
+                .list_playlist_items(
+                    access_token,
+                    playlist_id,
+                    page_token.as_deref(),
+                    YOUTUBE_PAGE_SIZE,
+                )
+                .await?;
+
+            let video_ids: Vec<String> = page
+                .items
+                .iter()
+                .filter_map(|item| item.snippet.available_video_id().map(String::from))
+                .collect();
+            let durations: HashMap<String, i64> = match video_ids.is_empty() {
+                true => HashMap::new(),
+                false => self
+                    .youtube_client
+                    .get_videos(access_token, &video_ids)
+                    .await?
+                    .into_iter()
+                    .filter_map(|video| {
+                        let duration_ms = video.content_details?.duration_ms()?;
+                        Some((video.id, duration_ms))
+                    })
+                    .collect(),
+            };
+
+            for item in page.items {
+                let item_id = item.id.clone();
+                let track = item.into_track();
+                let video_id = track.external_id(Provider::YouTube).map(String::from);
+                items.push(PlaylistItem {
+                    duration_ms: video_id
+                        .as_ref()
+                        .and_then(|video_id| durations.get(video_id).copied()),
+                    id: video_id,
+                    item_id: Some(item_id),
+                    artists: track.artist_names(),
+                    title: track.title,
+                    album: None,
+                    isrc: None,
+                });
+            }
+
+            match page.next_page_token {
+                Some(next_page_token) => page_token = Some(next_page_token),
+                None => return Ok(items),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl PlaylistEditor for SpotitubePlaylistEditor {
+    async fn read_playlist(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+        playlist_id: &str,
+    ) -> SpotitubeResult<PlaylistContents> {
+        let access_token = self.token_manager.access_token(user_id, provider).await?;
+        match provider {
+            Provider::Spotify => {
+                let playlist = self
+                    .spotify_client
+                    .get_playlist(&access_token, playlist_id)
+                    .await?;
+                let source_version = Some(playlist.snapshot_id.clone());
+                let playlist = playlist.into_playlist(Vec::new());
+                Ok(PlaylistContents {
+                    name: playlist.name,
+                    description: playlist.description,
+                    source_version,
+                    items: self.read_spotify_items(&access_token, playlist_id).await?,
+                })
+            }
+            Provider::YouTube => {
+                let playlist = self
+                    .youtube_client
+                    .get_playlist(&access_token, playlist_id)
+                    .await?;
+                let source_version = playlist.etag.clone();
+                let playlist = playlist.into_playlist(Vec::new());
+                Ok(PlaylistContents {
+                    name: playlist.name,
+                    description: playlist.description,
+                    source_version,
+                    items: self.read_youtube_items(&access_token, playlist_id).await?,
+                })
+            }
+        }
+    }
+
+    async fn apply(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+        playlist_id: &str,
+        contents: &PlaylistContents,
+        target: &[&str],
+    ) -> SpotitubeResult<()> {
+        let access_token = self.token_manager.access_token(user_id, provider).await?;
+        match provider {
+            Provider::Spotify => {
+                self.apply_to_spotify(&access_token, playlist_id, contents, target)
+                    .await
+            }
+            Provider::YouTube => {
+                self.apply_to_youtube(&access_token, playlist_id, contents, target)
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use spotitube_core::{
+        accounts::token_manager::ProviderTokenManager,
+        errors::SpotitubeResult,
+        playlists::editor::{PlaylistContents, PlaylistEditor, PlaylistItem},
+        spotify::{
+            client::SpotifyClient,
+            models::{
+                SpotifyPage, SpotifyPlaylist, SpotifyPlaylistTrack, SpotifySavedTrack,
+                SpotifyTrack, SpotifyUser,
+            },
+        },
+        youtube::{
+            client::YouTubeClient,
+            models::{
+                YouTubeChannel, YouTubePage, YouTubePlaylist, YouTubePlaylistItem,
+                YouTubePlaylistItemSnippet, YouTubeQuotaUsage, YouTubeResourceId,
+                YouTubeSearchResult, YouTubeVideo,
+            },
+        },
+    };
+    use spotitube_domain::providers::Provider;
+    use uuid::Uuid;
+
+    use super::SpotitubePlaylistEditor;
+
+    struct FakeTokenManager;
+
+    #[async_trait]
+    impl ProviderTokenManager for FakeTokenManager {
+        async fn access_token(
+            &self,
+            _user_id: &Uuid,
+            _provider: Provider,
+        ) -> SpotitubeResult<String> {
+            Ok(String::from("token"))
+        }
+    }
+
+    /// Records the playlist edits it receives; the editor is not expected to read anything.
+    #[derive(Default)]
+    struct FakeClient {
+        requests: Mutex<Vec<String>>,
+    }
+
+    impl FakeClient {
+        fn record(&self, request: String) {
+            self.requests.lock().unwrap().push(request);
+        }
+    }
+
+    #[async_trait]
+    impl SpotifyClient for FakeClient {
+        async fn get_current_user(&self, _access_token: &str) -> SpotitubeResult<SpotifyUser> {
+            unimplemented!()
+        }
+
+        async fn get_current_user_playlists(
+            &self,
+            _access_token: &str,
+            _limit: u32,
+            _offset: u32,
+        ) -> SpotitubeResult<SpotifyPage<SpotifyPlaylist>> {
+            unimplemented!()
+        }
+
+        async fn get_playlist(
+            &self,
+            _access_token: &str,
+            _playlist_id: &str,
+        ) -> SpotitubeResult<SpotifyPlaylist> {
+            unimplemented!()
+        }
+
+        async fn get_playlist_tracks(
+            &self,
+            _access_token: &str,
+            _playlist_id: &str,
+            _limit: u32,
+            _offset: u32,
+        ) -> SpotitubeResult<SpotifyPage<SpotifyPlaylistTrack>> {
+            unimplemented!()
+        }
+
+        async fn get_saved_tracks(
+            &self,
+            _access_token: &str,
+            _limit: u32,
+            _offset: u32,
+        ) -> SpotitubeResult<SpotifyPage<SpotifySavedTrack>> {
+            unimplemented!()
+        }
+
+        async fn get_track(
+            &self,
+            _access_token: &str,
+            _track_id: &str,
+        ) -> SpotitubeResult<SpotifyTrack> {
+            unimplemented!()
+        }
+
+        async fn search_tracks(
+            &self,
+            _access_token: &str,
+            _query: &str,
+            _limit: u32,
+        ) -> SpotitubeResult<Vec<SpotifyTrack>> {
+            unimplemented!()
+        }
+
+        async fn create_playlist(
+            &self,
+            _access_token: &str,
+            _user_id: &str,
+            _name: &str,
+            _description: &str,
+            _public: bool,
+        ) -> SpotitubeResult<SpotifyPlaylist> {
+            unimplemented!()
+        }
+
+        async fn add_tracks_to_playlist(
+            &self,
+            _access_token: &str,
+            playlist_id: &str,
+            uris: &[String],
+            position: Option<u32>,
+        ) -> SpotitubeResult<String> {
+            self.record(format!(
+                "add {} to {} at {:?}",
+                uris.join(","),
+                playlist_id,
+                position
+            ));
+            Ok(String::from("snapshot"))
+        }
+
+        async fn remove_tracks_from_playlist(
+            &self,
+            _access_token: &str,
+            playlist_id: &str,
+            tracks: &[(String, u32)],
+            snapshot_id: Option<&str>,
+        ) -> SpotitubeResult<String> {
+            self.record(format!(
+                "remove {:?} from {} at {:?}",
+                tracks, playlist_id, snapshot_id
+            ));
+            Ok(String::from("snapshot"))
+        }
+
+        async fn reorder_playlist_track(
+            &self,
+            _access_token: &str,
+            playlist_id: &str,
+            range_start: u32,
+            insert_before: u32,
+        ) -> SpotitubeResult<String> {
+            self.record(format!(
+                "move {} before {} in {}",
+                range_start, insert_before, playlist_id
+            ));
+            Ok(String::from("snapshot"))
+        }
+    }
+
+    #[async_trait]
+    impl YouTubeClient for FakeClient {
+        async fn get_my_channel(&self, _access_token: &str) -> SpotitubeResult<YouTubeChannel> {
+            unimplemented!()
+        }
+
+        async fn list_playlists(
+            &self,
+            _access_token: &str,
+            _page_token: Option<&str>,
+            _max_results: u32,
+        ) -> SpotitubeResult<YouTubePage<YouTubePlaylist>> {
+            unimplemented!()
+        }
+
+        async fn get_playlist(
+            &self,
+            _access_token: &str,
+            _playlist_id: &str,
+        ) -> SpotitubeResult<YouTubePlaylist> {
+            unimplemented!()
+        }
+
+        async fn list_playlist_items(
+            &self,
+            _access_token: &str,
+            _playlist_id: &str,
+            _page_token: Option<&str>,
+            _max_results: u32,
+        ) -> SpotitubeResult<YouTubePage<YouTubePlaylistItem>> {
+            unimplemented!()
+        }
+
+        async fn search_videos(
+            &self,
+            _access_token: &str,
+            _query: &str,
+            _max_results: u32,
+        ) -> SpotitubeResult<Vec<YouTubeSearchResult>> {
+            unimplemented!()
+        }
+
+        async fn get_videos(
+            &self,
+            _access_token: &str,
+            _video_ids: &[String],
+        ) -> SpotitubeResult<Vec<YouTubeVideo>> {
+            unimplemented!()
+        }
+
+        async fn create_playlist(
+            &self,
+            _access_token: &str,
+            _title: &str,
+            _description: &str,
+            _privacy_status: &str,
+        ) -> SpotitubeResult<YouTubePlaylist> {
+            unimplemented!()
+        }
+
+        async fn insert_playlist_item(
+            &self,
+            _access_token: &str,
+            playlist_id: &str,
+            video_id: &str,
+            position: Option<u32>,
+        ) -> SpotitubeResult<YouTubePlaylistItem> {
+            self.record(format!(
+                "insert {} into {} at {:?}",
+                video_id, playlist_id, position
+            ));
+            Ok(playlist_item(playlist_id, video_id))
+        }
+
+        async fn update_playlist_item(
+            &self,
+            _access_token: &str,
+            item_id: &str,
+            playlist_id: &str,
+            video_id: &str,
+            position: u32,
+        ) -> SpotitubeResult<YouTubePlaylistItem> {
+            self.record(format!(
+                "move {} ({}) in {} to {}",
+                item_id, video_id, playlist_id, position
+            ));
+            Ok(playlist_item(playlist_id, video_id))
+        }
+
+        async fn delete_playlist_item(
+            &self,
+            _access_token: &str,
+            item_id: &str,
+        ) -> SpotitubeResult<()> {
+            self.record(format!("delete {}", item_id));
+            Ok(())
+        }
+
+        async fn quota_usage(&self) -> SpotitubeResult<YouTubeQuotaUsage> {
+            unimplemented!()
+        }
+    }
+
+    fn playlist_item(playlist_id: &str, video_id: &str) -> YouTubePlaylistItem {
+        YouTubePlaylistItem {
+            id: format!("new-{}", video_id),
+            snippet: YouTubePlaylistItemSnippet {
+                title: String::from(video_id),
+                description: String::new(),
+                playlist_id: String::from(playlist_id),
+                position: None,
+                resource_id: YouTubeResourceId {
+                    kind: String::from("youtube#video"),
+                    video_id: Some(String::from(video_id)),
+                },
+                video_owner_channel_title: None,
+            },
+        }
+    }
+
+    /// Playlist of the given ids, `None` standing for a local file or an unavailable video.
+    /// Each item is known on YouTube as `item-<position>`.
+    fn contents(ids: &[Option<&str>]) -> PlaylistContents {
+        PlaylistContents {
+            name: String::from("Road trip"),
+            description: None,
+            source_version: Some(String::from("read")),
+            items: ids
+                .iter()
+                .enumerate()
+                .map(|(position, id)| PlaylistItem {
+                    id: id.map(String::from),
+                    item_id: Some(format!("item-{}", position)),
+                    title: String::from(id.unwrap_or("local file")),
+                    artists: Vec::new(),
+                    album: None,
+                    duration_ms: None,
+                    isrc: None,
+                })
+                .collect(),
+        }
+    }
+
+    async fn apply(provider: Provider, ids: &[Option<&str>], target: &[&str]) -> Vec<String> {
+        let client = Arc::new(FakeClient::default());
+        let editor = SpotitubePlaylistEditor::new(
+            Arc::new(FakeTokenManager),
+            client.clone(),
+            client.clone(),
+        );
+
+        editor
+            .apply(&Uuid::nil(), provider, "road", &contents(ids), target)
+            .await
+            .unwrap();
+
+        let requests = client.requests.lock().unwrap();
+        requests.clone()
+    }
+
+    #[tokio::test]
+    async fn moves_a_track_on_spotify_with_one_request() {
+        let requests = apply(
+            Provider::Spotify,
+            &[Some("a"), Some("b"), Some("c")],
+            &["c", "a", "b"],
+        )
+        .await;
+
+        assert_eq!(requests, ["move 2 before 0 in road"]);
+    }
+
+    #[tokio::test]
+    async fn inserts_a_missing_track_on_spotify_at_its_position() {
+        let requests = apply(
+            Provider::Spotify,
+            &[Some("a"), Some("d")],
+            &["a", "b", "c", "d"],
+        )
+        .await;
+
+        assert_eq!(
+            requests,
+            ["add spotify:track:b,spotify:track:c to road at Some(1)"]
+        );
+    }
+
+    #[tokio::test]
+    async fn removes_the_later_copy_of_a_duplicate_on_spotify_by_position() {
+        let requests = apply(
+            Provider::Spotify,
+            &[Some("a"), Some("b"), Some("a")],
+            &["a", "b"],
+        )
+        .await;
+
+        assert_eq!(
+            requests,
+            [r#"remove [("spotify:track:a", 2)] from road at Some("read")"#]
+        );
+    }
+
+    #[tokio::test]
+    async fn leaves_a_local_file_in_place_on_spotify() {
+        let requests = apply(
+            Provider::Spotify,
+            &[Some("a"), None, Some("b")],
+            &["a", "c", "b"],
+        )
+        .await;
+
+        assert_eq!(requests, ["add spotify:track:c to road at Some(2)"]);
+    }
+
+    #[tokio::test]
+    async fn moves_a_video_on_youtube_with_one_request() {
+        let requests = apply(
+            Provider::YouTube,
+            &[Some("a"), Some("b"), Some("c")],
+            &["c", "a", "b"],
+        )
+        .await;
+
+        assert_eq!(requests, ["move item-2 (c) in road to 0"]);
+    }
+
+    #[tokio::test]
+    async fn inserts_missing_videos_on_youtube_at_their_position() {
+        let requests = apply(
+            Provider::YouTube,
+            &[Some("a"), Some("d")],
+            &["a", "b", "c", "d"],
+        )
+        .await;
+
+        assert_eq!(
+            requests,
+            [
+                "insert b into road at Some(1)",
+                "insert c into road at Some(2)"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn deletes_the_later_copy_of_a_duplicate_on_youtube() {
+        let requests = apply(
+            Provider::YouTube,
+            &[Some("a"), Some("b"), Some("a")],
+            &["a", "b"],
+        )
+        .await;
+
+        assert_eq!(requests, ["delete item-2"]);
+    }
+
+    #[tokio::test]
+    async fn leaves_an_unavailable_video_in_place_on_youtube() {
+        let requests = apply(
+            Provider::YouTube,
+            &[Some("a"), None, Some("b")],
+            &["a", "c", "b"],
+        )
+        .await;
+
+        assert_eq!(requests, ["insert c into road at Some(2)"]);
+    }
+}
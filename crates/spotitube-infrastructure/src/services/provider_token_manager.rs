@@ -0,0 +1,212 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use async_trait::async_trait;
+use spotitube_core::{
+    accounts::{
+        repository::{DynLinkedAccountsRepository, LinkedAccountEntity},
+        token_manager::ProviderTokenManager,
+    },
+    errors::{SpotitubeError, SpotitubeResult},
+    oauth::client::DynOAuthClient,
+    utils::encryption_service::DynEncryptionService,
+};
+use spotitube_domain::providers::Provider;
+use time::{Duration, OffsetDateTime};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Tokens expiring within this window are refreshed ahead of the client call.
+const EXPIRY_LEEWAY_SECONDS: i64 = 60;
+const REFRESH_LEASE_SECONDS: i64 = 30;
+const LEASE_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Refreshes are serialised per linked account twice over: an in-process mutex keeps local
+/// tasks from racing each other, and a lease column on the row keeps other processes out.
+pub struct SpotitubeProviderTokenManager {
+    repository: DynLinkedAccountsRepository,
+    encryption_service: DynEncryptionService,
+    clients: HashMap<Provider, DynOAuthClient>,
+    refresh_locks: Mutex<HashMap<Uuid, Arc<tokio::sync::Mutex<()>>>>,
+}
+
+impl SpotitubeProviderTokenManager {
+    pub fn new(
+        repository: DynLinkedAccountsRepository,
+        encryption_service: DynEncryptionService,
+        clients: HashMap<Provider, DynOAuthClient>,
+    ) -> Self {
+        Self {
+            repository,
+            encryption_service,
+            clients,
+            refresh_locks: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn is_fresh(account: &LinkedAccountEntity) -> bool {
+        account.expires_at > OffsetDateTime::now_utc() + Duration::seconds(EXPIRY_LEEWAY_SECONDS)
+    }
+
+    fn relink_required(provider: Provider) -> SpotitubeError {
+        SpotitubeError::ProviderUnauthorized(format!("{} account must be linked again", provider))
+    }
+
+    fn refresh_lock(&self, id: &Uuid) -> SpotitubeResult<Arc<tokio::sync::Mutex<()>>> {
+        let mut locks = self
+            .refresh_locks
+            .lock()
+            .map_err(|_| SpotitubeError::InternalServerError)?;
+        Ok(locks.entry(*id).or_default().clone())
+    }
+
+    fn release_refresh_lock(&self, id: &Uuid, lock: Arc<tokio::sync::Mutex<()>>) {
+        if let Ok(mut locks) = self.refresh_locks.lock() {
+            // one reference is held by the map and one by us, nobody else is waiting
+            if Arc::strong_count(&lock) == 2 {
+                locks.remove(id);
+            }
+        }
+    }
+
+    async fn refresh(&self, id: &Uuid, provider: Provider) -> SpotitubeResult<LinkedAccountEntity> {
+        let deadline =
+            Instant::now() + std::time::Duration::from_secs(2 * REFRESH_LEASE_SECONDS as u64);
+
+        loop {
+            let locked_until = OffsetDateTime::now_utc() + Duration::seconds(REFRESH_LEASE_SECONDS);
+            if let Some(account) = self
+                .repository
+                .acquire_refresh_lease(id, locked_until)
+                .await?
+            {
+                if account.needs_relink {
+                    self.repository.release_refresh_lease(id).await?;
+                    return Err(Self::relink_required(provider));
+                }
+                if Self::is_fresh(&account) {
+                    self.repository.release_refresh_lease(id).await?;
+                    return Ok(account);
+                }
+
+                return self.refresh_with_lease(account, provider).await;
+            }
+
+            // another process holds the lease, wait for it to store the new tokens
+            let account = self
+                .repository
+                .get_linked_account_by_id(id)
+                .await?
+                .ok_or_else(|| {
+                    SpotitubeError::NotFound(format!("no {} account is linked", provider))
+                })?;
+            if account.needs_relink {
+                return Err(Self::relink_required(provider));
+            }
+            if Self::is_fresh(&account) {
+                return Ok(account);
+            }
+            if Instant::now() >= deadline {
+                return Err(SpotitubeError::ProviderError(format!(
+                    "timed out waiting for {} token refresh",
+                    provider
+                )));
+            }
+
+            tokio::time::sleep(LEASE_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn refresh_with_lease(
+        &self,
+        account: LinkedAccountEntity,
+        provider: Provider,
+    ) -> SpotitubeResult<LinkedAccountEntity> {
+        let Some(refresh_token) = account.refresh_token.as_deref() else {
+            warn!("{} account {:?} has no refresh token", provider, account.id);
+            self.repository.mark_needs_relink(&account.id).await?;
+            return Err(Self::relink_required(provider));
+        };
+
+        let result = match self.clients.get(&provider) {
+            Some(client) => match self.encryption_service.decrypt(refresh_token) {
+                Ok(refresh_token) => client.refresh_access_token(&refresh_token).await,
+                Err(err) => Err(err),
+            },
+            None => Err(SpotitubeError::BadRequest(format!(
+                "{} is not supported",
+                provider
+            ))),
+        };
+
+        let tokens = match result {
+            Ok(tokens) => tokens,
+            Err(SpotitubeError::ProviderUnauthorized(reason)) => {
+                warn!(
+                    "{} account {:?} could not be refreshed: {}",
+                    provider, account.id, reason
+                );
+                self.repository.mark_needs_relink(&account.id).await?;
+                return Err(Self::relink_required(provider));
+            }
+            Err(err) => {
+                self.repository.release_refresh_lease(&account.id).await?;
+                return Err(err);
+            }
+        };
+
+        let access_token = self.encryption_service.encrypt(&tokens.access_token)?;
+        let refresh_token = tokens
+            .refresh_token
+            .as_deref()
+            .map(|token| self.encryption_service.encrypt(token))
+            .transpose()?;
+
+        let account = self
+            .repository
+            .update_refreshed_tokens(
+                &account.id,
+                &access_token,
+                refresh_token.as_deref(),
+                OffsetDateTime::now_utc() + Duration::seconds(tokens.expires_in),
+            )
+            .await?;
+
+        info!(
+            "refreshed {} access token of account {:?}",
+            provider, account.id
+        );
+        Ok(account)
+    }
+}
+
+#[async_trait]
+impl ProviderTokenManager for SpotitubeProviderTokenManager {
+    async fn access_token(&self, user_id: &Uuid, provider: Provider) -> SpotitubeResult<String> {
+        let account = self
+            .repository
+            .get_linked_account(user_id, provider.as_str())
+            .await?
+            .ok_or_else(|| {
+                SpotitubeError::NotFound(format!("no {} account is linked", provider))
+            })?;
+
+        if account.needs_relink {
+            return Err(Self::relink_required(provider));
+        }
+        if Self::is_fresh(&account) {
+            return self.encryption_service.decrypt(&account.access_token);
+        }
+
+        let lock = self.refresh_lock(&account.id)?;
+        let guard = lock.lock().await;
+        let result = self.refresh(&account.id, provider).await;
+        drop(guard);
+        self.release_refresh_lock(&account.id, lock);
+
+        self.encryption_service.decrypt(&result?.access_token)
+    }
+}
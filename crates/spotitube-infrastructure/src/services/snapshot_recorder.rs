@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use spotitube_core::{
+    errors::SpotitubeResult,
+    playlists::editor::DynPlaylistEditor,
+    snapshots::{
+        recorder::SnapshotRecorder,
+        repository::{
+            DynSnapshotsRepository, NewPlaylistSnapshot, NewSnapshotTrack, PlaylistSnapshotEntity,
+        },
+    },
+};
+use spotitube_domain::{providers::Provider, snapshots::SnapshotOrigin};
+use tracing::info;
+use uuid::Uuid;
+
+pub struct SpotitubeSnapshotRecorder {
+    repository: DynSnapshotsRepository,
+    editor: DynPlaylistEditor,
+}
+
+impl SpotitubeSnapshotRecorder {
+    pub fn new(repository: DynSnapshotsRepository, editor: DynPlaylistEditor) -> Self {
+        Self { repository, editor }
+    }
+
+    async fn is_unchanged(
+        &self,
+        latest: &PlaylistSnapshotEntity,
+        snapshot: &NewPlaylistSnapshot,
+        tracks: &[NewSnapshotTrack],
+    ) -> SpotitubeResult<bool> {
+        if latest.name != snapshot.name
+            || latest.description != snapshot.description
+            || latest.track_count as usize != tracks.len()
+        {
+            return Ok(false);
+        }
+
+        let latest_tracks = self.repository.get_snapshot_tracks(&latest.id).await?;
+        Ok(latest_tracks
+            .into_iter()
+            .map(|track| NewSnapshotTrack {
+                provider_id: track.provider_id,
+                title: track.title,
+                artists: track.artists,
+                album: track.album,
+                duration_ms: track.duration_ms,
+                isrc: track.isrc,
+            })
+            .eq(tracks.iter().cloned()))
+    }
+}
+
+#[async_trait]
+impl SnapshotRecorder for SpotitubeSnapshotRecorder {
+    async fn record(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+        playlist_id: &str,
+        origin: SnapshotOrigin,
+    ) -> SpotitubeResult<PlaylistSnapshotEntity> {
+        let contents = self
+            .editor
+            .read_playlist(user_id, provider, playlist_id)
+            .await?;
+        let snapshot = NewPlaylistSnapshot {
+            user_id: *user_id,
+            provider: String::from(provider.as_str()),
+            playlist_id: String::from(playlist_id),
+            origin: String::from(origin.as_str()),
+            name: contents.name,
+            description: contents.description,
+            source_version: contents.source_version,
+        };
+        let tracks: Vec<NewSnapshotTrack> = contents
+            .items
+            .into_iter()
+            .map(|item| NewSnapshotTrack {
+                provider_id: item.id,
+                title: item.title,
+                artists: item.artists,
+                album: item.album,
+                duration_ms: item.duration_ms,
+                isrc: item.isrc,
+            })
+            .collect();
+
+        if let Some(latest) = self
+            .repository
+            .get_latest_snapshot(user_id, provider.as_str(), playlist_id)
+            .await?
+        {
+            if self.is_unchanged(&latest, &snapshot, &tracks).await? {
+                return Ok(latest);
+            }
+        }
+
+        let created = self.repository.create_snapshot(&snapshot, &tracks).await?;
+        info!(
+            "recorded version {} of {} playlist {:?}",
+            created.version, provider, playlist_id
+        );
+        Ok(created)
+    }
+}
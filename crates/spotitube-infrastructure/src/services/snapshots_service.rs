@@ -0,0 +1,162 @@
+use async_trait::async_trait;
+use spotitube_core::{
+    errors::{SpotitubeError, SpotitubeResult},
+    playlists::editor::DynPlaylistEditor,
+    snapshots::{
+        diff::diff_snapshots,
+        recorder::DynSnapshotRecorder,
+        repository::{DynSnapshotsRepository, PlaylistSnapshotEntity, SnapshotTrackEntity},
+        service::SnapshotsService,
+    },
+};
+use spotitube_domain::{
+    providers::Provider,
+    snapshots::{PlaylistSnapshotDto, SnapshotDiffDto, SnapshotOrigin},
+};
+use tracing::info;
+use uuid::Uuid;
+
+pub struct SpotitubeSnapshotsService {
+    repository: DynSnapshotsRepository,
+    recorder: DynSnapshotRecorder,
+    editor: DynPlaylistEditor,
+}
+
+impl SpotitubeSnapshotsService {
+    pub fn new(
+        repository: DynSnapshotsRepository,
+        recorder: DynSnapshotRecorder,
+        editor: DynPlaylistEditor,
+    ) -> Self {
+        Self {
+            repository,
+            recorder,
+            editor,
+        }
+    }
+
+    async fn get_user_snapshot(
+        &self,
+        user_id: &Uuid,
+        id: &Uuid,
+    ) -> SpotitubeResult<PlaylistSnapshotEntity> {
+        self.repository
+            .get_snapshot(id)
+            .await?
+            .filter(|snapshot| snapshot.user_id == *user_id)
+            .ok_or_else(|| SpotitubeError::NotFound(String::from("snapshot not found")))
+    }
+}
+
+#[async_trait]
+impl SnapshotsService for SpotitubeSnapshotsService {
+    async fn create_snapshot(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+        playlist_id: &str,
+    ) -> SpotitubeResult<PlaylistSnapshotDto> {
+        let snapshot = self
+            .recorder
+            .record(user_id, provider, playlist_id, SnapshotOrigin::Import)
+            .await?;
+        let tracks = self.repository.get_snapshot_tracks(&snapshot.id).await?;
+        snapshot.into_dto(Some(tracks))
+    }
+
+    async fn list_snapshots(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+        playlist_id: &str,
+    ) -> SpotitubeResult<Vec<PlaylistSnapshotDto>> {
+        self.repository
+            .get_playlist_snapshots(user_id, provider.as_str(), playlist_id)
+            .await?
+            .into_iter()
+            .map(|snapshot| snapshot.into_dto(None))
+            .collect()
+    }
+
+    async fn get_snapshot(
+        &self,
+        user_id: &Uuid,
+        id: &Uuid,
+    ) -> SpotitubeResult<PlaylistSnapshotDto> {
+        let snapshot = self.get_user_snapshot(user_id, id).await?;
+        let tracks = self.repository.get_snapshot_tracks(id).await?;
+        snapshot.into_dto(Some(tracks))
+    }
+
+    async fn diff_snapshots(
+        &self,
+        user_id: &Uuid,
+        from: &Uuid,
+        to: &Uuid,
+    ) -> SpotitubeResult<SnapshotDiffDto> {
+        let from_snapshot = self.get_user_snapshot(user_id, from).await?;
+        let to_snapshot = self.get_user_snapshot(user_id, to).await?;
+        if from_snapshot.provider != to_snapshot.provider
+            || from_snapshot.playlist_id != to_snapshot.playlist_id
+        {
+            return Err(SpotitubeError::BadRequest(String::from(
+                "snapshots are of different playlists",
+            )));
+        }
+
+        let from_tracks: Vec<_> = self
+            .repository
+            .get_snapshot_tracks(from)
+            .await?
+            .into_iter()
+            .map(SnapshotTrackEntity::into_dto)
+            .collect();
+        let to_tracks: Vec<_> = self
+            .repository
+            .get_snapshot_tracks(to)
+            .await?
+            .into_iter()
+            .map(SnapshotTrackEntity::into_dto)
+            .collect();
+
+        Ok(diff_snapshots(*from, &from_tracks, *to, &to_tracks))
+    }
+
+    async fn restore_snapshot(
+        &self,
+        user_id: &Uuid,
+        id: &Uuid,
+    ) -> SpotitubeResult<PlaylistSnapshotDto> {
+        let snapshot = self.get_user_snapshot(user_id, id).await?;
+        let provider = snapshot.provider()?;
+        let tracks = self.repository.get_snapshot_tracks(id).await?;
+
+        let contents = self
+            .editor
+            .read_playlist(user_id, provider, &snapshot.playlist_id)
+            .await?;
+        let target: Vec<&str> = tracks
+            .iter()
+            .filter_map(|track| track.provider_id.as_deref())
+            .collect();
+        self.editor
+            .apply(user_id, provider, &snapshot.playlist_id, &contents, &target)
+            .await?;
+        info!(
+            "restored {} playlist {:?} to version {}",
+            provider, snapshot.playlist_id, snapshot.version
+        );
+
+        let restored = self
+            .recorder
+            .record(
+                user_id,
+                provider,
+                &snapshot.playlist_id,
+                SnapshotOrigin::Restore,
+            )
+            .await?;
+        let tracks = self.repository.get_snapshot_tracks(&restored.id).await?;
+        restored.into_dto(Some(tracks))
+    }
+}
@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use spotitube_core::{
+    errors::{SpotitubeError, SpotitubeResult},
+    jobs::{handler::JobHandler, repository::JobEntity},
+    syncs::runner::{DynSyncRunner, SyncJob},
+};
+use tracing::error;
+
+/// Runs playlist pair syncs queued as background jobs.
+pub struct SyncJobHandler {
+    runner: DynSyncRunner,
+}
+
+impl SyncJobHandler {
+    pub fn new(runner: DynSyncRunner) -> Self {
+        Self { runner }
+    }
+
+    fn payload(job: &JobEntity) -> SpotitubeResult<SyncJob> {
+        serde_json::from_value(job.payload.clone()).map_err(|err| {
+            error!("job {:?} has an invalid payload: {:?}", job.id, err);
+            SpotitubeError::BadRequest(String::from("invalid sync job"))
+        })
+    }
+}
+
+#[async_trait]
+impl JobHandler for SyncJobHandler {
+    async fn handle(&self, job: &JobEntity) -> SpotitubeResult<()> {
+        let payload = Self::payload(job)?;
+        self.runner.sync(&payload.sync_id).await
+    }
+
+    async fn dead_lettered(&self, job: &JobEntity, err: &SpotitubeError) -> SpotitubeResult<()> {
+        let payload = Self::payload(job)?;
+        self.runner.fail(&payload.sync_id, err).await
+    }
+}
@@ -0,0 +1,789 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    sync::Arc,
+};
+
+use async_trait::async_trait;
+use spotitube_core::{
+    config::AppConfig,
+    errors::{SpotitubeError, SpotitubeResult},
+    matching::resolver::{DynTrackResolver, TrackResolution},
+    playlists::editor::{DynPlaylistEditor, PlaylistItem},
+    snapshots::recorder::DynSnapshotRecorder,
+    syncs::{
+        merge::{merge, Merge, MergeConflict},
+        repository::{
+            DynSyncsRepository, NewSyncConflict, NewSyncEntry, PlaylistSyncEntity, SyncEntryEntity,
+        },
+        runner::SyncRunner,
+    },
+};
+use spotitube_domain::{providers::Provider, snapshots::SnapshotOrigin, syncs::SyncConflictKind};
+use time::{Duration, OffsetDateTime};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// A track in the three-way merge: one from the last synced version, or one added since on
+/// either side, by its index in the playlist items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Token {
+    Entry(usize),
+    Spotify(usize),
+    YouTube(usize),
+}
+
+/// A track of the merged pair, with its id on each side once known.
+struct MergedEntry {
+    origin: Token,
+    spotify: Option<String>,
+    youtube: Option<String>,
+    title: String,
+}
+
+pub struct SpotitubeSyncRunner {
+    repository: DynSyncsRepository,
+    resolver: DynTrackResolver,
+    editor: DynPlaylistEditor,
+    snapshot_recorder: DynSnapshotRecorder,
+    config: Arc<AppConfig>,
+}
+
+impl SpotitubeSyncRunner {
+    pub fn new(
+        repository: DynSyncsRepository,
+        resolver: DynTrackResolver,
+        editor: DynPlaylistEditor,
+        snapshot_recorder: DynSnapshotRecorder,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        Self {
+            repository,
+            resolver,
+            editor,
+            snapshot_recorder,
+            config,
+        }
+    }
+
+    /// Syncs that started before this are assumed to have died with their worker.
+    fn stale_before(&self) -> OffsetDateTime {
+        OffsetDateTime::now_utc() - Duration::seconds(self.config.job_visibility_timeout)
+    }
+
+    async fn run(&self, sync: &PlaylistSyncEntity) -> SpotitubeResult<()> {
+        let synced = self.repository.get_sync_entries(&sync.id).await?;
+        let spotify_contents = self
+            .editor
+            .read_playlist(&sync.user_id, Provider::Spotify, &sync.spotify_playlist_id)
+            .await?;
+        let youtube_contents = self
+            .editor
+            .read_playlist(&sync.user_id, Provider::YouTube, &sync.youtube_playlist_id)
+            .await?;
+        let spotify_items = &spotify_contents.items;
+        let youtube_items = &youtube_contents.items;
+
+        let spotify_tokens = Self::side_tokens(
+            &synced,
+            |entry| entry.spotify_track_id.as_deref(),
+            spotify_items,
+            Token::Spotify,
+        );
+        let youtube_tokens = Self::side_tokens(
+            &synced,
+            |entry| entry.youtube_video_id.as_deref(),
+            youtube_items,
+            Token::YouTube,
+        );
+        let merged = Self::merge_tokens(synced.len(), spotify_tokens, youtube_tokens);
+
+        let mut entries: Vec<MergedEntry> = merged
+            .items
+            .into_iter()
+            .map(|token| Self::merged_entry(token, &synced, spotify_items, youtube_items))
+            .collect();
+        Self::pair_added_tracks(
+            &self.resolver,
+            &sync.user_id,
+            &mut entries,
+            spotify_items,
+            youtube_items,
+        )
+        .await?;
+
+        let spotify_target: Vec<&str> = entries
+            .iter()
+            .filter_map(|entry| entry.spotify.as_deref())
+            .collect();
+        self.editor
+            .apply(
+                &sync.user_id,
+                Provider::Spotify,
+                &sync.spotify_playlist_id,
+                &spotify_contents,
+                &spotify_target,
+            )
+            .await?;
+        let youtube_target: Vec<&str> = entries
+            .iter()
+            .filter_map(|entry| entry.youtube.as_deref())
+            .collect();
+        self.editor
+            .apply(
+                &sync.user_id,
+                Provider::YouTube,
+                &sync.youtube_playlist_id,
+                &youtube_contents,
+                &youtube_target,
+            )
+            .await?;
+
+        let conflicts = Self::conflicts(&merged.conflicts, &entries, |token| match token {
+            Token::Entry(index) => synced[index].title.clone(),
+            Token::Spotify(index) => spotify_items[index].title.clone(),
+            Token::YouTube(index) => youtube_items[index].title.clone(),
+        });
+        self.repository
+            .replace_sync_entries(
+                &sync.id,
+                &entries
+                    .into_iter()
+                    .map(|entry| NewSyncEntry {
+                        spotify_track_id: entry.spotify,
+                        youtube_video_id: entry.youtube,
+                        title: entry.title,
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .await?;
+        for conflict in &conflicts {
+            self.repository
+                .create_sync_conflict(&sync.id, conflict)
+                .await?;
+        }
+
+        info!(
+            "synced playlist pair {:?} with {} conflicts",
+            sync.id,
+            conflicts.len()
+        );
+
+        for (provider, playlist_id) in [
+            (Provider::Spotify, &sync.spotify_playlist_id),
+            (Provider::YouTube, &sync.youtube_playlist_id),
+        ] {
+            // The sync itself went through; a missing version is caught up by the next one.
+            if let Err(err) = self
+                .snapshot_recorder
+                .record(&sync.user_id, provider, playlist_id, SnapshotOrigin::Sync)
+                .await
+            {
+                warn!(
+                    "failed to snapshot {} playlist {:?}: {:?}",
+                    provider, playlist_id, err
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// The tracks of one side in order, as entries of the last synced version where they match
+    /// one and as additions otherwise. Entries this side never had, because no match was found
+    /// for them, are put back where they were so they do not count as removed.
+    fn side_tokens<'a>(
+        entries: &'a [SyncEntryEntity],
+        id_of: impl Fn(&'a SyncEntryEntity) -> Option<&'a str>,
+        items: &[PlaylistItem],
+        added: fn(usize) -> Token,
+    ) -> Vec<Token> {
+        let mut known: HashMap<&str, VecDeque<usize>> = HashMap::new();
+        for (index, entry) in entries.iter().enumerate() {
+            if let Some(id) = id_of(entry) {
+                known.entry(id).or_default().push_back(index);
+            }
+        }
+
+        let mut tokens: Vec<Token> = items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                let id = item.id.as_deref()?;
+                Some(
+                    match known.get_mut(id).and_then(|indexes| indexes.pop_front()) {
+                        Some(entry) => Token::Entry(entry),
+                        None => added(index),
+                    },
+                )
+            })
+            .collect();
+
+        for (index, _) in entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| id_of(entry).is_none())
+        {
+            let position = (0..index)
+                .rev()
+                .find_map(|previous| {
+                    tokens
+                        .iter()
+                        .position(|token| *token == Token::Entry(previous))
+                })
+                .map_or(0, |position| position + 1);
+            tokens.insert(position, Token::Entry(index));
+        }
+
+        tokens
+    }
+
+    /// Merges the order of both sides over the `entries` tracks of the last synced version.
+    /// A removal on one side applies as it is and only the order of what is left is merged, so
+    /// a track removed next to one moved on the other side is not a conflict. A track removed
+    /// on both sides stays in the base, so that both replacing it differently is a conflict.
+    fn merge_tokens(
+        entries: usize,
+        spotify_tokens: Vec<Token>,
+        youtube_tokens: Vec<Token>,
+    ) -> Merge<Token> {
+        let on_spotify: HashSet<Token> = spotify_tokens.iter().copied().collect();
+        let on_youtube: HashSet<Token> = youtube_tokens.iter().copied().collect();
+        let is_kept = |token: &Token| {
+            !matches!(token, Token::Entry(_))
+                || on_spotify.contains(token) == on_youtube.contains(token)
+        };
+        let base: Vec<Token> = (0..entries).map(Token::Entry).filter(is_kept).collect();
+        let spotify_tokens: Vec<Token> = spotify_tokens.into_iter().filter(is_kept).collect();
+        let youtube_tokens: Vec<Token> = youtube_tokens.into_iter().filter(is_kept).collect();
+        let mut merged = merge(&base, &spotify_tokens, &youtube_tokens);
+
+        // A track moved on one side while the other changed around it can come out twice.
+        let mut seen = HashSet::new();
+        merged.items.retain(|token| seen.insert(*token));
+        merged
+    }
+
+    fn merged_entry(
+        token: Token,
+        entries: &[SyncEntryEntity],
+        spotify_items: &[PlaylistItem],
+        youtube_items: &[PlaylistItem],
+    ) -> MergedEntry {
+        match token {
+            Token::Entry(index) => MergedEntry {
+                origin: token,
+                spotify: entries[index].spotify_track_id.clone(),
+                youtube: entries[index].youtube_video_id.clone(),
+                title: entries[index].title.clone(),
+            },
+            Token::Spotify(index) => MergedEntry {
+                origin: token,
+                spotify: spotify_items[index].id.clone(),
+                youtube: None,
+                title: spotify_items[index].title.clone(),
+            },
+            Token::YouTube(index) => MergedEntry {
+                origin: token,
+                spotify: None,
+                youtube: youtube_items[index].id.clone(),
+                title: youtube_items[index].title.clone(),
+            },
+        }
+    }
+
+    /// Finds the counterpart of every track added on one side. When the same song was added
+    /// on both sides the two are paired instead of being copied across twice.
+    async fn pair_added_tracks(
+        resolver: &DynTrackResolver,
+        user_id: &Uuid,
+        entries: &mut Vec<MergedEntry>,
+        spotify_items: &[PlaylistItem],
+        youtube_items: &[PlaylistItem],
+    ) -> SpotitubeResult<()> {
+        let mut paired = HashSet::new();
+        for index in 0..entries.len() {
+            let Token::Spotify(item) = entries[index].origin else {
+                continue;
+            };
+            let TrackResolution::Matched(resolved) = resolver
+                .resolve(
+                    user_id,
+                    Provider::Spotify,
+                    &spotify_items[item].source_track(),
+                )
+                .await?
+            else {
+                continue;
+            };
+
+            let twin = entries.iter().position(|entry| {
+                matches!(entry.origin, Token::YouTube(_))
+                    && entry.youtube.as_deref() == Some(resolved.id.as_str())
+            });
+            if let Some(twin) = twin {
+                paired.insert(twin);
+            }
+            entries[index].youtube = Some(resolved.id);
+        }
+
+        for index in 0..entries.len() {
+            let Token::YouTube(item) = entries[index].origin else {
+                continue;
+            };
+            if paired.contains(&index) {
+                continue;
+            }
+            let TrackResolution::Matched(resolved) = resolver
+                .resolve(
+                    user_id,
+                    Provider::YouTube,
+                    &youtube_items[item].source_track(),
+                )
+                .await?
+            else {
+                continue;
+            };
+
+            let twin = entries.iter().position(|entry| {
+                matches!(entry.origin, Token::Spotify(_))
+                    && entry.youtube.is_none()
+                    && entry.spotify.as_deref() == Some(resolved.id.as_str())
+            });
+            match twin {
+                Some(twin) => {
+                    entries[twin].youtube = entries[index].youtube.take();
+                    paired.insert(index);
+                }
+                None => entries[index].spotify = Some(resolved.id),
+            }
+        }
+
+        let mut index = 0;
+        entries.retain(|_| {
+            index += 1;
+            !paired.contains(&(index - 1))
+        });
+        Ok(())
+    }
+
+    /// Position conflicts from the merge and additions that could not be copied across.
+    fn conflicts(
+        merge_conflicts: &[MergeConflict<Token>],
+        entries: &[MergedEntry],
+        title_of: impl Fn(Token) -> String,
+    ) -> Vec<NewSyncConflict> {
+        let positions: HashMap<Token, usize> = entries
+            .iter()
+            .enumerate()
+            .map(|(position, entry)| (entry.origin, position))
+            .collect();
+        let titles_of = |tokens: &[Token]| -> Vec<String> {
+            tokens.iter().map(|token| title_of(*token)).collect()
+        };
+
+        let mut conflicts: Vec<NewSyncConflict> = merge_conflicts
+            .iter()
+            .map(|conflict| NewSyncConflict {
+                kind: SyncConflictKind::Position,
+                position: conflict
+                    .left
+                    .iter()
+                    .chain(&conflict.right)
+                    .find_map(|token| positions.get(token))
+                    .copied()
+                    .unwrap_or(conflict.position) as i32,
+                base_titles: titles_of(&conflict.base),
+                spotify_titles: titles_of(&conflict.left),
+                youtube_titles: titles_of(&conflict.right),
+            })
+            .collect();
+
+        for (position, entry) in entries.iter().enumerate() {
+            let unmatched = match entry.origin {
+                Token::Entry(_) => false,
+                _ => entry.spotify.is_none() || entry.youtube.is_none(),
+            };
+            if !unmatched {
+                continue;
+            }
+
+            let title = vec![entry.title.clone()];
+            let (spotify_titles, youtube_titles) = match entry.spotify {
+                Some(_) => (title, Vec::new()),
+                None => (Vec::new(), title),
+            };
+            conflicts.push(NewSyncConflict {
+                kind: SyncConflictKind::Unmatched,
+                position: position as i32,
+                base_titles: Vec::new(),
+                spotify_titles,
+                youtube_titles,
+            });
+        }
+
+        conflicts
+    }
+}
+
+#[async_trait]
+impl SyncRunner for SpotitubeSyncRunner {
+    async fn sync(&self, sync_id: &Uuid) -> SpotitubeResult<()> {
+        let Some(sync) = self.repository.get_sync(sync_id).await? else {
+            info!("playlist pair {:?} is no longer synced", sync_id);
+            return Ok(());
+        };
+        if !self
+            .repository
+            .start_sync(sync_id, self.stale_before())
+            .await?
+        {
+            info!("playlist pair {:?} is already being synced", sync_id);
+            return Ok(());
+        }
+
+        match self.run(&sync).await {
+            Ok(()) => self.repository.finish_sync(sync_id, None).await,
+            Err(err) => {
+                warn!("sync of playlist pair {:?} stopped: {:?}", sync_id, err);
+                self.repository
+                    .finish_sync(sync_id, Some(&err.user_message()))
+                    .await?;
+                Err(err)
+            }
+        }
+    }
+
+    async fn fail(&self, sync_id: &Uuid, err: &SpotitubeError) -> SpotitubeResult<()> {
+        error!("sync of playlist pair {:?} failed: {:?}", sync_id, err);
+        self.repository
+            .finish_sync(sync_id, Some(&err.user_message()))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::{HashMap, HashSet},
+        sync::Arc,
+    };
+
+    use async_trait::async_trait;
+    use spotitube_core::{
+        errors::SpotitubeResult,
+        matching::{
+            matcher::MatchScore,
+            resolver::{
+                DynTrackResolver, ResolvedTrack, SourceTrack, TrackResolution, TrackResolver,
+            },
+        },
+        playlists::editor::PlaylistItem,
+        syncs::{merge::MergeConflict, repository::SyncEntryEntity},
+    };
+    use spotitube_domain::providers::Provider;
+    use uuid::Uuid;
+
+    use super::{SpotitubeSyncRunner, Token};
+
+    /// Resolves the tracks it was given a counterpart for and finds nothing for the others.
+    struct FakeResolver {
+        matches: HashMap<(Provider, &'static str), &'static str>,
+    }
+
+    #[async_trait]
+    impl TrackResolver for FakeResolver {
+        async fn resolve(
+            &self,
+            _user_id: &Uuid,
+            source: Provider,
+            track: &SourceTrack<'_>,
+        ) -> SpotitubeResult<TrackResolution> {
+            let resolved = track
+                .id
+                .and_then(|id| self.matches.get(&(source, id)))
+                .map(|id| ResolvedTrack {
+                    id: String::from(*id),
+                    title: String::from(*id),
+                    score: MatchScore {
+                        confidence: 1.0,
+                        reasons: Vec::new(),
+                    },
+                });
+            Ok(match resolved {
+                Some(resolved) => TrackResolution::Matched(resolved),
+                None => TrackResolution::NotFound,
+            })
+        }
+    }
+
+    fn entry(spotify: Option<&str>, youtube: Option<&str>) -> SyncEntryEntity {
+        SyncEntryEntity {
+            id: Uuid::new_v4(),
+            sync_id: Uuid::nil(),
+            position: 0,
+            spotify_track_id: spotify.map(String::from),
+            youtube_video_id: youtube.map(String::from),
+            title: String::from(spotify.or(youtube).unwrap_or_default()),
+        }
+    }
+
+    fn item(id: Option<&str>) -> PlaylistItem {
+        PlaylistItem {
+            id: id.map(String::from),
+            item_id: None,
+            title: String::from(id.unwrap_or("local file")),
+            artists: Vec::new(),
+            album: None,
+            duration_ms: None,
+            isrc: None,
+        }
+    }
+
+    fn spotify_tokens(entries: &[SyncEntryEntity], items: &[PlaylistItem]) -> Vec<Token> {
+        SpotitubeSyncRunner::side_tokens(
+            entries,
+            |entry| entry.spotify_track_id.as_deref(),
+            items,
+            Token::Spotify,
+        )
+    }
+
+    #[test]
+    fn side_tokens_follow_the_playlist_order() {
+        let entries = [
+            entry(Some("s1"), None),
+            entry(Some("s2"), None),
+            entry(Some("s3"), None),
+        ];
+        let items = [
+            item(Some("s3")),
+            item(None),
+            item(Some("s1")),
+            item(Some("s4")),
+        ];
+
+        assert_eq!(
+            spotify_tokens(&entries, &items),
+            [Token::Entry(2), Token::Entry(0), Token::Spotify(3)]
+        );
+    }
+
+    #[test]
+    fn side_tokens_count_copies_of_a_duplicate() {
+        let entries = [entry(Some("s1"), None), entry(Some("s1"), None)];
+        let items = [item(Some("s1")), item(Some("s1")), item(Some("s1"))];
+
+        assert_eq!(
+            spotify_tokens(&entries, &items),
+            [Token::Entry(0), Token::Entry(1), Token::Spotify(2)]
+        );
+    }
+
+    #[test]
+    fn side_tokens_keep_unmatched_entries_in_place() {
+        let entries = [
+            entry(None, Some("v0")),
+            entry(Some("s1"), Some("v1")),
+            entry(None, Some("v2")),
+            entry(Some("s3"), Some("v3")),
+        ];
+        let items = [item(Some("s3")), item(Some("s1"))];
+
+        assert_eq!(
+            spotify_tokens(&entries, &items),
+            [
+                Token::Entry(0),
+                Token::Entry(3),
+                Token::Entry(1),
+                Token::Entry(2)
+            ]
+        );
+    }
+
+    #[test]
+    fn merges_concurrent_changes_on_both_sides() {
+        // Spotify moved the last entry to the front and added a track at the end, while
+        // YouTube removed the second entry and added a video after the first.
+        let spotify = vec![
+            Token::Entry(3),
+            Token::Entry(0),
+            Token::Entry(1),
+            Token::Entry(2),
+            Token::Spotify(4),
+        ];
+        let youtube = vec![
+            Token::Entry(0),
+            Token::YouTube(1),
+            Token::Entry(2),
+            Token::Entry(3),
+        ];
+
+        let merged = SpotitubeSyncRunner::merge_tokens(4, spotify, youtube);
+
+        assert_eq!(
+            merged.items,
+            [
+                Token::Entry(3),
+                Token::Entry(0),
+                Token::YouTube(1),
+                Token::Entry(2),
+                Token::Spotify(4)
+            ]
+        );
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn reports_a_track_replaced_differently_on_both_sides() {
+        let spotify = vec![Token::Entry(0), Token::Spotify(1), Token::Entry(2)];
+        let youtube = vec![Token::Entry(0), Token::YouTube(1), Token::Entry(2)];
+
+        let merged = SpotitubeSyncRunner::merge_tokens(3, spotify, youtube);
+
+        assert_eq!(
+            merged.items,
+            [
+                Token::Entry(0),
+                Token::Spotify(1),
+                Token::YouTube(1),
+                Token::Entry(2)
+            ]
+        );
+        assert_eq!(
+            merged.conflicts,
+            [MergeConflict {
+                position: 1,
+                base: vec![Token::Entry(1)],
+                left: vec![Token::Spotify(1)],
+                right: vec![Token::YouTube(1)],
+            }]
+        );
+    }
+
+    #[test]
+    fn removes_a_track_removed_on_both_sides() {
+        let spotify = vec![Token::Entry(0), Token::Entry(2)];
+        let youtube = vec![Token::Entry(0), Token::Entry(2)];
+
+        let merged = SpotitubeSyncRunner::merge_tokens(3, spotify, youtube);
+
+        assert_eq!(merged.items, [Token::Entry(0), Token::Entry(2)]);
+        assert!(merged.conflicts.is_empty());
+    }
+
+    #[test]
+    fn reports_the_same_track_moved_differently_once() {
+        let spotify = vec![Token::Entry(1), Token::Entry(0), Token::Entry(2)];
+        let youtube = vec![Token::Entry(0), Token::Entry(2), Token::Entry(1)];
+
+        let merged = SpotitubeSyncRunner::merge_tokens(3, spotify, youtube);
+
+        assert_eq!(merged.items.len(), 3);
+        assert_eq!(
+            merged.items.iter().copied().collect::<HashSet<Token>>(),
+            HashSet::from([Token::Entry(0), Token::Entry(1), Token::Entry(2)])
+        );
+        assert_eq!(merged.conflicts.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn pairs_added_tracks_across_sides() {
+        let resolver = Arc::new(FakeResolver {
+            matches: HashMap::from([
+                ((Provider::Spotify, "s2"), "v2"),
+                ((Provider::Spotify, "s3"), "v3"),
+                ((Provider::YouTube, "v4"), "s4"),
+                ((Provider::YouTube, "v7"), "s7"),
+            ]),
+        }) as DynTrackResolver;
+        let synced = [entry(Some("s1"), Some("v1"))];
+        let spotify_items = [
+            item(Some("s2")),
+            item(Some("s3")),
+            item(Some("s6")),
+            item(Some("s7")),
+        ];
+        let youtube_items = [
+            item(Some("v2")),
+            item(Some("v4")),
+            item(Some("v5")),
+            item(Some("v7")),
+        ];
+        let mut entries = [
+            Token::Entry(0),
+            // the same song added on both sides
+            Token::Spotify(0),
+            Token::YouTube(0),
+            Token::Spotify(1),
+            Token::YouTube(1),
+            // found on neither side
+            Token::YouTube(2),
+            Token::Spotify(2),
+            // only found from the YouTube side
+            Token::Spotify(3),
+            Token::YouTube(3),
+        ]
+        .into_iter()
+        .map(|token| {
+            SpotitubeSyncRunner::merged_entry(token, &synced, &spotify_items, &youtube_items)
+        })
+        .collect();
+
+        SpotitubeSyncRunner::pair_added_tracks(
+            &resolver,
+            &Uuid::nil(),
+            &mut entries,
+            &spotify_items,
+            &youtube_items,
+        )
+        .await
+        .unwrap();
+
+        let pairs: Vec<(Option<&str>, Option<&str>)> = entries
+            .iter()
+            .map(|entry| (entry.spotify.as_deref(), entry.youtube.as_deref()))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                (Some("s1"), Some("v1")),
+                (Some("s2"), Some("v2")),
+                (Some("s3"), Some("v3")),
+                (Some("s4"), Some("v4")),
+                (None, Some("v5")),
+                (Some("s6"), None),
+                (Some("s7"), Some("v7")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn copies_each_copy_of_a_duplicate_added_on_one_side() {
+        let resolver = Arc::new(FakeResolver {
+            matches: HashMap::from([((Provider::Spotify, "s2"), "v2")]),
+        }) as DynTrackResolver;
+        let spotify_items = [item(Some("s2")), item(Some("s2"))];
+        let youtube_items = [item(Some("v2"))];
+        let mut entries = [Token::Spotify(0), Token::YouTube(0), Token::Spotify(1)]
+            .into_iter()
+            .map(|token| {
+                SpotitubeSyncRunner::merged_entry(token, &[], &spotify_items, &youtube_items)
+            })
+            .collect();
+
+        SpotitubeSyncRunner::pair_added_tracks(
+            &resolver,
+            &Uuid::nil(),
+            &mut entries,
+            &spotify_items,
+            &youtube_items,
+        )
+        .await
+        .unwrap();
+
+        let pairs: Vec<(Option<&str>, Option<&str>)> = entries
+            .iter()
+            .map(|entry| (entry.spotify.as_deref(), entry.youtube.as_deref()))
+            .collect();
+        assert_eq!(pairs, [(Some("s2"), Some("v2")), (Some("s2"), Some("v2"))]);
+    }
+}
@@ -0,0 +1,61 @@
+use std::{sync::Arc, time::Duration as StdDuration};
+
+use spotitube_core::{
+    config::AppConfig, errors::SpotitubeResult, syncs::repository::DynSyncsRepository,
+};
+use time::{Duration, OffsetDateTime};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
+
+/// Queues a sync job for every playlist pair whose next sync is due.
+pub struct SpotitubeSyncScheduler {
+    repository: DynSyncsRepository,
+    config: Arc<AppConfig>,
+}
+
+impl SpotitubeSyncScheduler {
+    pub fn new(repository: DynSyncsRepository, config: Arc<AppConfig>) -> Self {
+        Self { repository, config }
+    }
+
+    /// Spawns the scheduling task; it runs until `shutdown` is cancelled.
+    pub fn start(self: &Arc<Self>, shutdown: CancellationToken) -> JoinHandle<()> {
+        let scheduler = self.clone();
+        tokio::spawn(async move { scheduler.poll(shutdown).await })
+    }
+
+    async fn poll(&self, shutdown: CancellationToken) {
+        let poll_interval = StdDuration::from_secs(self.config.sync_poll_interval);
+        while !shutdown.is_cancelled() {
+            if let Err(err) = self.schedule_due_syncs().await {
+                error!("failed to schedule playlist syncs: {:?}", err);
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(poll_interval) => {}
+                _ = shutdown.cancelled() => {}
+            }
+        }
+        info!("sync scheduler stopped");
+    }
+
+    async fn schedule_due_syncs(&self) -> SpotitubeResult<()> {
+        let stale_before =
+            OffsetDateTime::now_utc() - Duration::seconds(self.config.job_visibility_timeout);
+        let syncs = self
+            .repository
+            .claim_due_syncs(
+                self.config.sync_batch_size,
+                stale_before,
+                self.config.job_max_attempts,
+            )
+            .await?;
+
+        for sync in syncs {
+            info!("queued sync of playlist pair {:?}", sync.id);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use spotitube_core::{
+    accounts::repository::DynLinkedAccountsRepository,
+    config::AppConfig,
+    errors::{SpotitubeError, SpotitubeResult},
+    syncs::{
+        repository::{DynSyncsRepository, PlaylistSyncEntity},
+        service::SyncsService,
+    },
+};
+use spotitube_domain::{
+    providers::Provider,
+    syncs::{PlaylistSyncDto, SyncConflictDto},
+};
+use time::OffsetDateTime;
+use tracing::info;
+use uuid::Uuid;
+
+pub struct SpotitubeSyncsService {
+    repository: DynSyncsRepository,
+    linked_accounts_repository: DynLinkedAccountsRepository,
+    config: Arc<AppConfig>,
+}
+
+impl SpotitubeSyncsService {
+    pub fn new(
+        repository: DynSyncsRepository,
+        linked_accounts_repository: DynLinkedAccountsRepository,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        Self {
+            repository,
+            linked_accounts_repository,
+            config,
+        }
+    }
+
+    async fn get_user_sync(
+        &self,
+        user_id: &Uuid,
+        id: &Uuid,
+    ) -> SpotitubeResult<PlaylistSyncEntity> {
+        self.repository
+            .get_sync(id)
+            .await?
+            .filter(|sync| sync.user_id == *user_id)
+            .ok_or_else(|| SpotitubeError::NotFound(String::from("sync not found")))
+    }
+
+    async fn require_linked_account(
+        &self,
+        user_id: &Uuid,
+        provider: Provider,
+    ) -> SpotitubeResult<()> {
+        match self
+            .linked_accounts_repository
+            .get_linked_account(user_id, provider.as_str())
+            .await?
+        {
+            Some(_) => Ok(()),
+            None => Err(SpotitubeError::BadRequest(format!(
+                "a {} account must be linked first",
+                provider
+            ))),
+        }
+    }
+}
+
+#[async_trait]
+impl SyncsService for SpotitubeSyncsService {
+    async fn create_sync(
+        &self,
+        user_id: &Uuid,
+        spotify_playlist_id: &str,
+        youtube_playlist_id: &str,
+        interval_seconds: Option<i64>,
+    ) -> SpotitubeResult<PlaylistSyncDto> {
+        self.require_linked_account(user_id, Provider::Spotify)
+            .await?;
+        self.require_linked_account(user_id, Provider::YouTube)
+            .await?;
+
+        let sync = self
+            .repository
+            .create_sync(
+                user_id,
+                spotify_playlist_id,
+                youtube_playlist_id,
+                interval_seconds.unwrap_or(self.config.sync_interval),
+            )
+            .await?;
+        info!(
+            "linked spotify playlist {:?} with youtube playlist {:?}",
+            spotify_playlist_id, youtube_playlist_id
+        );
+
+        Ok(sync.into_dto())
+    }
+
+    async fn list_syncs(&self, user_id: &Uuid) -> SpotitubeResult<Vec<PlaylistSyncDto>> {
+        let syncs = self.repository.get_user_syncs(user_id).await?;
+        Ok(syncs
+            .into_iter()
+            .map(PlaylistSyncEntity::into_dto)
+            .collect())
+    }
+
+    async fn get_sync(&self, user_id: &Uuid, id: &Uuid) -> SpotitubeResult<PlaylistSyncDto> {
+        Ok(self.get_user_sync(user_id, id).await?.into_dto())
+    }
+
+    async fn delete_sync(&self, user_id: &Uuid, id: &Uuid) -> SpotitubeResult<()> {
+        self.get_user_sync(user_id, id).await?;
+        self.repository.delete_sync(id).await?;
+        info!("unlinked playlist pair {:?}", id);
+        Ok(())
+    }
+
+    async fn request_sync(&self, user_id: &Uuid, id: &Uuid) -> SpotitubeResult<PlaylistSyncDto> {
+        self.get_user_sync(user_id, id).await?;
+        self.repository
+            .schedule_sync(id, OffsetDateTime::now_utc())
+            .await?;
+        Ok(self.get_user_sync(user_id, id).await?.into_dto())
+    }
+
+    async fn list_conflicts(
+        &self,
+        user_id: &Uuid,
+        id: &Uuid,
+    ) -> SpotitubeResult<Vec<SyncConflictDto>> {
+        self.get_user_sync(user_id, id).await?;
+        self.repository
+            .get_sync_conflicts(id)
+            .await?
+            .into_iter()
+            .map(|conflict| conflict.into_dto())
+            .collect()
+    }
+}
@@ -0,0 +1,302 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use spotitube_core::{
+    accounts::token_manager::DynProviderTokenManager,
+    config::AppConfig,
+    conversions::title_parser::parse_video_title,
+    errors::SpotitubeResult,
+    matching::{
+        matcher::{rank_candidates, DynTrackMatcher, MatchScore, TrackFeatures},
+        repository::{DynTrackMatchesRepository, NewTrackMatch},
+        resolver::{ResolvedTrack, SourceTrack, TrackResolution, TrackResolver},
+    },
+    spotify::{client::DynSpotifyClient, models::SpotifyTrack},
+    youtube::{client::DynYouTubeClient, models::YouTubeVideo},
+};
+use spotitube_domain::providers::Provider;
+use time::{Duration, OffsetDateTime};
+use tracing::warn;
+use uuid::Uuid;
+
+const SEARCH_RESULTS: u32 = 5;
+const MAX_TITLE_CANDIDATES: usize = 3;
+
+pub struct SpotitubeTrackResolver {
+    token_manager: DynProviderTokenManager,
+    matcher: DynTrackMatcher,
+    track_matches_repository: DynTrackMatchesRepository,
+    spotify_client: DynSpotifyClient,
+    youtube_client: DynYouTubeClient,
+    config: Arc<AppConfig>,
+}
+
+impl SpotitubeTrackResolver {
+    pub fn new(
+        token_manager: DynProviderTokenManager,
+        matcher: DynTrackMatcher,
+        track_matches_repository: DynTrackMatchesRepository,
+        spotify_client: DynSpotifyClient,
+        youtube_client: DynYouTubeClient,
+        config: Arc<AppConfig>,
+    ) -> Self {
+        Self {
+            token_manager,
+            matcher,
+            track_matches_repository,
+            spotify_client,
+            youtube_client,
+            config,
+        }
+    }
+
+    fn is_confident(&self, score: &MatchScore) -> bool {
+        score.confidence >= self.config.match_review_threshold
+    }
+
+    /// Picks the best of the ranked results when it is confident enough and remembers it.
+    async fn resolution(
+        &self,
+        source: Provider,
+        destination: Provider,
+        track: &SourceTrack<'_>,
+        ranked: Vec<(ResolvedTrack, Option<String>)>,
+    ) -> TrackResolution {
+        let Some((best, isrc)) = ranked.first() else {
+            return TrackResolution::NotFound;
+        };
+        if !self.is_confident(&best.score) {
+            return TrackResolution::Unsure(ranked.into_iter().map(|(found, _)| found).collect());
+        }
+
+        let isrc = track.isrc.or(isrc.as_deref());
+        self.cache_match(source, destination, track, isrc, best)
+            .await;
+        TrackResolution::Matched(best.clone())
+    }
+
+    /// A match chosen earlier for the same song, confirmed by the user or by enough others.
+    async fn cached_match(
+        &self,
+        user_id: &Uuid,
+        source: Provider,
+        destination: Provider,
+        track: &SourceTrack<'_>,
+    ) -> SpotitubeResult<Option<ResolvedTrack>> {
+        let Some(source_id) = track.id else {
+            return Ok(None);
+        };
+
+        let cached = self
+            .track_matches_repository
+            .find_track_match(
+                user_id,
+                self.config.match_confirmations,
+                source.as_str(),
+                source_id,
+                track.isrc,
+                destination.as_str(),
+            )
+            .await?;
+
+        Ok(cached.map(|cached| {
+            let reason = match cached.confirmed_by {
+                Some(_) => "confirmed during an earlier review",
+                None => "matched by an earlier conversion",
+            };
+            ResolvedTrack {
+                id: cached.destination_id,
+                title: cached.destination_title,
+                score: MatchScore {
+                    confidence: cached.confidence,
+                    reasons: vec![String::from(reason)],
+                },
+            }
+        }))
+    }
+
+    /// Remembers an automatic match for a while; failing to do so only costs a search later.
+    async fn cache_match(
+        &self,
+        source: Provider,
+        destination: Provider,
+        track: &SourceTrack<'_>,
+        isrc: Option<&str>,
+        resolved: &ResolvedTrack,
+    ) {
+        let Some(source_id) = track.id else {
+            return;
+        };
+
+        let expires_at =
+            OffsetDateTime::now_utc() + Duration::seconds(self.config.match_cache_lifetime);
+        if let Err(err) = self
+            .track_matches_repository
+            .upsert_track_match(NewTrackMatch {
+                source_provider: source.as_str(),
+                source_id,
+                isrc,
+                destination_provider: destination.as_str(),
+                destination_id: &resolved.id,
+                destination_title: &resolved.title,
+                confidence: resolved.score.confidence,
+                confirmed_by: None,
+                expires_at: Some(expires_at),
+            })
+            .await
+        {
+            warn!("failed to cache the match of {:?}: {:?}", source_id, err);
+        }
+    }
+
+    /// YouTube search results for the track, best match first.
+    async fn find_youtube_matches(
+        &self,
+        user_id: &Uuid,
+        track: &SourceTrack<'_>,
+    ) -> SpotitubeResult<Vec<(YouTubeVideo, MatchScore)>> {
+        let access_token = self
+            .token_manager
+            .access_token(user_id, Provider::YouTube)
+            .await?;
+
+        let query = format!("{} - {}", track.artists.join(", "), track.title);
+        let video_ids: Vec<String> = self
+            .youtube_client
+            .search_videos(&access_token, &query, SEARCH_RESULTS)
+            .await?
+            .into_iter()
+            .filter_map(|result| result.id.video_id)
+            .collect();
+        if video_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let candidates = self
+            .youtube_client
+            .get_videos(&access_token, &video_ids)
+            .await?
+            .into_iter()
+            .map(|video| {
+                let features = TrackFeatures::from_youtube_video(&video);
+                (video, features)
+            })
+            .collect();
+
+        let source = TrackFeatures {
+            title: String::from(track.title),
+            artists: track.artists.to_vec(),
+            duration_ms: track.duration_ms,
+            isrc: track.isrc.map(String::from),
+        };
+        Ok(rank_candidates(self.matcher.as_ref(), &source, candidates))
+    }
+
+    /// Searches Spotify with the artist/title guesses parsed from the video title, stopping
+    /// once a guess produces a confident match, and returns the results best match first.
+    async fn find_spotify_matches(
+        &self,
+        user_id: &Uuid,
+        track: &SourceTrack<'_>,
+    ) -> SpotitubeResult<Vec<(SpotifyTrack, MatchScore)>> {
+        let access_token = self
+            .token_manager
+            .access_token(user_id, Provider::Spotify)
+            .await?;
+
+        let channel_title = track.artists.first().map(String::as_str);
+        let source = TrackFeatures::from_video_title(track.title, channel_title, track.duration_ms);
+
+        let mut found: Vec<SpotifyTrack> = Vec::new();
+        let mut ranked = Vec::new();
+        for candidate in parse_video_title(track.title, channel_title)
+            .into_iter()
+            .take(MAX_TITLE_CANDIDATES)
+        {
+            let query = match candidate.artists.first() {
+                Some(artist) => format!("track:{} artist:{}", candidate.title, artist),
+                None => candidate.title,
+            };
+            let results = self
+                .spotify_client
+                .search_tracks(&access_token, &query, SEARCH_RESULTS)
+                .await?;
+            for result in results {
+                if result.id.is_some() && !found.iter().any(|known| known.id == result.id) {
+                    found.push(result);
+                }
+            }
+
+            let candidates = found
+                .iter()
+                .map(|spotify_track| {
+                    (
+                        spotify_track.clone(),
+                        TrackFeatures::from_spotify_track(spotify_track),
+                    )
+                })
+                .collect();
+            ranked = rank_candidates(self.matcher.as_ref(), &source, candidates);
+            if ranked
+                .first()
+                .is_some_and(|(_, score)| self.is_confident(score))
+            {
+                break;
+            }
+        }
+
+        Ok(ranked)
+    }
+}
+
+#[async_trait]
+impl TrackResolver for SpotitubeTrackResolver {
+    async fn resolve(
+        &self,
+        user_id: &Uuid,
+        source: Provider,
+        track: &SourceTrack<'_>,
+    ) -> SpotitubeResult<TrackResolution> {
+        let destination = match source {
+            Provider::Spotify => Provider::YouTube,
+            Provider::YouTube => Provider::Spotify,
+        };
+        if let Some(cached) = self
+            .cached_match(user_id, source, destination, track)
+            .await?
+        {
+            return Ok(TrackResolution::Matched(cached));
+        }
+
+        let ranked = match destination {
+            Provider::YouTube => self
+                .find_youtube_matches(user_id, track)
+                .await?
+                .into_iter()
+                .map(|(video, score)| {
+                    let resolved = ResolvedTrack {
+                        id: video.id,
+                        title: video.snippet.title,
+                        score,
+                    };
+                    (resolved, None)
+                })
+                .collect(),
+            Provider::Spotify => self
+                .find_spotify_matches(user_id, track)
+                .await?
+                .into_iter()
+                .map(|(spotify_track, score)| {
+                    let resolved = ResolvedTrack {
+                        title: spotify_track.display_title(),
+                        id: spotify_track.id.unwrap_or_default(),
+                        score,
+                    };
+                    (resolved, spotify_track.external_ids.isrc)
+                })
+                .collect(),
+        };
+
+        Ok(self.resolution(source, destination, track, ranked).await)
+    }
+}
@@ -1,20 +1,22 @@
 use async_trait::async_trait;
 use spotitube_core::{
+    auth::service::DynAuthService,
     errors::{SpotitubeError, SpotitubeResult},
     users::{repository::DynUsersRepository, service::UsersService},
     utils::{security_service::DynSecurityService, token_service::DynTokenService},
 };
 use spotitube_domain::users::{
-    requests::{LoginUserDto, RegisterUserDto},
+    requests::{LoginUserDto, RegisterUserDto, UpdateUserDto},
     UserDto,
 };
-use tracing::error;
+use tracing::{error, info};
 use uuid::Uuid;
 
 pub struct SpotitubeUsersService {
     repository: DynUsersRepository,
     security_service: DynSecurityService,
     token_service: DynTokenService,
+    auth_service: DynAuthService,
 }
 
 impl SpotitubeUsersService {
@@ -22,13 +24,30 @@ impl SpotitubeUsersService {
         repository: DynUsersRepository,
         security_service: DynSecurityService,
         token_service: DynTokenService,
+        auth_service: DynAuthService,
     ) -> Self {
         Self {
             repository,
             security_service,
             token_service,
+            auth_service,
         }
     }
+
+    async fn rehash_password(
+        &self,
+        user_id: &Uuid,
+        username: &str,
+        password: &str,
+    ) -> SpotitubeResult<()> {
+        let hashed_password = self.security_service.hash_password(password).await?;
+        self.repository
+            .update_user(user_id, username, &hashed_password, false)
+            .await?;
+
+        info!("rehashed password for user {:?}", user_id);
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -45,7 +64,7 @@ impl UsersService for SpotitubeUsersService {
             return Err(SpotitubeError::Conflict(String::from("username is taken")));
         };
 
-        let hashed_password = self.security_service.hash_password(&password)?;
+        let hashed_password = self.security_service.hash_password(&password).await?;
         let created_user = self
             .repository
             .create_user(&username, &hashed_password)
@@ -54,8 +73,14 @@ impl UsersService for SpotitubeUsersService {
         let token = self
             .token_service
             .new_token(&created_user.id, &created_user.username)?;
+        let refresh_token = self
+            .auth_service
+            .issue_refresh_token(&created_user.id)
+            .await?;
 
-        Ok(created_user.into_dto(token))
+        let mut user = created_user.into_dto(token);
+        user.refresh_token = Some(refresh_token);
+        Ok(user)
     }
     async fn login_user(&self, request: LoginUserDto) -> SpotitubeResult<UserDto> {
         let username = request.username.unwrap();
@@ -71,11 +96,28 @@ impl UsersService for SpotitubeUsersService {
 
         let is_valid_password = self
             .security_service
-            .verify_password(&user.password, &attempted_password)?;
+            .verify_password(&user.password, &attempted_password)
+            .await?;
 
         if is_valid_password {
+            if self.security_service.needs_rehash(&user.password) {
+                if let Err(err) = self
+                    .rehash_password(&user.id, &user.username, &attempted_password)
+                    .await
+                {
+                    error!(
+                        "failed to rehash password for user {:?}: {:?}",
+                        user.id, err
+                    );
+                }
+            }
+
             let token = self.token_service.new_token(&user.id, &user.username)?;
-            Ok(user.into_dto(token))
+            let refresh_token = self.auth_service.issue_refresh_token(&user.id).await?;
+
+            let mut user = user.into_dto(token);
+            user.refresh_token = Some(refresh_token);
+            Ok(user)
         } else {
             Err(SpotitubeError::InvalidPassword)
         }
@@ -87,4 +129,44 @@ impl UsersService for SpotitubeUsersService {
 
         Ok(user.into_dto(token))
     }
+
+    async fn update_user(
+        &self,
+        user_id: &Uuid,
+        request: UpdateUserDto,
+    ) -> SpotitubeResult<UserDto> {
+        let user = self.repository.get_user_by_id(user_id).await?;
+
+        let username = match request.username {
+            Some(username) if username != user.username => {
+                if self
+                    .repository
+                    .get_user_by_username(&username)
+                    .await?
+                    .is_some()
+                {
+                    error!("user with username {:?} already exists", username);
+                    return Err(SpotitubeError::Conflict(String::from("username is taken")));
+                }
+                username
+            }
+            _ => user.username,
+        };
+
+        // a new password signs out every other session
+        let (hashed_password, revoke_refresh_tokens) = match request.password {
+            Some(password) => (self.security_service.hash_password(&password).await?, true),
+            None => (user.password, false),
+        };
+
+        let updated_user = self
+            .repository
+            .update_user(user_id, &username, &hashed_password, revoke_refresh_tokens)
+            .await?;
+        let token = self
+            .token_service
+            .new_token(&updated_user.id, &updated_user.username)?;
+
+        Ok(updated_user.into_dto(token))
+    }
 }
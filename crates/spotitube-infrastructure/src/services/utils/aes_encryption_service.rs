@@ -0,0 +1,338 @@
+use std::{collections::HashMap, sync::Arc};
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine};
+use spotitube_core::{
+    config::AppConfig,
+    errors::{SpotitubeError, SpotitubeResult},
+    utils::encryption_service::EncryptionService,
+};
+use tracing::{error, info};
+
+const SEALED_PREFIX: &str = "enc";
+const SEPARATOR: char = ':';
+const KEY_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 12;
+
+/// Envelope encryption with AES-256-GCM: every value is sealed with a fresh data key, which is
+/// in turn wrapped by a versioned key encryption key. Sealed values are formatted as
+/// `enc:<key version>:<wrapped data key>:<ciphertext>` so older key versions can still be
+/// opened after rotation.
+pub struct AesEncryptionService {
+    active_version: String,
+    keys: HashMap<String, Aes256Gcm>,
+}
+
+impl AesEncryptionService {
+    pub fn new(config: Arc<AppConfig>) -> SpotitubeResult<Self> {
+        let mut keys = HashMap::new();
+        for entry in &config.encryption_keys {
+            let (version, encoded_key) = entry.split_once('=').ok_or_else(|| {
+                error!("encryption key must be formatted as <version>=<base64 key>");
+                SpotitubeError::AppStartup
+            })?;
+            if version.is_empty() || version.contains(SEPARATOR) {
+                error!("invalid encryption key version {:?}", version);
+                return Err(SpotitubeError::AppStartup);
+            }
+
+            let key = STANDARD_NO_PAD
+                .decode(encoded_key.trim_end_matches('='))
+                .ok()
+                .filter(|key| key.len() == KEY_LENGTH)
+                .ok_or_else(|| {
+                    error!(
+                        "encryption key {:?} must be {} base64 encoded bytes",
+                        version, KEY_LENGTH
+                    );
+                    SpotitubeError::AppStartup
+                })?;
+            let cipher = Aes256Gcm::new_from_slice(&key).map_err(|_| SpotitubeError::AppStartup)?;
+            keys.insert(String::from(version), cipher);
+        }
+
+        if keys.is_empty() {
+            error!("encryption_keys is required to store provider tokens");
+            return Err(SpotitubeError::AppStartup);
+        }
+
+        let active_version = match &config.encryption_key_version {
+            Some(version) => version.clone(),
+            None if keys.len() == 1 => keys.keys().next().cloned().unwrap_or_default(),
+            None => {
+                error!("encryption_key_version is required when several keys are configured");
+                return Err(SpotitubeError::AppStartup);
+            }
+        };
+        if !keys.contains_key(&active_version) {
+            error!(
+                "no encryption key configured for version {:?}",
+                active_version
+            );
+            return Err(SpotitubeError::AppStartup);
+        }
+
+        info!(
+            "loaded {} encryption keys, sealing with {:?}",
+            keys.len(),
+            active_version
+        );
+        Ok(Self {
+            active_version,
+            keys,
+        })
+    }
+
+    fn key_version(value: &str) -> Option<&str> {
+        let mut parts = value.splitn(3, SEPARATOR);
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(SEALED_PREFIX), Some(version), Some(_)) => Some(version),
+            _ => None,
+        }
+    }
+
+    fn seal(nonce: &[u8], ciphertext: Vec<u8>) -> String {
+        let mut sealed = Vec::with_capacity(nonce.len() + ciphertext.len());
+        sealed.extend_from_slice(nonce);
+        sealed.extend(ciphertext);
+        STANDARD_NO_PAD.encode(sealed)
+    }
+
+    fn unseal(encoded: &str) -> SpotitubeResult<(Vec<u8>, Vec<u8>)> {
+        let mut sealed = STANDARD_NO_PAD
+            .decode(encoded)
+            .map_err(|_| Self::corrupted())?;
+        if sealed.len() <= NONCE_LENGTH {
+            return Err(Self::corrupted());
+        }
+
+        let ciphertext = sealed.split_off(NONCE_LENGTH);
+        Ok((sealed, ciphertext))
+    }
+
+    fn corrupted() -> SpotitubeError {
+        error!("encrypted value is corrupted or was sealed with a different key");
+        SpotitubeError::InternalServerError
+    }
+}
+
+impl EncryptionService for AesEncryptionService {
+    fn encrypt(&self, plaintext: &str) -> SpotitubeResult<String> {
+        let key_cipher = &self.keys[&self.active_version];
+
+        let data_key = Aes256Gcm::generate_key(OsRng);
+        let data_nonce = Aes256Gcm::generate_nonce(OsRng);
+        let ciphertext = Aes256Gcm::new(&data_key)
+            .encrypt(&data_nonce, plaintext.as_bytes())
+            .map_err(|_| SpotitubeError::InternalServerError)?;
+
+        let key_nonce = Aes256Gcm::generate_nonce(OsRng);
+        let wrapped_key = key_cipher
+            .encrypt(
+                &key_nonce,
+                Payload {
+                    msg: data_key.as_slice(),
+                    aad: self.active_version.as_bytes(),
+                },
+            )
+            .map_err(|_| SpotitubeError::InternalServerError)?;
+
+        Ok(format!(
+            "{}{sep}{}{sep}{}{sep}{}",
+            SEALED_PREFIX,
+            self.active_version,
+            Self::seal(&key_nonce, wrapped_key),
+            Self::seal(&data_nonce, ciphertext),
+            sep = SEPARATOR
+        ))
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> SpotitubeResult<String> {
+        let parts: Vec<&str> = ciphertext.split(SEPARATOR).collect();
+        let [SEALED_PREFIX, version, wrapped_key, payload] = parts[..] else {
+            return Err(Self::corrupted());
+        };
+
+        let key_cipher = self.keys.get(version).ok_or_else(|| {
+            error!("no encryption key configured for version {:?}", version);
+            SpotitubeError::InternalServerError
+        })?;
+
+        let (key_nonce, wrapped_key) = Self::unseal(wrapped_key)?;
+        let data_key = key_cipher
+            .decrypt(
+                Nonce::from_slice(&key_nonce),
+                Payload {
+                    msg: &wrapped_key,
+                    aad: version.as_bytes(),
+                },
+            )
+            .map_err(|_| Self::corrupted())?;
+
+        let (data_nonce, payload) = Self::unseal(payload)?;
+        let plaintext = Aes256Gcm::new_from_slice(&data_key)
+            .map_err(|_| Self::corrupted())?
+            .decrypt(Nonce::from_slice(&data_nonce), payload.as_slice())
+            .map_err(|_| Self::corrupted())?;
+
+        String::from_utf8(plaintext).map_err(|_| Self::corrupted())
+    }
+
+    fn needs_reencryption(&self, value: &str) -> bool {
+        Self::key_version(value) != Some(self.active_version.as_str())
+    }
+
+    fn reencrypt(&self, value: &str) -> SpotitubeResult<String> {
+        match Self::key_version(value) {
+            Some(_) => self.encrypt(&self.decrypt(value)?),
+            None => self.encrypt(value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine};
+    use spotitube_core::{errors::SpotitubeError, utils::encryption_service::EncryptionService};
+
+    use super::AesEncryptionService;
+    use crate::clients::test_support::stub_config;
+
+    /// Service holding a key of repeated `byte`s for each version, sealing with `active`.
+    fn service(versions: &[(&str, u8)], active: &str) -> AesEncryptionService {
+        let keys: Vec<String> = versions
+            .iter()
+            .map(|(version, byte)| format!("{}={}", version, STANDARD_NO_PAD.encode([*byte; 32])))
+            .collect();
+        let args = [
+            format!("--encryption-keys={}", keys.join(",")),
+            format!("--encryption-key-version={}", active),
+        ];
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        AesEncryptionService::new(stub_config("http://localhost", &args)).unwrap()
+    }
+
+    /// Flips the last byte of one of the sealed parts of `value`.
+    fn tamper(value: &str, part: usize) -> String {
+        let mut parts: Vec<String> = value.split(':').map(String::from).collect();
+        let mut sealed = STANDARD_NO_PAD.decode(&parts[part]).unwrap();
+        *sealed.last_mut().unwrap() ^= 1;
+        parts[part] = STANDARD_NO_PAD.encode(sealed);
+        parts.join(":")
+    }
+
+    #[test]
+    fn decrypts_what_it_encrypted() {
+        let service = service(&[("v1", 1)], "v1");
+
+        let sealed = service.encrypt("refresh-token").unwrap();
+
+        assert!(sealed.starts_with("enc:v1:"));
+        assert!(!sealed.contains("refresh-token"));
+        assert_ne!(service.encrypt("refresh-token").unwrap(), sealed);
+        assert_eq!(service.decrypt(&sealed).unwrap(), "refresh-token");
+    }
+
+    #[test]
+    fn decrypts_values_sealed_with_an_older_key_version() {
+        let sealed = service(&[("v1", 1)], "v1")
+            .encrypt("refresh-token")
+            .unwrap();
+
+        let rotated = service(&[("v1", 1), ("v2", 2)], "v2");
+
+        assert_eq!(rotated.decrypt(&sealed).unwrap(), "refresh-token");
+    }
+
+    #[test]
+    fn rejects_values_sealed_with_a_removed_key_version() {
+        let sealed = service(&[("v1", 1)], "v1")
+            .encrypt("refresh-token")
+            .unwrap();
+
+        let rotated = service(&[("v2", 2)], "v2");
+
+        assert!(matches!(
+            rotated.decrypt(&sealed),
+            Err(SpotitubeError::InternalServerError)
+        ));
+    }
+
+    #[test]
+    fn reencrypts_older_and_plaintext_values_with_the_active_key() {
+        let sealed = service(&[("v1", 1)], "v1")
+            .encrypt("refresh-token")
+            .unwrap();
+        let rotated = service(&[("v1", 1), ("v2", 2)], "v2");
+
+        assert!(rotated.needs_reencryption(&sealed));
+        assert!(rotated.needs_reencryption("plaintext-token"));
+
+        let resealed = rotated.reencrypt(&sealed).unwrap();
+        assert!(resealed.starts_with("enc:v2:"));
+        assert!(!rotated.needs_reencryption(&resealed));
+        assert_eq!(rotated.decrypt(&resealed).unwrap(), "refresh-token");
+
+        let sealed_plaintext = rotated.reencrypt("plaintext-token").unwrap();
+        assert!(!rotated.needs_reencryption(&sealed_plaintext));
+        assert_eq!(
+            rotated.decrypt(&sealed_plaintext).unwrap(),
+            "plaintext-token"
+        );
+    }
+
+    #[test]
+    fn rejects_a_tampered_wrapped_key_or_ciphertext() {
+        let service = service(&[("v1", 1)], "v1");
+        let sealed = service.encrypt("refresh-token").unwrap();
+
+        for part in [2, 3] {
+            assert!(matches!(
+                service.decrypt(&tamper(&sealed, part)),
+                Err(SpotitubeError::InternalServerError)
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_a_value_sealed_under_another_version_label() {
+        let service = service(&[("v1", 1), ("v2", 1)], "v1");
+        let sealed = service.encrypt("refresh-token").unwrap();
+
+        let relabeled = sealed.replacen("enc:v1:", "enc:v2:", 1);
+
+        assert!(matches!(
+            service.decrypt(&relabeled),
+            Err(SpotitubeError::InternalServerError)
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_values_without_panicking() {
+        let service = service(&[("v1", 1)], "v1");
+
+        for value in [
+            "",
+            "plaintext-token",
+            "enc:",
+            "enc:v1",
+            "enc:v1:AAAA",
+            "enc:v1:not base64:AAAA",
+            "enc:v1:AAAA:AAAA",
+            "enc:v1:AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA:AAAA:extra",
+        ] {
+            assert!(
+                matches!(
+                    service.decrypt(value),
+                    Err(SpotitubeError::InternalServerError)
+                ),
+                "{:?} was not rejected",
+                value
+            );
+        }
+    }
+}
@@ -1,36 +1,117 @@
 use std::sync::Arc;
 
-use argon2::Config;
+use argon2::{Config, Variant, Version};
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine};
+use rand::{rngs::OsRng, RngCore};
 use spotitube_core::{
-    config::AppConfig, errors::SpotitubeResult, utils::security_service::SecurityService,
+    config::AppConfig,
+    errors::{SpotitubeError, SpotitubeResult},
+    utils::security_service::SecurityService,
 };
+use tokio::{sync::Semaphore, task};
+use tracing::error;
+
+const SALT_LENGTH: usize = 16;
 
 pub struct ArgonSecurityService {
     config: Arc<AppConfig>,
+    permits: Semaphore,
 }
 
 impl ArgonSecurityService {
     pub fn new(config: Arc<AppConfig>) -> Self {
-        Self { config }
+        let permits = Semaphore::new(config.argon_max_concurrency.max(1));
+        Self { config, permits }
+    }
+
+    fn argon_config(config: &AppConfig) -> Config<'static> {
+        Config {
+            variant: Variant::Argon2id,
+            version: Version::Version13,
+            mem_cost: config.argon_memory_cost,
+            time_cost: config.argon_iterations,
+            lanes: config.argon_parallelism,
+            ..Config::default()
+        }
+    }
+
+    /// Runs an Argon2 computation on the blocking pool, waiting for a permit first so that
+    /// bursts of hashing cannot exhaust it.
+    async fn run_blocking<T, F>(&self, f: F) -> SpotitubeResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> SpotitubeResult<T> + Send + 'static,
+    {
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .map_err(|_| SpotitubeError::InternalServerError)?;
+
+        task::spawn_blocking(f).await.map_err(|err| {
+            error!("password hashing task failed: {:?}", err);
+            SpotitubeError::InternalServerError
+        })?
     }
 }
 
+#[async_trait]
 impl SecurityService for ArgonSecurityService {
-    fn hash_password(&self, raw_password: &str) -> SpotitubeResult<String> {
-        let hashed_password = argon2::hash_encoded(
-            raw_password.as_bytes(),
-            self.config.argon_salt.as_bytes(),
-            &Config::default(),
-        )?;
-        Ok(hashed_password)
+    async fn hash_password(&self, raw_password: &str) -> SpotitubeResult<String> {
+        let raw_password = String::from(raw_password);
+        let config = Self::argon_config(&self.config);
+
+        self.run_blocking(move || {
+            let mut salt = [0u8; SALT_LENGTH];
+            OsRng.fill_bytes(&mut salt);
+
+            let hashed_password = argon2::hash_encoded(raw_password.as_bytes(), &salt, &config)?;
+            Ok(hashed_password)
+        })
+        .await
     }
 
-    fn verify_password(
+    async fn verify_password(
         &self,
         stored_password: &str,
         attempted_password: &str,
     ) -> SpotitubeResult<bool> {
-        let hashes_match = argon2::verify_encoded(stored_password, attempted_password.as_bytes())?;
-        Ok(hashes_match)
+        let stored_password = String::from(stored_password);
+        let attempted_password = String::from(attempted_password);
+
+        self.run_blocking(move || {
+            let hashes_match =
+                argon2::verify_encoded(&stored_password, attempted_password.as_bytes())?;
+            Ok(hashes_match)
+        })
+        .await
+    }
+
+    fn needs_rehash(&self, stored_password: &str) -> bool {
+        // $argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>
+        let parts: Vec<&str> = stored_password.split('$').collect();
+        let [_, variant, version, params, salt, _] = parts.as_slice() else {
+            return true;
+        };
+
+        let config = Self::argon_config(&self.config);
+        let expected_params = format!(
+            "m={},t={},p={}",
+            config.mem_cost, config.time_cost, config.lanes
+        );
+        if *variant != config.variant.as_lowercase_str()
+            || *version != format!("v={}", config.version.as_u32())
+            || *params != expected_params
+        {
+            return true;
+        }
+
+        // hashes created before per-user salts all share the legacy global salt
+        match (&self.config.argon_salt, STANDARD_NO_PAD.decode(salt)) {
+            (Some(legacy_salt), Ok(salt)) => salt == legacy_salt.as_bytes(),
+            (_, Err(_)) => true,
+            (None, Ok(_)) => false,
+        }
     }
 }
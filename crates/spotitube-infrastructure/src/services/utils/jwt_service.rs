@@ -1,52 +1,392 @@
-use std::{str::FromStr, sync::Arc};
+use std::{collections::BTreeMap, fs, str::FromStr, sync::Arc};
 
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{
+    decode, decode_header, encode,
+    jwk::{
+        AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm,
+        OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+    },
+    Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
+use rsa::{
+    pkcs1::DecodeRsaPublicKey,
+    pkcs8::{
+        der::DecodePem,
+        spki::{ObjectIdentifier, SubjectPublicKeyInfoOwned},
+        DecodePublicKey,
+    },
+    traits::PublicKeyParts,
+    RsaPublicKey,
+};
 use serde::{Deserialize, Serialize};
 use spotitube_core::{
-    config::AppConfig, errors::SpotitubeResult, utils::token_service::TokenService,
+    config::AppConfig,
+    errors::{SpotitubeError, SpotitubeResult},
+    utils::token_service::TokenService,
 };
+use time::{Duration, OffsetDateTime};
+use tracing::{error, info};
 use uuid::Uuid;
 
+const ED25519_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+
 #[derive(Debug, Serialize, Deserialize)]
 struct Claims {
     sub: String,
     user_id: String,
+    iat: i64,
+    exp: i64,
+    jti: String,
+}
+
+struct VerificationKey {
+    algorithm: Algorithm,
+    decoding_key: DecodingKey,
+    jwk: Jwk,
 }
 
 pub struct JwtService {
     config: Arc<AppConfig>,
+    algorithm: Algorithm,
+    key_id: Option<String>,
+    encoding_key: EncodingKey,
+    verification_keys: BTreeMap<String, VerificationKey>,
 }
 
 impl JwtService {
-    pub fn new(config: Arc<AppConfig>) -> Self {
-        Self { config }
+    pub fn new(config: Arc<AppConfig>) -> SpotitubeResult<Self> {
+        let algorithm = Algorithm::from_str(&config.token_algorithm).map_err(|err| {
+            error!(
+                "invalid token algorithm {:?}: {:?}",
+                config.token_algorithm, err
+            );
+            SpotitubeError::AppStartup
+        })?;
+
+        let mut verification_keys = BTreeMap::new();
+        for entry in &config.token_public_keys {
+            let (key_id, path) = entry.split_once('=').ok_or_else(|| {
+                error!("public key {:?} must be formatted as <kid>=<path>", entry);
+                SpotitubeError::AppStartup
+            })?;
+            let verification_key = Self::load_verification_key(key_id, path)?;
+            info!(
+                "loaded {:?} verification key {:?}",
+                verification_key.algorithm, key_id
+            );
+            verification_keys.insert(String::from(key_id), verification_key);
+        }
+
+        let encoding_key = match algorithm {
+            Algorithm::HS256 => EncodingKey::from_secret(config.token_secret.as_bytes()),
+            Algorithm::RS256 | Algorithm::EdDSA => {
+                let key_id = config.token_key_id.as_deref().ok_or_else(|| {
+                    error!("token_key_id is required for {:?} signing", algorithm);
+                    SpotitubeError::AppStartup
+                })?;
+                match verification_keys.get(key_id) {
+                    Some(key) if key.algorithm == algorithm => {}
+                    _ => {
+                        error!(
+                            "signing key {:?} has no matching {:?} public key",
+                            key_id, algorithm
+                        );
+                        return Err(SpotitubeError::AppStartup);
+                    }
+                }
+
+                let path = config.token_private_key.as_deref().ok_or_else(|| {
+                    error!("token_private_key is required for {:?} signing", algorithm);
+                    SpotitubeError::AppStartup
+                })?;
+                let pem = Self::read_key_file(path)?;
+                let encoding_key = if algorithm == Algorithm::RS256 {
+                    EncodingKey::from_rsa_pem(&pem)
+                } else {
+                    EncodingKey::from_ed_pem(&pem)
+                };
+                encoding_key.map_err(|err| {
+                    error!("failed to load private key {:?}: {:?}", path, err);
+                    SpotitubeError::AppStartup
+                })?
+            }
+            _ => {
+                error!("unsupported token algorithm {:?}", algorithm);
+                return Err(SpotitubeError::AppStartup);
+            }
+        };
+
+        let key_id = match algorithm {
+            Algorithm::HS256 => None,
+            _ => config.token_key_id.clone(),
+        };
+
+        Ok(Self {
+            config,
+            algorithm,
+            key_id,
+            encoding_key,
+            verification_keys,
+        })
+    }
+
+    fn read_key_file(path: &str) -> SpotitubeResult<Vec<u8>> {
+        fs::read(path).map_err(|err| {
+            error!("failed to read key file {:?}: {:?}", path, err);
+            SpotitubeError::AppStartup
+        })
+    }
+
+    fn load_verification_key(key_id: &str, path: &str) -> SpotitubeResult<VerificationKey> {
+        let pem = Self::read_key_file(path)?;
+        let pem_str = String::from_utf8_lossy(&pem);
+
+        let rsa_key = RsaPublicKey::from_public_key_pem(&pem_str)
+            .or_else(|_| RsaPublicKey::from_pkcs1_pem(&pem_str));
+        if let Ok(rsa_key) = rsa_key {
+            let decoding_key = DecodingKey::from_rsa_pem(&pem)?;
+            let parameters = AlgorithmParameters::RSA(RSAKeyParameters {
+                key_type: RSAKeyType::RSA,
+                n: URL_SAFE_NO_PAD.encode(rsa_key.n().to_bytes_be()),
+                e: URL_SAFE_NO_PAD.encode(rsa_key.e().to_bytes_be()),
+            });
+            return Ok(VerificationKey {
+                algorithm: Algorithm::RS256,
+                decoding_key,
+                jwk: Self::new_jwk(key_id, KeyAlgorithm::RS256, parameters),
+            });
+        }
+
+        let public_key_info = SubjectPublicKeyInfoOwned::from_pem(&pem).map_err(|err| {
+            error!("failed to parse public key {:?}: {:?}", path, err);
+            SpotitubeError::AppStartup
+        })?;
+        let public_key = public_key_info.subject_public_key.as_bytes();
+        match public_key {
+            Some(public_key)
+                if public_key_info.algorithm.oid == ED25519_OID && public_key.len() == 32 =>
+            {
+                let parameters = AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    key_type: OctetKeyPairType::OctetKeyPair,
+                    curve: EllipticCurve::Ed25519,
+                    x: URL_SAFE_NO_PAD.encode(public_key),
+                });
+                Ok(VerificationKey {
+                    algorithm: Algorithm::EdDSA,
+                    decoding_key: DecodingKey::from_ed_der(public_key),
+                    jwk: Self::new_jwk(key_id, KeyAlgorithm::EdDSA, parameters),
+                })
+            }
+            _ => {
+                error!("public key {:?} is neither RSA nor Ed25519", path);
+                Err(SpotitubeError::AppStartup)
+            }
+        }
+    }
+
+    fn new_jwk(key_id: &str, algorithm: KeyAlgorithm, parameters: AlgorithmParameters) -> Jwk {
+        Jwk {
+            common: CommonParameters {
+                public_key_use: Some(PublicKeyUse::Signature),
+                key_algorithm: Some(algorithm),
+                key_id: Some(String::from(key_id)),
+                ..Default::default()
+            },
+            algorithm: parameters,
+        }
     }
 }
 
 impl TokenService for JwtService {
     fn new_token(&self, user_id: &Uuid, username: &str) -> SpotitubeResult<String> {
+        let issued_at = OffsetDateTime::now_utc();
+        let expires_at = issued_at + Duration::seconds(self.config.access_token_lifetime);
         let claims = Claims {
             sub: String::from(username),
             user_id: user_id.to_string(),
+            iat: issued_at.unix_timestamp(),
+            exp: expires_at.unix_timestamp(),
+            jti: Uuid::new_v4().to_string(),
         };
 
-        let token = encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(self.config.token_secret.as_bytes()),
-        )?;
+        let mut header = Header::new(self.algorithm);
+        header.kid.clone_from(&self.key_id);
+
+        let token = encode(&header, &claims, &self.encoding_key)?;
 
         Ok(token)
     }
 
     fn get_user_id_from_token(&self, token: String) -> SpotitubeResult<Uuid> {
-        let decoded_token = decode::<Claims>(
-            token.as_str(),
-            &DecodingKey::from_secret(self.config.token_secret.as_bytes()),
-            &Validation::new(Algorithm::HS256),
-        )?;
+        let header = decode_header(&token)?;
+
+        let decoded_token = match header.kid {
+            Some(key_id) => {
+                let key = self
+                    .verification_keys
+                    .get(&key_id)
+                    .ok_or(SpotitubeError::Unauthorized)?;
+                decode::<Claims>(
+                    token.as_str(),
+                    &key.decoding_key,
+                    &Validation::new(key.algorithm),
+                )?
+            }
+            None if self.algorithm == Algorithm::HS256 => decode::<Claims>(
+                token.as_str(),
+                &DecodingKey::from_secret(self.config.token_secret.as_bytes()),
+                &Validation::new(Algorithm::HS256),
+            )?,
+            None => return Err(SpotitubeError::Unauthorized),
+        };
 
         let user_id = Uuid::from_str(&decoded_token.claims.user_id)?;
         Ok(user_id)
     }
+
+    fn jwk_set(&self) -> JwkSet {
+        JwkSet {
+            keys: self
+                .verification_keys
+                .values()
+                .map(|key| key.jwk.clone())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use spotitube_core::{errors::SpotitubeError, utils::token_service::TokenService};
+    use uuid::Uuid;
+
+    use super::JwtService;
+    use crate::clients::test_support::stub_config;
+
+    fn key_file(name: &str) -> String {
+        format!(
+            "{}/tests/fixtures/jwt/{}.pem",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        )
+    }
+
+    /// Service signing with `signing_key` and trusting the public keys of `verification_keys`.
+    fn service(algorithm: &str, signing_key: &str, verification_keys: &[&str]) -> JwtService {
+        let public_keys: Vec<String> = verification_keys
+            .iter()
+            .map(|key_id| format!("{}={}", key_id, key_file(&format!("{}.pub", key_id))))
+            .collect();
+        let args = [
+            format!("--token-algorithm={}", algorithm),
+            format!("--token-key-id={}", signing_key),
+            format!("--token-private-key={}", key_file(signing_key)),
+            format!("--token-public-keys={}", public_keys.join(",")),
+        ];
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        JwtService::new(stub_config("http://localhost", &args)).unwrap()
+    }
+
+    #[test]
+    fn signs_and_verifies_rs256_tokens() {
+        let service = service("RS256", "rs-1", &["rs-1"]);
+        let user_id = Uuid::new_v4();
+
+        let token = service.new_token(&user_id, "user").unwrap();
+
+        assert_eq!(service.get_user_id_from_token(token).unwrap(), user_id);
+    }
+
+    #[test]
+    fn signs_and_verifies_eddsa_tokens() {
+        let service = service("EdDSA", "ed-1", &["ed-1"]);
+        let user_id = Uuid::new_v4();
+
+        let token = service.new_token(&user_id, "user").unwrap();
+
+        assert_eq!(service.get_user_id_from_token(token).unwrap(), user_id);
+    }
+
+    #[test]
+    fn verifies_tokens_signed_before_a_key_rotation() {
+        let user_id = Uuid::new_v4();
+        let token = service("RS256", "rs-1", &["rs-1"])
+            .new_token(&user_id, "user")
+            .unwrap();
+
+        let rotated = service("EdDSA", "ed-1", &["rs-1", "ed-1"]);
+
+        assert_eq!(rotated.get_user_id_from_token(token).unwrap(), user_id);
+        let token = rotated.new_token(&user_id, "user").unwrap();
+        assert_eq!(rotated.get_user_id_from_token(token).unwrap(), user_id);
+    }
+
+    #[test]
+    fn rejects_tokens_signed_with_an_unknown_key() {
+        let token = service("RS256", "rs-1", &["rs-1"])
+            .new_token(&Uuid::new_v4(), "user")
+            .unwrap();
+
+        let rotated = service("RS256", "rs-2", &["rs-2"]);
+
+        assert!(matches!(
+            rotated.get_user_id_from_token(token),
+            Err(SpotitubeError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn rejects_tokens_signed_by_another_key_under_a_known_id() {
+        let forged = service("RS256", "rs-2", &["rs-2"])
+            .new_token(&Uuid::new_v4(), "user")
+            .unwrap();
+        let service = service("RS256", "rs-1", &["rs-1"]);
+        let token = service.new_token(&Uuid::new_v4(), "user").unwrap();
+
+        let (header, _) = token.split_once('.').unwrap();
+        let (_, claims_and_signature) = forged.split_once('.').unwrap();
+        let forged = format!("{}.{}", header, claims_and_signature);
+
+        assert!(matches!(
+            service.get_user_id_from_token(forged),
+            Err(SpotitubeError::JwtError(_))
+        ));
+    }
+
+    #[test]
+    fn publishes_every_verification_key_as_a_jwk() {
+        let service = service("EdDSA", "ed-1", &["ed-1", "rs-1"]);
+
+        let jwks = serde_json::to_value(service.jwk_set()).unwrap();
+
+        let keys = jwks["keys"].as_array().unwrap();
+        assert_eq!(keys.len(), 2);
+        let ed = &keys[0];
+        assert_eq!(ed["kid"], "ed-1");
+        assert_eq!(ed["kty"], "OKP");
+        assert_eq!(ed["crv"], "Ed25519");
+        assert_eq!(ed["alg"], "EdDSA");
+        assert_eq!(ed["use"], "sig");
+        assert_eq!(ed["x"].as_str().unwrap().len(), 43);
+        assert!(ed.get("d").is_none());
+        let rs = &keys[1];
+        assert_eq!(rs["kid"], "rs-1");
+        assert_eq!(rs["kty"], "RSA");
+        assert_eq!(rs["alg"], "RS256");
+        assert_eq!(rs["use"], "sig");
+        assert_eq!(rs["e"], "AQAB");
+        assert_eq!(rs["n"].as_str().unwrap().len(), 342);
+    }
+
+    #[test]
+    fn does_not_publish_keys_for_shared_secrets() {
+        let service = JwtService::new(stub_config("http://localhost", &[])).unwrap();
+
+        assert!(service.jwk_set().keys.is_empty());
+        let user_id = Uuid::new_v4();
+        let token = service.new_token(&user_id, "user").unwrap();
+        assert_eq!(service.get_user_id_from_token(token).unwrap(), user_id);
+    }
 }
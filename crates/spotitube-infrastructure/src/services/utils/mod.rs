@@ -1,2 +1,3 @@
+pub mod aes_encryption_service;
 pub mod argon_security_service;
 pub mod jwt_service;